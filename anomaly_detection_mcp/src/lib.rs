@@ -7,13 +7,42 @@ use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
 use weil_rs::runtime::Runtime;
 
+mod fuzzy_match;
+mod http_fixtures;
+mod outbound_guard;
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
+mod trade_data;
+use trade_data::{TradeDataMcp, Trade};
+mod upsi_database;
+use upsi_database::UpsiDatabaseMcp;
+
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
 pub struct AnomalyDetectionConfig {
     pub dashboard_contract_id: String,
+    pub trade_data_contract_id: String,
     pub alpha_vantage_key: String,
     pub taapi_secret: String,
+    /// Index or sector ETF ticker detect_pump_dump compares a symbol's move
+    /// against, so a move on a day the whole market is up 5% doesn't get flagged
+    /// on its own
+    pub benchmark_symbol: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert/upsert_case's
+    /// caller_token
+    pub dashboard_caller_token: String,
+    /// Source for evaluate_rules' is_insider/window_closed features via
+    /// check_insider_status
+    pub entity_relationship_contract_id: String,
+    /// Source for detect_coordinated_trading's UPSI accessor/access-window
+    /// lookup via get_upsi/get_upsi_accessors
+    pub upsi_database_contract_id: String,
+    /// "live" (default): call Alpha Vantage/TAAPI for real. "record": call for
+    /// real and save the response as a fixture. "playback": skip the network
+    /// and return the previously recorded fixture, erroring if none exists -
+    /// see http_fixtures for the whole scheme
+    pub http_fixture_mode: String,
 }
 
 // ===== DATA STRUCTURES =====
@@ -27,6 +56,10 @@ pub struct AnomalyResult {
     pub details: String,
     pub timestamp: u64,
     pub supporting_evidence: String,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -36,6 +69,34 @@ pub struct SpoofingIndicator {
     pub cancellation_rate: String,
     pub order_size_vs_market: String,
     pub price_impact: String,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// order_to_trade_ratio and short_lived_order_pct describe raw order-message
+/// behavior (new/cancel/modify), but this platform only ingests executed trades -
+/// no order or cancellation events are available. message_rate and
+/// order_to_trade_ratio are approximated from executed-trade frequency and the
+/// count of distinct order_ids per trade (so they will under-count relative to a
+/// detector fed real order-book messages); short_lived_order_pct needs order
+/// lifetime (submit-to-cancel/fill), which isn't derivable at all from trade data
+/// alone, so it's always "0.00".
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct QuoteStuffingIndicator {
+    pub symbol: String,
+    pub window_seconds: u64,
+    pub message_count: u32,
+    pub message_rate: String,
+    pub order_to_trade_ratio: String,
+    pub short_lived_order_pct: String,
+    pub is_stuffing: bool,
+    pub contributing_accounts: Vec<String>,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -46,6 +107,75 @@ pub struct WashTradeIndicator {
     pub volume_match: bool,
     pub price_match: bool,
     pub time_gap_seconds: u32,
+    pub offsetting_pairs: Vec<OffsettingTradePair>,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// window_days is informational only, same as detect_block_deals' date param -
+/// this platform has no persisted per-day trade store, so get_trades_by_accounts
+/// returns whatever fetch_trades currently synthesizes rather than a real
+/// window slice. delivered_quantity approximates what would actually settle
+/// (the net position after buys and sells offset each other); turnover_ratio
+/// is gross traded quantity over delivered_quantity, so an account that buys
+/// and sells the same 10,000 shares five times over while ending flat reads
+/// as a high ratio even though its "position" never moved.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TurnoverIndicator {
+    pub account_id: String,
+    pub symbol: String,
+    pub window_days: u32,
+    pub buy_quantity: u64,
+    pub sell_quantity: u64,
+    pub net_position: i64,
+    pub delivered_quantity: u64,
+    pub round_trip_quantity: u64,
+    pub turnover_ratio: String,
+    pub is_excessive_turnover: bool,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// One metric compared between the pre-event window and the 6-month
+/// baseline - deviation_pct is (recent_value - baseline_value) /
+/// baseline_value * 100, so a doubling reads as "100.00%". baseline_value and
+/// recent_value are both normalized to a per-day rate for count-like metrics
+/// (trade_frequency) and left as a plain ratio/average for the rest
+/// (avg_trade_size, buy_ratio), per the metric's own name
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BehaviorMetricDeviation {
+    pub metric: String,
+    pub baseline_value: String,
+    pub recent_value: String,
+    pub deviation_pct: String,
+}
+
+/// Compares an entity's trading in the 10 days before event_timestamp against
+/// its 6-month baseline (the 180 days before that 10-day window), the
+/// before/after shape most insider-trading narratives are built on. Trades
+/// come from trade_data_mcp's synthetic feed, which regenerates a fixed set
+/// of trades counting back from a constant epoch on every call rather than
+/// persisting real elapsed history - see fetch_trades' doc comment - so this
+/// is a real windowed comparison over whatever that feed currently returns,
+/// not a guarantee of matching a real 6-month trading record.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BehaviorDeviationReport {
+    pub entity_id: String,
+    pub event_timestamp: u64,
+    pub baseline_trade_count: u32,
+    pub recent_trade_count: u32,
+    pub baseline_symbols: Vec<String>,
+    pub recent_symbols: Vec<String>,
+    /// Symbols traded in the 10-day window that never appear in the 6-month baseline
+    pub new_symbols: Vec<String>,
+    pub deviations: Vec<BehaviorMetricDeviation>,
+    pub narrative: String,
+    /// Non-empty if a deviation was significant enough to open/update a case
+    pub case_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -55,6 +185,34 @@ pub struct PumpDumpIndicator {
     pub price_velocity: String,
     pub volume_surge: String,
     pub social_sentiment_score: i32,
+    /// benchmark_symbol's own change over the same window, and price_velocity
+    /// minus beta * benchmark_change_pct - is_pump_dump only fires when this
+    /// excess move clears the configured threshold, so a broad market rally
+    /// doesn't get flagged symbol-by-symbol
+    #[serde(default)]
+    pub benchmark_change_pct: String,
+    #[serde(default)]
+    pub excess_move_pct: String,
+    /// True if set_detector_enabled turned this detector off for the symbol
+    /// (or all symbols); the rest of the struct is left at defaults
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct MarketMoverResult {
+    pub symbol: String,
+    pub change_percent: String,
+    pub volume: u64,
+    pub is_pump_dump: bool,
+    pub is_volume_anomaly: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct MarketMoversSummary {
+    pub movers: Vec<MarketMoverResult>,
+    pub flagged_count: u32,
+    pub scan_timestamp: u64,
 }
 
 // Helper structs for API responses
@@ -64,6 +222,19 @@ struct AlphaVantageGlobalQuote {
     quote: Option<GlobalQuoteData>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AlphaVantageTopMovers {
+    top_gainers: Vec<TopMoverEntry>,
+    top_losers: Vec<TopMoverEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TopMoverEntry {
+    ticker: String,
+    change_percentage: String,
+    volume: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GlobalQuoteData {
     #[serde(rename = "05. price")]
@@ -84,17 +255,297 @@ struct TaapiRsi {
 trait AnomalyDetection {
     fn new() -> Result<Self, String> where Self: Sized;
     async fn get_context(&mut self) -> QueryContext;
+    /// kind: "entity" or "symbol" - see ReferenceResolution's doc comment
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String>;
     async fn detect_spoofing(&mut self, order_id: String, entity_id: String, symbol: String, order_details: String) -> Result<SpoofingIndicator, String>;
     async fn detect_wash_trading(&mut self, entity_id: String, counterparty_id: String, symbol: String, trade_timestamp: u64) -> Result<WashTradeIndicator, String>;
+    /// Expands a UPSI's accessors to their 1-hop relations via
+    /// entity_relationship_mcp, pulls each one's trades in the affected symbol
+    /// during the UPSI-active window via trade_data_mcp, and flags the subset
+    /// that traded in the same direction close together in time - raising one
+    /// composite case for the whole group instead of isolated per-person alerts
+    async fn detect_coordinated_trading(&mut self, upsi_id: String) -> Result<CoordinatedTradingIndicator, String>;
+    async fn detect_excessive_turnover(&mut self, account_id: String, symbol: String, window_days: u32) -> Result<TurnoverIndicator, String>;
+    /// Compares trading in the 10 days before event_timestamp against the
+    /// 6-month baseline before that window - see BehaviorDeviationReport's
+    /// doc comment
+    async fn generate_behavior_deviation_report(&mut self, entity_id: String, event_timestamp: u64) -> Result<BehaviorDeviationReport, String>;
+    async fn detect_quote_stuffing(&mut self, symbol: String, window_seconds: u64) -> Result<QuoteStuffingIndicator, String>;
+    fn get_quote_stuffing_rules(&self) -> Vec<QuoteStuffingRule>;
+    fn set_quote_stuffing_rules(&mut self, rules: Vec<QuoteStuffingRule>) -> Vec<QuoteStuffingRule>;
+    fn get_pump_dump_benchmark_rules(&self) -> Vec<PumpDumpBenchmarkRule>;
+    fn set_pump_dump_benchmark_rules(&mut self, rules: Vec<PumpDumpBenchmarkRule>) -> Vec<PumpDumpBenchmarkRule>;
     async fn detect_pump_dump(&mut self, symbol: String, time_window_minutes: u32) -> Result<PumpDumpIndicator, String>;
     async fn detect_front_running(&mut self, entity_id: String, symbol: String, client_trade_timestamp: u64, prop_trade_timestamp: u64) -> Result<AnomalyResult, String>;
     async fn analyze_volume_anomaly(&mut self, symbol: String, interval: String) -> Result<AnomalyResult, String>;
     async fn check_rsi_levels(&mut self, symbol: String) -> Result<String, String>;
     async fn scan_entity_anomalies(&self, entity_id: String) -> Result<Vec<AnomalyResult>, String>;
+    async fn scan_market_movers(&mut self) -> Result<MarketMoversSummary, String>;
+    fn get_severity_matrix(&self) -> Vec<SeverityMatrixEntry>;
+    fn set_severity_matrix(&mut self, entries: Vec<SeverityMatrixEntry>) -> Vec<SeverityMatrixEntry>;
+    fn get_wash_trade_rules(&self) -> Vec<WashTradeRule>;
+    fn set_wash_trade_rules(&mut self, rules: Vec<WashTradeRule>) -> Vec<WashTradeRule>;
+    /// Validates expression_json via validate_expr, then upserts by name into
+    /// the custom rule set evaluate_rules pulls from
+    fn add_rule(&mut self, name: String, expression_json: String) -> Result<DetectionRule, String>;
+    fn get_rules(&self) -> Vec<DetectionRule>;
+    /// Gathers price_change_pct/rsi (Alpha Vantage/TAAPI), volume_ratio
+    /// (trade_data_mcp) and is_insider/window_closed (entity_relationship_mcp)
+    /// into one feature map and runs every enabled custom rule against it
+    async fn evaluate_rules(&mut self, symbol: String, entity_id: String) -> Result<Vec<RuleEvaluation>, String>;
+    fn set_detector_enabled(&mut self, detector: String, symbol_or_all: String, enabled: bool) -> DetectorFlag;
+    fn get_detector_flags(&self) -> Vec<DetectorFlag>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
+    /// Drains the buffer push_history has been accumulating and sends it to the
+    /// dashboard as a single push_history_batch call, returning how many entries
+    /// were actually flushed (0 if the dashboard call failed - they stay queued)
+    async fn flush_history(&mut self) -> Result<u32, String>;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely migrate the
+/// Supabase schema / Neo4j graph without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SeverityMatrixEntry {
+    pub alert_type: String,
+    pub min_confidence: u32,
+    pub max_confidence: u32,
+    pub severity: String,
+    pub risk_score: u32,
+}
+
+/// Default severity matrix, mirroring the severities/risk_scores that used to be
+/// hard-coded per detector - each band covers the full 0-100 confidence range so
+/// behavior is unchanged until the compliance team calls set_severity_matrix
+fn default_severity_matrix() -> Vec<SeverityMatrixEntry> {
+    let bands = [
+        ("SPOOFING", "HIGH", 75),
+        ("SPOOFING_CHECK", "INFO", 10),
+        ("WASH_TRADING", "HIGH", 80),
+        ("WASH_TRADING_CHECK", "INFO", 10),
+        ("PUMP_DUMP", "CRITICAL", 85),
+        ("PUMP_DUMP_CHECK", "INFO", 10),
+        ("FRONT_RUNNING", "CRITICAL", 90),
+        ("FRONT_RUNNING_CHECK", "INFO", 10),
+        ("VOLUME_SPIKE", "MEDIUM", 60),
+        ("VOLUME_CHECK", "INFO", 10),
+        ("RSI_OVERBOUGHT", "HIGH", 70),
+        ("RSI_OVERSOLD", "MEDIUM", 50),
+        ("RSI_CHECK", "INFO", 10),
+    ];
+
+    bands.into_iter().map(|(alert_type, severity, risk_score)| SeverityMatrixEntry {
+        alert_type: alert_type.to_string(),
+        min_confidence: 0,
+        max_confidence: 100,
+        severity: severity.to_string(),
+        risk_score,
+    }).collect()
+}
+
+/// Which other symbols to also check for offsetting trades when investigating wash
+/// trading on `symbol` (e.g. a stock and its futures contract), and how many minutes
+/// apart a buy and a sell still count as a reversal - configurable so compliance can
+/// tune baskets/windows without a code change. No baskets are seeded by default, so
+/// detection stays single-symbol until a rule is added via set_wash_trade_rules.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct WashTradeRule {
+    pub symbol: String,
+    pub correlated_symbols: Vec<String>,
+    pub reversal_window_minutes: u32,
+}
+
+/// Per-symbol quote-stuffing burst thresholds; a symbol with no rule falls back to
+/// the defaults below. Configurable so compliance can tune per instrument without
+/// a code change, matching the WashTradeRule pattern.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct QuoteStuffingRule {
+    pub symbol: String,
+    pub message_rate_threshold: u32,
+    pub order_to_trade_ratio_threshold: String,
+}
+
+/// Per-symbol beta and excess-move threshold for the benchmark-adjusted pump-dump
+/// check; a symbol with no rule falls back to the defaults below. Configurable so
+/// compliance can tune per instrument without a code change, matching the
+/// WashTradeRule/QuoteStuffingRule pattern.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PumpDumpBenchmarkRule {
+    pub symbol: String,
+    pub beta: String,
+    pub excess_move_threshold: String,
+}
+
+/// One custom detection added via add_rule - expression_json is a small JSON
+/// tree ({"cmp": {"feature", "op", "value"}} / {"and": [..]} / {"or": [..]} /
+/// {"not": expr}) evaluated against evaluate_rules' feature map, so compliance
+/// can add simple new detections without a contract release
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DetectionRule {
+    pub name: String,
+    pub expression_json: String,
+    pub enabled: bool,
+    pub created_at: u64,
+}
+
+/// Result of evaluating one enabled DetectionRule's expression against
+/// evaluate_rules' feature map for a given symbol/entity
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct RuleEvaluation {
+    pub rule_name: String,
+    pub triggered: bool,
+    pub error: String,
+}
+
+/// Feature names evaluate_rules makes available to a rule's expression tree
+const RULE_FEATURES: [&str; 5] = ["price_change_pct", "volume_ratio", "rsi", "is_insider", "window_closed"];
+const RULE_OPERATORS: [&str; 6] = ["<", ">", "<=", ">=", "==", "!="];
+
+/// Checks that an add_rule expression tree is well-formed - a {"cmp": {feature,
+/// op, value}} leaf with feature/op drawn from RULE_FEATURES/RULE_OPERATORS, or
+/// an {"and": [..]} / {"or": [..]} / {"not": expr} combinator wrapping more
+/// expressions
+fn validate_expr(expr: &serde_json::Value) -> Result<(), String> {
+    let obj = expr.as_object().ok_or("expression node must be a JSON object")?;
+
+    if let Some(cmp) = obj.get("cmp") {
+        let cmp = cmp.as_object().ok_or("cmp must be an object")?;
+        let feature = cmp.get("feature").and_then(|v| v.as_str())
+            .ok_or("cmp.feature is required and must be a string")?;
+        if !RULE_FEATURES.contains(&feature) {
+            return Err(format!("unknown feature '{}', expected one of {:?}", feature, RULE_FEATURES));
+        }
+        let op = cmp.get("op").and_then(|v| v.as_str())
+            .ok_or("cmp.op is required and must be a string")?;
+        if !RULE_OPERATORS.contains(&op) {
+            return Err(format!("unknown operator '{}', expected one of {:?}", op, RULE_OPERATORS));
+        }
+        if !cmp.get("value").map(|v| v.is_number()).unwrap_or(false) {
+            return Err("cmp.value is required and must be a number".to_string());
+        }
+        Ok(())
+    } else if obj.contains_key("and") || obj.contains_key("or") {
+        let list = obj.get("and").or_else(|| obj.get("or")).unwrap().as_array()
+            .ok_or("and/or must be a list of expressions")?;
+        if list.is_empty() {
+            return Err("and/or must not be empty".to_string());
+        }
+        list.iter().try_for_each(validate_expr)
+    } else if let Some(inner) = obj.get("not") {
+        validate_expr(inner)
+    } else {
+        Err("expression node must contain one of: cmp, and, or, not".to_string())
+    }
+}
+
+/// Evaluates a previously-validated expression tree against a feature map -
+/// all features, including the booleans is_insider/window_closed, are
+/// represented as 1.0/0.0 so a single comparison operator set covers everything
+fn eval_expr(expr: &serde_json::Value, features: &HashMap<String, f64>) -> Result<bool, String> {
+    let obj = expr.as_object().ok_or("expression node must be a JSON object")?;
+
+    if let Some(cmp) = obj.get("cmp") {
+        let cmp = cmp.as_object().ok_or("cmp must be an object")?;
+        let feature = cmp.get("feature").and_then(|v| v.as_str()).ok_or("cmp.feature is required")?;
+        let op = cmp.get("op").and_then(|v| v.as_str()).ok_or("cmp.op is required")?;
+        let value = cmp.get("value").and_then(|v| v.as_f64()).ok_or("cmp.value must be a number")?;
+        let actual = *features.get(feature)
+            .ok_or_else(|| format!("no value available for feature '{}'", feature))?;
+
+        Ok(match op {
+            "<" => actual < value,
+            ">" => actual > value,
+            "<=" => actual <= value,
+            ">=" => actual >= value,
+            "==" => (actual - value).abs() < f64::EPSILON,
+            "!=" => (actual - value).abs() >= f64::EPSILON,
+            other => return Err(format!("unknown operator '{}'", other)),
+        })
+    } else if let Some(list) = obj.get("and") {
+        let list = list.as_array().ok_or("and must be a list of expressions")?;
+        for item in list {
+            if !eval_expr(item, features)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    } else if let Some(list) = obj.get("or") {
+        let list = list.as_array().ok_or("or must be a list of expressions")?;
+        for item in list {
+            if eval_expr(item, features)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    } else if let Some(inner) = obj.get("not") {
+        Ok(!eval_expr(inner, features)?)
+    } else {
+        Err("expression node must contain one of: cmp, and, or, not".to_string())
+    }
+}
+
+/// One detector feature flag; symbol is "ALL" to cover every symbol, or a specific
+/// ticker for a per-symbol override
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DetectorFlag {
+    pub detector: String,
+    pub symbol: String,
+    pub enabled: bool,
+}
+
+/// One matched pair of offsetting trades (opposite sides, same/correlated symbol,
+/// within the configured reversal window) between the entity and the counterparty
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct OffsettingTradePair {
+    pub entity_trade_id: String,
+    pub counterparty_trade_id: String,
+    pub symbol: String,
+    pub quantity: u64,
+    pub entity_side: String,
+    pub counterparty_side: String,
+    pub time_gap_seconds: u32,
+}
+
+/// One trade contributing to a detect_coordinated_trading finding
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CoordinatedTradeEntry {
+    pub account_id: String,
+    pub trade_id: String,
+    pub trade_type: String,
+    pub quantity: u64,
+    pub timestamp: u64,
+    /// 0 for a direct UPSI accessor, 1 for an entity reached via get_connected_entities
+    pub hops_from_accessor: u32,
+}
+
+/// Result of checking whether a UPSI's accessors (and their 1-hop relations)
+/// traded the affected symbol in the same direction, close together in time,
+/// during the UPSI-active window - see detect_coordinated_trading
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CoordinatedTradingIndicator {
+    pub upsi_id: String,
+    pub company_symbol: String,
+    pub upsi_active_from: u64,
+    pub upsi_active_to: u64,
+    pub is_coordinated: bool,
+    /// "BUY" or "SELL" - whichever direction the flagged subset traded in;
+    /// "" if no trades were found in the window
+    pub direction: String,
+    pub participant_count: u32,
+    pub trades: Vec<CoordinatedTradeEntry>,
+    pub disabled: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct Alert {
     pub id: String,
@@ -106,6 +557,44 @@ pub struct Alert {
     pub description: String,
     pub workflow_id: String,
     pub timestamp: u64,
+    /// {detector}:{entity}:{symbol}:{date} - lets the dashboard group every
+    /// artifact for the same underlying event across detectors/MCPs, even
+    /// though each one raises its own separately-typed alert
+    pub correlation_key: String,
+}
+
+/// One buffered call to push_history, held locally until flush_history_buffer
+/// sends the batch on to the dashboard in a single push_history_batch call
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub timestamp: u64,
+    pub source_mcp: String,
+    pub method_name: String,
+    pub params: String,
+    pub result_summary: String,
+    pub status: String,
+    pub entity_id: String,
+    pub symbol: String,
+}
+
+/// One runner-up candidate resolve_reference didn't pick, with its own confidence
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceCandidate {
+    pub value: String,
+    pub confidence: u32,
+}
+
+/// resolve_reference's result: the resolved value plus a 0-100 confidence
+/// score and up to 3 runner-up candidates, so a caller can ask a clarifying
+/// question instead of silently acting on a low-confidence fuzzy match
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceResolution {
+    pub kind: String,
+    pub query: String,
+    pub resolved_value: String,
+    pub confidence: u32,
+    pub alternatives: Vec<ReferenceCandidate>,
 }
 
 // ===== CONTEXT CACHE STRUCTURES =====
@@ -132,6 +621,21 @@ pub struct QueryContext {
 pub struct AnomalyDetectionContractState {
     secrets: Secrets<AnomalyDetectionConfig>,
     query_cache: QueryContext,
+    outbound_guard: OutboundGuard,
+    severity_matrix: Vec<SeverityMatrixEntry>,
+    wash_trade_rules: Vec<WashTradeRule>,
+    maintenance: MaintenanceStatus,
+    detector_flags: Vec<DetectorFlag>,
+    quote_stuffing_rules: Vec<QuoteStuffingRule>,
+    pump_dump_benchmark_rules: Vec<PumpDumpBenchmarkRule>,
+    /// Entries queued by push_history, awaiting flush_history_buffer - see
+    /// push_history's doc comment
+    history_buffer: Vec<HistoryEntry>,
+    /// Custom detections added via add_rule, evaluated by evaluate_rules
+    custom_rules: Vec<DetectionRule>,
+    /// Recorded Alpha Vantage/TAAPI responses, consulted/updated by
+    /// make_request according to config.http_fixture_mode
+    http_fixtures: Vec<http_fixtures::HttpFixture>,
 }
 
 impl AnomalyDetectionContractState {
@@ -141,71 +645,120 @@ impl AnomalyDetectionContractState {
         ])
     }
 
+    /// Issue a GET against `url`, gated by the per-host outbound circuit breaker
+    /// so a rate-limited or down provider (Alpha Vantage, TAAPI) can't be hammered with retries
     async fn make_request(
-        &self,
+        &mut self,
         url: &str,
         query_params: Vec<(String, String)>,
     ) -> Result<String, String> {
+        self.outbound_guard.check(url)?;
+
+        let mode = self.secrets.config().http_fixture_mode.clone();
+        let mut sorted_params = query_params.clone();
+        sorted_params.sort();
+        let params_key = sorted_params.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+        let key = http_fixtures::fixture_key("GET", url, &params_key);
+
+        if mode == "playback" {
+            return match http_fixtures::find(&self.http_fixtures, &key) {
+                Some(f) if (200..300).contains(&f.status) => Ok(f.body.clone()),
+                Some(f) => Err(format!("HTTP {} (fixture): {}", f.status, f.body)),
+                None => Err(format!("No recorded HTTP fixture for {}", key)),
+            };
+        }
+
         let headers = self.get_headers();
-        
-        let response = HttpClient::request(url, HttpMethod::Get)
+
+        let response = match HttpClient::request(url, HttpMethod::Get)
             .headers(headers)
             .query(query_params)
             .send()
-            .map_err(|err| err.to_string())?;
-        
+        {
+            Ok(response) => response,
+            Err(err) => {
+                self.outbound_guard.record_result(url, false);
+                if mode == "record" {
+                    http_fixtures::upsert(&mut self.http_fixtures, key, 599, err.to_string());
+                }
+                return Err(err.to_string());
+            }
+        };
+
         let status = response.status();
         let text = response.text();
-        
+        self.outbound_guard.record_result(url, (200..300).contains(&status));
+
+        if mode == "record" {
+            http_fixtures::upsert(&mut self.http_fixtures, key, status, text.clone());
+        }
+
         if !(200..300).contains(&status) {
             return Err(format!("HTTP {}: {}", status, text));
         }
-        
+
         Ok(text)
     }
 
     /// Fetch real-time quote from Alpha Vantage
     /// API: https://www.alphavantage.co/query?function=GLOBAL_QUOTE&symbol=IBM&apikey=demo
-    async fn get_quote(&self, symbol: &str) -> Result<GlobalQuoteData, String> {
+    async fn get_quote(&mut self, symbol: &str) -> Result<GlobalQuoteData, String> {
         let config = self.secrets.config();
         let url = "https://www.alphavantage.co/query";
-        
+
         let query_params = vec![
             ("function".to_string(), "GLOBAL_QUOTE".to_string()),
             ("symbol".to_string(), symbol.to_string()),
             ("apikey".to_string(), config.alpha_vantage_key.clone()),
         ];
-        
+
         let response_text = self.make_request(url, query_params).await?;
-            
+
         let quote_res: AlphaVantageGlobalQuote = serde_json::from_str(&response_text)
             .map_err(|e| format!("Failed to parse quote: {}. Response: {}", e, response_text))?;
-            
+
         quote_res.quote.ok_or_else(|| format!("Symbol not found or API limit reached. Response: {}", response_text))
     }
 
+    /// Fetch the day's top gainers/losers from Alpha Vantage
+    /// API: https://www.alphavantage.co/query?function=TOP_GAINERS_LOSERS&apikey=demo
+    async fn fetch_top_movers(&mut self) -> Result<AlphaVantageTopMovers, String> {
+        let config = self.secrets.config();
+        let url = "https://www.alphavantage.co/query";
+
+        let query_params = vec![
+            ("function".to_string(), "TOP_GAINERS_LOSERS".to_string()),
+            ("apikey".to_string(), config.alpha_vantage_key.clone()),
+        ];
+
+        let response_text = self.make_request(url, query_params).await?;
+
+        serde_json::from_str(&response_text)
+            .map_err(|e| format!("Failed to parse top movers: {}. Response: {}", e, response_text))
+    }
+
     /// Fetch RSI from TAAPI.IO
     /// API: https://api.taapi.io/rsi?secret=MY_SECRET&exchange=binance&symbol=BTC/USDT&interval=1h
-    async fn get_rsi(&self, symbol: &str) -> Result<f64, String> {
+    async fn get_rsi(&mut self, symbol: &str) -> Result<f64, String> {
         let config = self.secrets.config();
         let url = "https://api.taapi.io/rsi";
-        
+
         // TAAPI uses crypto pairs - convert stock symbol to crypto for demo
         // For production, would need proper stock data source
         let crypto_symbol = format!("{}/USDT", symbol);
-        
+
         let query_params = vec![
             ("secret".to_string(), config.taapi_secret.clone()),
             ("exchange".to_string(), "binance".to_string()),
             ("symbol".to_string(), crypto_symbol),
             ("interval".to_string(), "1h".to_string()),
         ];
-        
+
         let response_text = self.make_request(url, query_params).await?;
-            
+
         let rsi: TaapiRsi = serde_json::from_str(&response_text)
             .map_err(|e| format!("Failed to parse RSI: {}. Response: {}", e, response_text))?;
-            
+
         Ok(rsi.value)
     }
 
@@ -243,28 +796,23 @@ impl AnomalyDetectionContractState {
         if partial.is_empty() {
             return self.query_cache.last_entity_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        // First check last entity (most likely match)
-        if self.query_cache.last_entity_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_entity_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_entity_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()));
+
+        if let Some(m) = fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES) {
+            return m.value;
         }
-        
-        // Search through cached queries for fuzzy match
+
+        // Fall back to checking whether the natural language prompt mentions
+        // this entity, since the entity id itself may not appear verbatim
+        let partial_lower = partial.to_lowercase();
         for query in self.query_cache.recent_queries.iter().rev() {
-            // Check if cached entity contains the partial
-            if !query.entity_id.is_empty() && query.entity_id.to_lowercase().contains(&partial_lower) {
+            if !query.entity_id.is_empty() && query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
                 return query.entity_id.clone();
             }
-            // Also check if natural language prompt mentions this entity
-            if query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
-                if !query.entity_id.is_empty() {
-                    return query.entity_id.clone();
-                }
-            }
         }
-        
+
         // No match found, return original
         partial.to_string()
     }
@@ -275,20 +823,13 @@ impl AnomalyDetectionContractState {
         if partial.is_empty() {
             return self.query_cache.last_symbol.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_symbol.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_symbol.clone();
-        }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.symbol.is_empty() && query.symbol.to_lowercase().contains(&partial_lower) {
-                return query.symbol.clone();
-            }
-        }
-        
-        partial.to_string()
+
+        let candidates = std::iter::once(self.query_cache.last_symbol.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.symbol.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
     }
 
     fn resolve_from_cache(&self, entity_partial: &str, symbol_partial: &str) -> (String, String) {
@@ -342,26 +883,147 @@ impl AnomalyDetectionContractState {
         (self.resolve_entity(entity_partial), self.resolve_symbol(symbol_partial))
     }
 
-    fn maybe_push_alert(&self, alert_type: &str, severity: &str, risk_score: u32, entity_id: &str, symbol: &str, description: &str) {
+    /// Look up (severity, risk_score) for an alert_type/confidence pair from the
+    /// configurable severity matrix, so the compliance team can retune alerting
+    /// without a code change
+    fn resolve_severity(&self, alert_type: &str, confidence: u32) -> (String, u32) {
+        self.severity_matrix.iter()
+            .find(|e| e.alert_type == alert_type && confidence >= e.min_confidence && confidence <= e.max_confidence)
+            .map(|e| (e.severity.clone(), e.risk_score))
+            .unwrap_or_else(|| ("INFO".to_string(), 10))
+    }
+
+    /// Reject mutating calls while maintenance mode is on, so an in-flight schema
+    /// migration in Supabase/Neo4j can't race a concurrent tool call into a
+    /// half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// A specific-symbol override wins over an "ALL" entry, which wins over the
+    /// default of enabled; used at the top of every detector to decide whether to
+    /// run or return a typed disabled result
+    fn detector_enabled(&self, detector: &str, symbol: &str) -> bool {
+        if let Some(flag) = self.detector_flags.iter().find(|f| f.detector.eq_ignore_ascii_case(detector) && f.symbol.eq_ignore_ascii_case(symbol)) {
+            return flag.enabled;
+        }
+        if let Some(flag) = self.detector_flags.iter().find(|f| f.detector.eq_ignore_ascii_case(detector) && f.symbol.eq_ignore_ascii_case("ALL")) {
+            return flag.enabled;
+        }
+        true
+    }
+
+    /// Message-rate and order-to-trade-ratio thresholds for `symbol`, falling back
+    /// to a default burst profile when no rule is configured
+    fn quote_stuffing_thresholds(&self, symbol: &str) -> (u32, f64) {
+        match self.quote_stuffing_rules.iter().find(|r| r.symbol == symbol) {
+            Some(rule) => (rule.message_rate_threshold, rule.order_to_trade_ratio_threshold.parse().unwrap_or(1.5)),
+            None => (20, 1.5),
+        }
+    }
+
+    fn pump_dump_benchmark_params(&self, symbol: &str) -> (f64, f64) {
+        match self.pump_dump_benchmark_rules.iter().find(|r| r.symbol == symbol) {
+            Some(rule) => (rule.beta.parse().unwrap_or(1.0), rule.excess_move_threshold.parse().unwrap_or(5.0)),
+            None => (1.0, 5.0),
+        }
+    }
+
+    /// Symbols to check for offsetting trades (the symbol itself plus any configured
+    /// correlated instruments) and the reversal window to use, falling back to
+    /// single-symbol matching with a 5 minute window when no rule is configured
+    fn wash_trade_scope(&self, symbol: &str) -> (Vec<String>, u32) {
+        match self.wash_trade_rules.iter().find(|r| r.symbol == symbol) {
+            Some(rule) => {
+                let mut symbols = vec![rule.symbol.clone()];
+                symbols.extend(rule.correlated_symbols.iter().cloned());
+                (symbols, rule.reversal_window_minutes)
+            }
+            None => (vec![symbol.to_string()], 5),
+        }
+    }
+
+    /// Fetch recent trades for the entity/counterparty across the given symbols and
+    /// pair up opposite-side trades that reverse within the window, so wash trading
+    /// across correlated instruments (e.g. a stock and its futures contract) is
+    /// caught, not just literal same-symbol self-trades
+    fn find_offsetting_trades(&self, entity_id: &str, counterparty_id: &str, symbols: &[String], window_minutes: u32) -> Vec<OffsettingTradePair> {
+        let config = self.secrets.config();
+        if config.trade_data_contract_id.is_empty() {
+            return Vec::new();
+        }
+
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let account_ids = format!("{},{}", entity_id, counterparty_id);
+        // Trade timestamps are epoch milliseconds
+        let window_ms = (window_minutes as u64) * 60_000;
+        let mut pairs = Vec::new();
+
+        for symbol in symbols {
+            let trades = match trade_data_mcp.get_trades_by_accounts(account_ids.clone(), symbol.clone()) {
+                Ok(trades) => trades,
+                Err(_) => continue,
+            };
+
+            let entity_trades: Vec<_> = trades.iter().filter(|t| t.account_id == entity_id).collect();
+            let counterparty_trades: Vec<_> = trades.iter().filter(|t| t.account_id == counterparty_id).collect();
+
+            for entity_trade in &entity_trades {
+                for counterparty_trade in &counterparty_trades {
+                    let opposite_sides = entity_trade.trade_type != counterparty_trade.trade_type;
+                    let same_quantity = entity_trade.quantity == counterparty_trade.quantity;
+                    let time_gap_ms = entity_trade.timestamp.abs_diff(counterparty_trade.timestamp);
+                    let time_gap_seconds = time_gap_ms / 1000;
+
+                    if opposite_sides && same_quantity && time_gap_ms <= window_ms {
+                        pairs.push(OffsettingTradePair {
+                            entity_trade_id: entity_trade.trade_id.clone(),
+                            counterparty_trade_id: counterparty_trade.trade_id.clone(),
+                            symbol: symbol.clone(),
+                            quantity: entity_trade.quantity,
+                            entity_side: entity_trade.trade_type.clone(),
+                            counterparty_side: counterparty_trade.trade_type.clone(),
+                            time_gap_seconds: time_gap_seconds as u32,
+                        });
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
+    fn maybe_push_alert(&self, alert_type: &str, confidence: u32, entity_id: &str, symbol: &str, description: &str) {
         let config = self.secrets.config();
         if config.dashboard_contract_id.is_empty() {
             return;
         }
 
+        let (severity, risk_score) = self.resolve_severity(alert_type, confidence);
+        // No real per-day clock in this crate (alerts, cases and history all
+        // carry timestamp: 0 placeholders); reuses the same fixed date the other
+        // MCPs' mocked "now" resolves to so correlation keys stay comparable
+        let date = "2026-01-13";
+
         let alert = Alert {
             id: format!("ANOMALY-{}-{}", alert_type, 0u64), // Simplified timestamp
             alert_type: alert_type.to_string(),
-            severity: severity.to_string(),
+            severity,
             risk_score,
             entity_id: entity_id.to_string(),
             symbol: symbol.to_string(),
             description: description.to_string(),
             workflow_id: "".to_string(),
-            timestamp: 0, 
+            timestamp: 0,
+            correlation_key: format!("{}:{}:{}:{}", alert_type, entity_id, symbol, date),
         };
 
-        let args = serde_json::json!({ "alert": alert }).to_string();
-        
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
+
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "push_alert".to_string(),
@@ -369,31 +1031,61 @@ impl AnomalyDetectionContractState {
         );
     }
 
-    fn push_history(&self, method_name: &str, params: &str, result_summary: &str, status: &str, entity_id: &str, symbol: &str) {
+    /// How many buffered entries trigger an automatic flush_history_buffer
+    const HISTORY_BATCH_SIZE: usize = 20;
+    /// Hard cap on the buffer so a prolonged dashboard outage can't grow state
+    /// without bound; once past this, oldest entries are dropped to make room
+    /// rather than blocking or erroring the calling method
+    const HISTORY_BUFFER_MAX: usize = 200;
+
+    /// Queues a history entry locally instead of calling the dashboard
+    /// synchronously on every method - that used to double the latency of every
+    /// tool call. flush_history_buffer drains the buffer in one batched call
+    /// once it reaches HISTORY_BATCH_SIZE, or on an explicit flush_history call.
+    fn push_history(&mut self, method_name: &str, params: &str, result_summary: &str, status: &str, entity_id: &str, symbol: &str) {
+        self.history_buffer.push(HistoryEntry {
+            id: format!("HIST-anomaly-{}-{}", method_name, 0u64),
+            timestamp: 0u64,
+            source_mcp: "anomaly_detection".to_string(),
+            method_name: method_name.to_string(),
+            params: params.to_string(),
+            result_summary: result_summary.to_string(),
+            status: status.to_string(),
+            entity_id: entity_id.to_string(),
+            symbol: symbol.to_string(),
+        });
+
+        if self.history_buffer.len() > Self::HISTORY_BUFFER_MAX {
+            let overflow = self.history_buffer.len() - Self::HISTORY_BUFFER_MAX;
+            self.history_buffer.drain(0..overflow);
+        }
+
+        if self.history_buffer.len() >= Self::HISTORY_BATCH_SIZE {
+            self.flush_history_buffer();
+        }
+    }
+
+    /// Sends every buffered entry to the dashboard in one push_history_batch
+    /// call. Loss-safe: entries are only cleared from the buffer once the call
+    /// actually succeeds, so a down or misconfigured dashboard leaves them
+    /// queued for the next flush instead of silently dropping them.
+    fn flush_history_buffer(&mut self) {
         let config = self.secrets.config();
-        if config.dashboard_contract_id.is_empty() {
+        if config.dashboard_contract_id.is_empty() || self.history_buffer.is_empty() {
             return;
         }
 
-        let entry = serde_json::json!({
-            "id": format!("HIST-anomaly-{}-{}", method_name, 0u64),
-            "timestamp": 0u64,
-            "source_mcp": "anomaly_detection",
-            "method_name": method_name,
-            "params": params,
-            "result_summary": result_summary,
-            "status": status,
-            "entity_id": entity_id,
-            "symbol": symbol
-        });
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "entries": self.history_buffer }).to_string();
 
-        let args = serde_json::json!({ "entry": entry }).to_string();
-        
-        let _ = Runtime::call_contract::<String>(
+        let sent = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
-            "push_history".to_string(),
+            "push_history_batch".to_string(),
             Some(args),
         );
+
+        if sent.is_ok() {
+            self.history_buffer.clear();
+        }
     }
 
     fn log_workflow(&self, workflow_id: &str, workflow_type: &str, trigger: &str) {
@@ -403,6 +1095,7 @@ impl AnomalyDetectionContractState {
         }
 
         let args = serde_json::json!({
+            "token": config.dashboard_caller_token,
             "workflow_id": workflow_id,
             "workflow_type": workflow_type,
             "trigger": trigger,
@@ -436,8 +1129,8 @@ impl AnomalyDetectionContractState {
             "summary": summary
         });
 
-        let args = serde_json::json!({ "case_record": case }).to_string();
-        
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "case_record": case }).to_string();
+
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "upsert_case".to_string(),
@@ -459,8 +1152,8 @@ impl AnomalyDetectionContractState {
             "last_alert_at": 0u64
         });
 
-        let args = serde_json::json!({ "entity": entity }).to_string();
-        
+        let args = serde_json::json!({ "token": config.dashboard_caller_token, "entity": entity }).to_string();
+
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "register_risk_entity".to_string(),
@@ -559,6 +1252,16 @@ impl AnomalyDetection for AnomalyDetectionContractState {
                 last_entity_id: "TRADER-001".to_string(),
                 last_symbol: "RELIANCE".to_string(),
             },
+            outbound_guard: OutboundGuard::default(),
+            severity_matrix: default_severity_matrix(),
+            wash_trade_rules: Vec::new(),
+            maintenance: MaintenanceStatus::default(),
+            detector_flags: Vec::new(),
+            quote_stuffing_rules: Vec::new(),
+            pump_dump_benchmark_rules: Vec::new(),
+            history_buffer: Vec::new(),
+            custom_rules: Vec::new(),
+            http_fixtures: Vec::new(),
         })
     }
 
@@ -567,14 +1270,52 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         self.query_cache.clone()
     }
 
+    #[query]
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String> {
+        if partial.is_empty() {
+            return Err("partial must not be empty".to_string());
+        }
+
+        let candidates: Vec<&str> = match kind.as_str() {
+            "entity" => std::iter::once(self.query_cache.last_entity_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()))
+                .collect(),
+            "symbol" => std::iter::once(self.query_cache.last_symbol.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.symbol.as_str()))
+                .collect(),
+            other => return Err(format!("Unknown reference kind '{}' - expected entity or symbol", other)),
+        };
+
+        let mut ranked = fuzzy_match::resolve_ranked(&partial, candidates.into_iter(), &fuzzy_match::DEFAULT_STRATEGIES, 4).into_iter();
+        let (resolved_value, confidence) = match ranked.next() {
+            Some(m) => (m.value, (m.score * 100.0).round() as u32),
+            None => (partial.clone(), 0),
+        };
+        let alternatives = ranked.map(|m| ReferenceCandidate { value: m.value, confidence: (m.score * 100.0).round() as u32 }).collect();
+
+        Ok(ReferenceResolution { kind, query: partial, resolved_value, confidence, alternatives })
+    }
+
     #[mutate]
     async fn detect_spoofing(&mut self, order_id: String, entity_id: String, symbol: String, order_details: String) -> Result<SpoofingIndicator, String> {
+        self.maintenance_guard()?;
         let (resolved_entity, resolved_symbol) = self.resolve_from_cache(&entity_id, &symbol);
-        
-        self.update_cache("detect_spoofing", &resolved_entity, &resolved_symbol, 
+
+        if !self.detector_enabled("spoofing", &resolved_symbol) {
+            return Ok(SpoofingIndicator {
+                order_id,
+                is_spoof: false,
+                cancellation_rate: "".to_string(),
+                order_size_vs_market: "".to_string(),
+                price_impact: "".to_string(),
+                disabled: true,
+            });
+        }
+
+        self.update_cache("detect_spoofing", &resolved_entity, &resolved_symbol,
             &format!("Check spoofing for order {} by {} on {}", order_id, resolved_entity, resolved_symbol));
-        
-        
+
+
         let quote = self.get_quote(&resolved_symbol).await?;
         
         let market_volume: u64 = quote.volume.parse().unwrap_or(10000);
@@ -592,8 +1333,7 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         if is_spoof {
             self.maybe_push_alert(
                 "SPOOFING",
-                "HIGH",
-                75,
+                90,
                 &resolved_entity,
                 &resolved_symbol,
                 &format!("Spoofing detected: Order {} has high cancellation rate and large size vs market", order_id),
@@ -609,7 +1349,6 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         } else {
             self.maybe_push_alert(
                 "SPOOFING_CHECK",
-                "INFO",
                 10,
                 &resolved_entity,
                 &resolved_symbol,
@@ -632,39 +1371,61 @@ impl AnomalyDetection for AnomalyDetectionContractState {
             cancellation_rate: "High".to_string(),
             order_size_vs_market: format!("{}% of daily vol", if is_large_order { "15" } else { "1" }),
             price_impact: "Potential manipulation detected".to_string(),
+            disabled: false,
         })
     }
 
     /// Detect wash trading
     #[mutate]
     async fn detect_wash_trading(&mut self, entity_id: String, counterparty_id: String, symbol: String, trade_timestamp: u64) -> Result<WashTradeIndicator, String> {
+        self.maintenance_guard()?;
         
         let (resolved_entity, resolved_symbol) = self.resolve_from_cache(&entity_id, &symbol);
         
         let (resolved_counterparty, _) = self.resolve_from_cache(&counterparty_id, &symbol);
-        
+
+        if !self.detector_enabled("wash_trading", &resolved_symbol) {
+            return Ok(WashTradeIndicator {
+                entity_id: resolved_entity,
+                counterparty_id: resolved_counterparty,
+                is_wash_trade: false,
+                volume_match: false,
+                price_match: false,
+                time_gap_seconds: 0,
+                offsetting_pairs: Vec::new(),
+                disabled: true,
+            });
+        }
+
         // Update cache
-        self.update_cache("detect_wash_trading", &resolved_entity, &resolved_symbol, 
+        self.update_cache("detect_wash_trading", &resolved_entity, &resolved_symbol,
             &format!("Check wash trading between {} and {} on {}", resolved_entity, resolved_counterparty, resolved_symbol));
         
-        // Wash trading = Entity trading with itself or collider
+        // Wash trading = entity trading with itself/collider, or offsetting trades
+        // with a counterparty across correlated instruments within the reversal window
         let is_same_entity = resolved_entity == resolved_counterparty;
-        
+        let (scope_symbols, window_minutes) = self.wash_trade_scope(&resolved_symbol);
+        let offsetting_pairs = if is_same_entity {
+            Vec::new()
+        } else {
+            self.find_offsetting_trades(&resolved_entity, &resolved_counterparty, &scope_symbols, window_minutes)
+        };
+        let is_wash_trade = is_same_entity || !offsetting_pairs.is_empty();
+
         // Log workflow
         self.log_workflow(
             &format!("WF-WASH-{}-{}", resolved_entity, resolved_counterparty),
             "WASH_TRADING_DETECTION",
             &format!("Check {} vs {}", resolved_entity, resolved_counterparty),
         );
-        
-        if is_same_entity {
+
+        if is_wash_trade {
             self.maybe_push_alert(
                 "WASH_TRADING",
-                "HIGH",
-                80,
+                90,
                 &resolved_entity,
                 &resolved_symbol,
-                &format!("Wash trading detected: {} trading with itself/collider {}", resolved_entity, resolved_counterparty),
+                &format!("Wash trading detected: {} trading with itself/collider {} ({} offsetting trade pair(s))", resolved_entity, resolved_counterparty, offsetting_pairs.len()),
             );
             self.create_case(
                 "WASH_TRADING",
@@ -678,101 +1439,759 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         } else {
             self.maybe_push_alert(
                 "WASH_TRADING_CHECK",
-                "INFO",
                 10,
                 &resolved_entity,
                 &resolved_symbol,
                 &format!("Wash trading check passed between {} and {}", resolved_entity, resolved_counterparty),
             );
         }
-        
+
         // Push history
         self.push_history(
             "detect_wash_trading",
             &format!("entity={}, counterparty={}, symbol={}", resolved_entity, resolved_counterparty, resolved_symbol),
-            &format!("is_wash_trade={}", is_same_entity),
-            if is_same_entity { "ALERT" } else { "OK" },
+            &format!("is_wash_trade={}", is_wash_trade),
+            if is_wash_trade { "ALERT" } else { "OK" },
             &resolved_entity,
             &resolved_symbol,
         );
-        
+
+        let time_gap_seconds = offsetting_pairs.iter().map(|p| p.time_gap_seconds).min().unwrap_or(0);
         Ok(WashTradeIndicator {
             entity_id: resolved_entity,
             counterparty_id: resolved_counterparty,
-            is_wash_trade: is_same_entity,
-            volume_match: true,
-            price_match: true,
-            time_gap_seconds: 0,
+            is_wash_trade,
+            volume_match: is_same_entity || !offsetting_pairs.is_empty(),
+            price_match: is_same_entity || !offsetting_pairs.is_empty(),
+            time_gap_seconds,
+            offsetting_pairs,
+            disabled: false,
+        })
+    }
+
+    /// How close together (in minutes) same-direction trades from distinct
+    /// entities have to land to count as coordinated, rather than several
+    /// unrelated people independently trading during a long UPSI-active window
+    const COORDINATED_TRADING_WINDOW_MINUTES: u64 = 60;
+
+    #[mutate]
+    async fn detect_coordinated_trading(&mut self, upsi_id: String) -> Result<CoordinatedTradingIndicator, String> {
+        self.maintenance_guard()?;
+
+        #[derive(Debug, Serialize)]
+        struct GetConnectedEntitiesArgs {
+            entity_id: String,
+            max_hops: u32,
+            page: Option<u32>,
+            page_size: Option<u32>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct ConnectedEntity {
+            connected_entity_id: String,
+        }
+        #[derive(Debug, Deserialize)]
+        struct ConnectedEntitiesPage {
+            connections: Vec<ConnectedEntity>,
+        }
+
+        let config = self.secrets.config();
+        if config.upsi_database_contract_id.is_empty() {
+            return Err("upsi_database_contract_id not configured".to_string());
+        }
+        if config.entity_relationship_contract_id.is_empty() {
+            return Err("entity_relationship_contract_id not configured".to_string());
+        }
+        if config.trade_data_contract_id.is_empty() {
+            return Err("trade_data_contract_id not configured".to_string());
+        }
+        let entity_relationship_contract_id = config.entity_relationship_contract_id.clone();
+
+        let upsi_mcp = UpsiDatabaseMcp::new(config.upsi_database_contract_id.clone());
+        let upsi = upsi_mcp.get_upsi(upsi_id.clone())
+            .map_err(|e| format!("Failed to fetch UPSI {}: {}", upsi_id, e))?;
+
+        if !self.detector_enabled("coordinated_trading", &upsi.company_symbol) {
+            return Ok(CoordinatedTradingIndicator {
+                upsi_id,
+                company_symbol: upsi.company_symbol,
+                upsi_active_from: upsi.created_date,
+                upsi_active_to: upsi.public_date,
+                is_coordinated: false,
+                direction: "".to_string(),
+                participant_count: 0,
+                trades: Vec::new(),
+                disabled: true,
+            });
+        }
+
+        self.update_cache("detect_coordinated_trading", "", &upsi.company_symbol,
+            &format!("Check coordinated trading around UPSI {}", upsi_id));
+
+        let accessors = upsi_mcp.get_upsi_accessors(upsi_id.clone())
+            .map_err(|e| format!("Failed to fetch UPSI accessors: {}", e))?;
+
+        // Every direct accessor, plus their 1-hop relations, each tagged with
+        // how many hops it took to reach them
+        let mut group: Vec<(String, u32)> = Vec::new();
+        for accessor in &accessors {
+            if !group.iter().any(|(id, _)| *id == accessor.accessor_entity_id) {
+                group.push((accessor.accessor_entity_id.clone(), 0));
+            }
+        }
+        for accessor in &accessors {
+            let args = serde_json::to_string(&GetConnectedEntitiesArgs {
+                entity_id: accessor.accessor_entity_id.clone(),
+                max_hops: 1,
+                page: None,
+                page_size: None,
+            }).unwrap();
+            if let Ok(page) = Runtime::call_contract::<ConnectedEntitiesPage>(
+                entity_relationship_contract_id.clone(),
+                "get_connected_entities".to_string(),
+                Some(args),
+            ) {
+                for conn in page.connections {
+                    if !group.iter().any(|(id, _)| *id == conn.connected_entity_id) {
+                        group.push((conn.connected_entity_id, 1));
+                    }
+                }
+            }
+        }
+
+        let account_ids = group.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>().join(",");
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let trades = trade_data_mcp.get_trades_by_accounts(account_ids, upsi.company_symbol.clone())
+            .map_err(|e| format!("Failed to fetch trades: {}", e))?;
+
+        let windowed: Vec<_> = trades.into_iter()
+            .filter(|t| t.timestamp >= upsi.created_date && t.timestamp <= upsi.public_date)
+            .collect();
+
+        let buy_count = windowed.iter().filter(|t| t.trade_type == "BUY").count();
+        let sell_count = windowed.len() - buy_count;
+        let direction = if windowed.is_empty() {
+            ""
+        } else if buy_count >= sell_count {
+            "BUY"
+        } else {
+            "SELL"
+        };
+        let same_direction: Vec<_> = windowed.into_iter().filter(|t| t.trade_type == direction).collect();
+
+        let mut distinct_entities: Vec<String> = Vec::new();
+        for trade in &same_direction {
+            if !distinct_entities.contains(&trade.account_id) {
+                distinct_entities.push(trade.account_id.clone());
+            }
+        }
+
+        let is_coordinated = if distinct_entities.len() < 2 {
+            false
+        } else {
+            let min_ts = same_direction.iter().map(|t| t.timestamp).min().unwrap_or(0);
+            let max_ts = same_direction.iter().map(|t| t.timestamp).max().unwrap_or(0);
+            max_ts.saturating_sub(min_ts) <= Self::COORDINATED_TRADING_WINDOW_MINUTES * 60_000
+        };
+
+        self.log_workflow(
+            &format!("WF-COORD-{}", upsi_id),
+            "COORDINATED_TRADING_DETECTION",
+            &format!("Check coordinated trading around UPSI {}", upsi_id),
+        );
+
+        let participants = distinct_entities.join(",");
+        if is_coordinated {
+            self.maybe_push_alert(
+                "COORDINATED_TRADING",
+                90,
+                &participants,
+                &upsi.company_symbol,
+                &format!("Coordinated {} trading detected around UPSI {} across {} entities: {}", direction, upsi_id, distinct_entities.len(), participants),
+            );
+            self.create_case(
+                "COORDINATED_TRADING",
+                &participants,
+                &upsi.company_symbol,
+                85,
+                &format!("{} entities traded {} on {} in the same direction within the UPSI-active window for {}", distinct_entities.len(), direction, upsi.company_symbol, upsi_id),
+            );
+            for entity_id in &distinct_entities {
+                self.register_risk(entity_id, &format!("Entity {}", entity_id), 85);
+            }
+        } else {
+            self.maybe_push_alert(
+                "COORDINATED_TRADING_CHECK",
+                10,
+                "",
+                &upsi.company_symbol,
+                &format!("Coordinated trading check passed around UPSI {}", upsi_id),
+            );
+        }
+
+        self.push_history(
+            "detect_coordinated_trading",
+            &format!("upsi_id={}", upsi_id),
+            &format!("is_coordinated={}, participants={}", is_coordinated, distinct_entities.len()),
+            if is_coordinated { "ALERT" } else { "OK" },
+            &participants,
+            &upsi.company_symbol,
+        );
+
+        let hops_by_entity = group;
+        let trades = same_direction.into_iter().map(|t| CoordinatedTradeEntry {
+            hops_from_accessor: hops_by_entity.iter().find(|(id, _)| *id == t.account_id).map(|(_, hops)| *hops).unwrap_or(0),
+            account_id: t.account_id,
+            trade_id: t.trade_id,
+            trade_type: t.trade_type,
+            quantity: t.quantity,
+            timestamp: t.timestamp,
+        }).collect();
+
+        Ok(CoordinatedTradingIndicator {
+            upsi_id,
+            company_symbol: upsi.company_symbol,
+            upsi_active_from: upsi.created_date,
+            upsi_active_to: upsi.public_date,
+            is_coordinated,
+            direction: direction.to_string(),
+            participant_count: distinct_entities.len() as u32,
+            trades,
+            disabled: false,
+        })
+    }
+
+    /// Flags an account whose intraday buying and selling churns far past what
+    /// its actual (net/delivered) position requires - a proxy for option-like
+    /// leverage or circular trading that keeps net exposure flat while running
+    /// up volume. See TurnoverIndicator's doc comment for what window_days and
+    /// delivered_quantity mean here.
+    #[mutate]
+    async fn detect_excessive_turnover(&mut self, account_id: String, symbol: String, window_days: u32) -> Result<TurnoverIndicator, String> {
+        self.maintenance_guard()?;
+
+        let (resolved_account, resolved_symbol) = self.resolve_from_cache(&account_id, &symbol);
+
+        if !self.detector_enabled("excessive_turnover", &resolved_symbol) {
+            return Ok(TurnoverIndicator {
+                account_id: resolved_account,
+                symbol: resolved_symbol,
+                window_days,
+                buy_quantity: 0,
+                sell_quantity: 0,
+                net_position: 0,
+                delivered_quantity: 0,
+                round_trip_quantity: 0,
+                turnover_ratio: "".to_string(),
+                is_excessive_turnover: false,
+                disabled: true,
+            });
+        }
+
+        self.update_cache("detect_excessive_turnover", &resolved_account, &resolved_symbol,
+            &format!("Check turnover for {} on {} over {} day(s)", resolved_account, resolved_symbol, window_days));
+
+        let config = self.secrets.config();
+        if config.trade_data_contract_id.is_empty() {
+            return Err("trade_data_contract_id not configured".to_string());
+        }
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let trades = trade_data_mcp
+            .get_trades_by_accounts(resolved_account.clone(), resolved_symbol.clone())
+            .map_err(|e| format!("Failed to fetch trades: {}", e))?;
+
+        let mut buy_quantity: u64 = 0;
+        let mut sell_quantity: u64 = 0;
+        for trade in &trades {
+            if trade.account_id != resolved_account {
+                continue;
+            }
+            if trade.trade_type == "BUY" {
+                buy_quantity += trade.quantity;
+            } else {
+                sell_quantity += trade.quantity;
+            }
+        }
+
+        let net_position = buy_quantity as i64 - sell_quantity as i64;
+        let gross_quantity = buy_quantity + sell_quantity;
+        let delivered_quantity = net_position.unsigned_abs();
+        let round_trip_quantity = buy_quantity.min(sell_quantity);
+        let turnover_ratio = gross_quantity as f64 / (delivered_quantity.max(1)) as f64;
+
+        // Heuristic: turned over at least 3x the delivered position, and at
+        // least half the gross quantity round-tripped, rather than flagging a
+        // one-sided accumulation that just happens to have a small net position
+        let is_excessive_turnover = gross_quantity > 0
+            && turnover_ratio >= 3.0
+            && round_trip_quantity as f64 / gross_quantity.max(1) as f64 >= 0.5;
+
+        self.log_workflow(
+            &format!("WF-TURNOVER-{}-{}", resolved_account, resolved_symbol),
+            "EXCESSIVE_TURNOVER_DETECTION",
+            &format!("Check turnover for {} on {}", resolved_account, resolved_symbol),
+        );
+
+        if is_excessive_turnover {
+            self.maybe_push_alert(
+                "EXCESSIVE_TURNOVER",
+                75,
+                &resolved_account,
+                &resolved_symbol,
+                &format!("Excessive turnover: {} churned {} shares against a delivered position of {} ({:.2}x turnover) on {}", resolved_account, gross_quantity, delivered_quantity, turnover_ratio, resolved_symbol),
+            );
+            self.create_case(
+                "EXCESSIVE_TURNOVER",
+                &resolved_account,
+                &resolved_symbol,
+                65,
+                &format!("Turnover ratio {:.2}x for {} on {}", turnover_ratio, resolved_account, resolved_symbol),
+            );
+            self.register_risk(&resolved_account, &format!("Account {}", resolved_account), 65);
+        } else {
+            self.maybe_push_alert(
+                "EXCESSIVE_TURNOVER_CHECK",
+                10,
+                &resolved_account,
+                &resolved_symbol,
+                &format!("Turnover check passed for {} on {} ({:.2}x)", resolved_account, resolved_symbol, turnover_ratio),
+            );
+        }
+
+        self.push_history(
+            "detect_excessive_turnover",
+            &format!("account={}, symbol={}, window_days={}", resolved_account, resolved_symbol, window_days),
+            &format!("is_excessive_turnover={}, turnover_ratio={:.2}x", is_excessive_turnover, turnover_ratio),
+            if is_excessive_turnover { "ALERT" } else { "OK" },
+            &resolved_account,
+            &resolved_symbol,
+        );
+
+        Ok(TurnoverIndicator {
+            account_id: resolved_account,
+            symbol: resolved_symbol,
+            window_days,
+            buy_quantity,
+            sell_quantity,
+            net_position,
+            delivered_quantity,
+            round_trip_quantity,
+            turnover_ratio: format!("{:.2}x", turnover_ratio),
+            is_excessive_turnover,
+            disabled: false,
+        })
+    }
+
+    /// See BehaviorDeviationReport's doc comment for the window definitions
+    /// and the caveat about the underlying feed being synthetic.
+    #[mutate]
+    async fn generate_behavior_deviation_report(&mut self, entity_id: String, event_timestamp: u64) -> Result<BehaviorDeviationReport, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+
+        self.update_cache("generate_behavior_deviation_report", &resolved_entity, "",
+            &format!("Behavior deviation report for {} around {}", resolved_entity, event_timestamp));
+
+        const RECENT_WINDOW_MS: u64 = 10 * 86_400_000;
+        const BASELINE_WINDOW_DAYS: u64 = 180;
+        const BASELINE_WINDOW_MS: u64 = BASELINE_WINDOW_DAYS * 86_400_000;
+
+        let config = self.secrets.config();
+        if config.trade_data_contract_id.is_empty() {
+            return Err("trade_data_contract_id not configured".to_string());
+        }
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let trades = trade_data_mcp
+            .get_trades_by_account(resolved_entity.clone(), 300)
+            .map_err(|e| format!("Failed to fetch trades: {}", e))?;
+
+        let recent_start = event_timestamp.saturating_sub(RECENT_WINDOW_MS);
+        let baseline_start = event_timestamp.saturating_sub(RECENT_WINDOW_MS + BASELINE_WINDOW_MS);
+
+        let recent_trades: Vec<&Trade> = trades.iter()
+            .filter(|t| t.timestamp >= recent_start && t.timestamp < event_timestamp)
+            .collect();
+        let baseline_trades: Vec<&Trade> = trades.iter()
+            .filter(|t| t.timestamp >= baseline_start && t.timestamp < recent_start)
+            .collect();
+
+        let symbols_of = |ts: &[&Trade]| -> Vec<String> {
+            let mut symbols: Vec<String> = ts.iter().map(|t| t.symbol.clone()).collect();
+            symbols.sort();
+            symbols.dedup();
+            symbols
+        };
+        let baseline_symbols = symbols_of(&baseline_trades);
+        let recent_symbols = symbols_of(&recent_trades);
+        let new_symbols: Vec<String> = recent_symbols.iter()
+            .filter(|s| !baseline_symbols.contains(s))
+            .cloned()
+            .collect();
+
+        let avg_quantity = |ts: &[&Trade]| -> f64 {
+            if ts.is_empty() { return 0.0; }
+            ts.iter().map(|t| t.quantity as f64).sum::<f64>() / ts.len() as f64
+        };
+        let buy_ratio = |ts: &[&Trade]| -> f64 {
+            if ts.is_empty() { return 0.0; }
+            let buy: f64 = ts.iter().filter(|t| t.trade_type == "BUY").map(|t| t.quantity as f64).sum();
+            let total: f64 = ts.iter().map(|t| t.quantity as f64).sum();
+            if total == 0.0 { 0.0 } else { buy / total }
+        };
+        let deviation_pct = |baseline: f64, recent: f64| -> f64 {
+            if baseline == 0.0 {
+                if recent == 0.0 { 0.0 } else { 100.0 }
+            } else {
+                (recent - baseline) / baseline * 100.0
+            }
+        };
+
+        let baseline_per_day = baseline_trades.len() as f64 / BASELINE_WINDOW_DAYS as f64;
+        let recent_per_day = recent_trades.len() as f64 / 10.0;
+        let baseline_avg_size = avg_quantity(&baseline_trades);
+        let recent_avg_size = avg_quantity(&recent_trades);
+        let baseline_buy_ratio = buy_ratio(&baseline_trades);
+        let recent_buy_ratio = buy_ratio(&recent_trades);
+
+        let deviations = vec![
+            BehaviorMetricDeviation {
+                metric: "trade_frequency".to_string(),
+                baseline_value: format!("{:.2}/day", baseline_per_day),
+                recent_value: format!("{:.2}/day", recent_per_day),
+                deviation_pct: format!("{:.2}%", deviation_pct(baseline_per_day, recent_per_day)),
+            },
+            BehaviorMetricDeviation {
+                metric: "avg_trade_size".to_string(),
+                baseline_value: format!("{:.0}", baseline_avg_size),
+                recent_value: format!("{:.0}", recent_avg_size),
+                deviation_pct: format!("{:.2}%", deviation_pct(baseline_avg_size, recent_avg_size)),
+            },
+            BehaviorMetricDeviation {
+                metric: "buy_ratio".to_string(),
+                baseline_value: format!("{:.2}", baseline_buy_ratio),
+                recent_value: format!("{:.2}", recent_buy_ratio),
+                deviation_pct: format!("{:.2}%", deviation_pct(baseline_buy_ratio, recent_buy_ratio)),
+            },
+        ];
+
+        let frequency_spike = recent_per_day > baseline_per_day * 2.0 && recent_trades.len() >= 2;
+        let size_spike = recent_avg_size > baseline_avg_size * 2.0 && !recent_trades.is_empty();
+        let is_significant = frequency_spike || size_spike || !new_symbols.is_empty();
+
+        let narrative = format!(
+            "{} traded {} time(s) in the 10 days before {} vs a baseline of {:.2}/day over the prior {} days ({:+.2}% frequency deviation); average trade size moved {:+.2}%; {}",
+            resolved_entity,
+            recent_trades.len(),
+            event_timestamp,
+            baseline_per_day,
+            BASELINE_WINDOW_DAYS,
+            deviation_pct(baseline_per_day, recent_per_day),
+            deviation_pct(baseline_avg_size, recent_avg_size),
+            if new_symbols.is_empty() {
+                "no symbols new to this entity's trading history appeared in the recent window".to_string()
+            } else {
+                format!("new symbols entered the recent window: {}", new_symbols.join(", "))
+            }
+        );
+
+        let mut case_id = String::new();
+        if is_significant {
+            self.maybe_push_alert(
+                "BEHAVIOR_DEVIATION",
+                60,
+                &resolved_entity,
+                "",
+                &narrative,
+            );
+            case_id = format!("CASE-BEHAVIOR_DEVIATION-{}", 0u64);
+            self.create_case(
+                "BEHAVIOR_DEVIATION",
+                &resolved_entity,
+                "",
+                60,
+                &narrative,
+            );
+        }
+
+        self.push_history(
+            "generate_behavior_deviation_report",
+            &format!("entity_id={}, event_timestamp={}", resolved_entity, event_timestamp),
+            &narrative,
+            if is_significant { "ALERT" } else { "OK" },
+            &resolved_entity,
+            "",
+        );
+
+        Ok(BehaviorDeviationReport {
+            entity_id: resolved_entity,
+            event_timestamp,
+            baseline_trade_count: baseline_trades.len() as u32,
+            recent_trade_count: recent_trades.len() as u32,
+            baseline_symbols,
+            recent_symbols,
+            new_symbols,
+            deviations,
+            narrative,
+            case_id,
+        })
+    }
+
+    /// Flags order-message bursts around a symbol. This platform only ingests
+    /// executed trades, not raw order/cancel messages, so message_rate and
+    /// order_to_trade_ratio are approximated from trade frequency and distinct
+    /// order_ids per trade rather than true order-book traffic; see
+    /// QuoteStuffingIndicator's doc comment for what that means for accuracy.
+    #[mutate]
+    async fn detect_quote_stuffing(&mut self, symbol: String, window_seconds: u64) -> Result<QuoteStuffingIndicator, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+
+        if !self.detector_enabled("quote_stuffing", &resolved_symbol) {
+            return Ok(QuoteStuffingIndicator {
+                symbol: resolved_symbol,
+                window_seconds,
+                message_count: 0,
+                message_rate: "".to_string(),
+                order_to_trade_ratio: "".to_string(),
+                short_lived_order_pct: "".to_string(),
+                is_stuffing: false,
+                contributing_accounts: Vec::new(),
+                disabled: true,
+            });
+        }
+
+        self.update_cache("detect_quote_stuffing", "", &resolved_symbol,
+            &format!("Check quote stuffing on {} over {}s", resolved_symbol, window_seconds));
+
+        let config = self.secrets.config();
+        if config.trade_data_contract_id.is_empty() {
+            return Err("trade_data_contract_id not configured".to_string());
+        }
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let trades = trade_data_mcp.get_trades_by_symbol(resolved_symbol.clone(), 500)
+            .map_err(|e| e.to_string())?;
+
+        // Trade timestamps are epoch milliseconds, counting back from the
+        // synthetic feed's fixed base timestamp
+        let now = 1737225600000u64;
+        let window_start = now.saturating_sub(window_seconds.saturating_mul(1000));
+        let windowed: Vec<_> = trades.iter().filter(|t| t.timestamp >= window_start && t.timestamp <= now).collect();
+
+        let message_count = windowed.len() as u32;
+        let message_rate = if window_seconds > 0 { message_count as f64 / window_seconds as f64 } else { 0.0 };
+
+        let mut distinct_orders: Vec<&String> = Vec::new();
+        let mut contributing_accounts: Vec<String> = Vec::new();
+        for trade in &windowed {
+            if !distinct_orders.contains(&&trade.order_id) {
+                distinct_orders.push(&trade.order_id);
+            }
+            if !contributing_accounts.contains(&trade.account_id) {
+                contributing_accounts.push(trade.account_id.clone());
+            }
+        }
+        let order_to_trade_ratio = if message_count > 0 { distinct_orders.len() as f64 / message_count as f64 } else { 0.0 };
+
+        let (rate_threshold, ratio_threshold) = self.quote_stuffing_thresholds(&resolved_symbol);
+        let is_stuffing = message_rate > rate_threshold as f64 || order_to_trade_ratio > ratio_threshold;
+
+        self.log_workflow(
+            &format!("WF-STUFF-{}", resolved_symbol),
+            "QUOTE_STUFFING_DETECTION",
+            &format!("Check quote stuffing on {}", resolved_symbol),
+        );
+
+        if is_stuffing {
+            self.maybe_push_alert(
+                "QUOTE_STUFFING",
+                80,
+                "",
+                &resolved_symbol,
+                &format!("Quote stuffing suspected on {}: {:.2} msgs/sec, order/trade ratio {:.2}, accounts: {}", resolved_symbol, message_rate, order_to_trade_ratio, contributing_accounts.join(", ")),
+            );
+            self.create_case(
+                "QUOTE_STUFFING",
+                "",
+                &resolved_symbol,
+                65,
+                &format!("Quote stuffing burst on {}", resolved_symbol),
+            );
+        }
+
+        self.push_history(
+            "detect_quote_stuffing",
+            &format!("symbol={}, window_seconds={}", resolved_symbol, window_seconds),
+            &format!("is_stuffing={}", is_stuffing),
+            if is_stuffing { "ALERT" } else { "OK" },
+            "",
+            &resolved_symbol,
+        );
+
+        Ok(QuoteStuffingIndicator {
+            symbol: resolved_symbol,
+            window_seconds,
+            message_count,
+            message_rate: format!("{:.2}", message_rate),
+            order_to_trade_ratio: format!("{:.2}", order_to_trade_ratio),
+            short_lived_order_pct: "0.00".to_string(),
+            is_stuffing,
+            contributing_accounts,
+            disabled: false,
         })
     }
 
+    #[query]
+    fn get_quote_stuffing_rules(&self) -> Vec<QuoteStuffingRule> {
+        self.quote_stuffing_rules.clone()
+    }
+
+    #[mutate]
+    fn set_quote_stuffing_rules(&mut self, rules: Vec<QuoteStuffingRule>) -> Vec<QuoteStuffingRule> {
+        self.quote_stuffing_rules = rules;
+        self.quote_stuffing_rules.clone()
+    }
+
+    #[query]
+    fn get_pump_dump_benchmark_rules(&self) -> Vec<PumpDumpBenchmarkRule> {
+        self.pump_dump_benchmark_rules.clone()
+    }
+
+    #[mutate]
+    fn set_pump_dump_benchmark_rules(&mut self, rules: Vec<PumpDumpBenchmarkRule>) -> Vec<PumpDumpBenchmarkRule> {
+        self.pump_dump_benchmark_rules = rules;
+        self.pump_dump_benchmark_rules.clone()
+    }
+
     /// Detect Pump & Dump schemes
     #[mutate]
     async fn detect_pump_dump(&mut self, symbol: String, time_window_minutes: u32) -> Result<PumpDumpIndicator, String> {
+        self.maintenance_guard()?;
         // Resolve partial symbol from cache
         let resolved_symbol = self.resolve_symbol(&symbol);
-        
+
+        if !self.detector_enabled("pump_dump", &resolved_symbol) {
+            return Ok(PumpDumpIndicator {
+                symbol: resolved_symbol,
+                is_pump_dump: false,
+                price_velocity: "".to_string(),
+                volume_surge: "".to_string(),
+                social_sentiment_score: 0,
+                benchmark_change_pct: "".to_string(),
+                excess_move_pct: "".to_string(),
+                disabled: true,
+            });
+        }
+
         // Update cache with resolved value
-        self.update_cache("detect_pump_dump", "", &resolved_symbol, 
+        self.update_cache("detect_pump_dump", "", &resolved_symbol,
             &format!("Check pump and dump on {} in last {} minutes", resolved_symbol, time_window_minutes));
-        
+
         // Use Alpha Vantage to check price velocity and volume surge
         let quote = self.get_quote(&resolved_symbol).await?;
-        
+
         let change_str = quote.change_percent.trim_end_matches('%');
         let change_pct: f64 = change_str.parse().unwrap_or(0.0);
-        
-        // Heuristic: Price up > 10% in short time is suspicious
-        let is_pump = change_pct > 10.0;
-        
+
+        // Compare against an index/sector ETF's move over the same window so a
+        // broad market rally doesn't get flagged symbol-by-symbol. If no benchmark
+        // is configured, or the benchmark quote fails, fall back to a flat 0%
+        // benchmark (equivalent to the old raw-threshold check).
+        let benchmark_symbol = self.secrets.config().benchmark_symbol.clone();
+        let benchmark_change_pct = if benchmark_symbol.is_empty() {
+            0.0
+        } else {
+            match self.get_quote(&benchmark_symbol).await {
+                Ok(bq) => bq.change_percent.trim_end_matches('%').parse().unwrap_or(0.0),
+                Err(_) => 0.0,
+            }
+        };
+
+        let (beta, excess_move_threshold) = self.pump_dump_benchmark_params(&resolved_symbol);
+        let expected_move_pct = benchmark_change_pct * beta;
+        let excess_move_pct = change_pct - expected_move_pct;
+
+        // Scale the raw price-move threshold by liquidity instead of a flat
+        // constant - a microcap needs a bigger move to clear ordinary thin-book
+        // noise than a mega-cap does. Falls back to the old flat 10% if
+        // trade_data_contract_id isn't configured or the classification call fails.
+        let trade_data_contract_id = self.secrets.config().trade_data_contract_id.clone();
+        let price_move_threshold = if trade_data_contract_id.is_empty() {
+            10.0
+        } else {
+            TradeDataMcp::new(trade_data_contract_id)
+                .get_liquidity_class(resolved_symbol.clone())
+                .ok()
+                .and_then(|c| c.price_move_threshold_pct.parse::<f64>().ok())
+                .unwrap_or(10.0)
+        };
+
+        // Heuristic: Price up past the liquidity-scaled threshold in short time
+        // is suspicious, but only flag it once the move clears the benchmark by
+        // more than the configured threshold
+        let is_pump = change_pct > price_move_threshold && excess_move_pct > excess_move_threshold;
+
         // Push alert to dashboard if pump & dump detected
         if is_pump {
             self.maybe_push_alert(
                 "PUMP_DUMP",
-                "CRITICAL",
-                85,
+                90,
                 "",
                 &resolved_symbol,
-                &format!("Pump & Dump detected: {} has {}% price change in {} min window", resolved_symbol, change_pct, time_window_minutes),
+                &format!("Pump & Dump detected: {} has {}% price change in {} min window ({}% excess over benchmark)", resolved_symbol, change_pct, time_window_minutes, excess_move_pct),
             );
         } else {
              self.maybe_push_alert(
                 "PUMP_DUMP_CHECK",
-                "INFO",
                 10,
                 "",
                 &resolved_symbol,
                 &format!("Pump & Dump check passed: {} has {}% price change (normal)", resolved_symbol, change_pct),
             );
         }
-        
+
         // Push history
         self.push_history(
             "detect_pump_dump",
             &format!("symbol={}, window={}min", resolved_symbol, time_window_minutes),
-            &format!("is_pump_dump={}, change={}%", is_pump, change_pct),
+            &format!("is_pump_dump={}, change={}%, excess={}%", is_pump, change_pct, excess_move_pct),
             if is_pump { "ALERT" } else { "OK" },
             "",
             &resolved_symbol,
         );
-        
+
         Ok(PumpDumpIndicator {
             symbol: resolved_symbol,
             is_pump_dump: is_pump,
             price_velocity: format!("{}%", change_pct),
             volume_surge: "High".to_string(),
             social_sentiment_score: if is_pump { 85 } else { 40 },
+            benchmark_change_pct: format!("{:.2}%", benchmark_change_pct),
+            excess_move_pct: format!("{:.2}%", excess_move_pct),
+            disabled: false,
         })
     }
 
     /// Detect potential front-running (placeholder for logic requiring high-frequency data)
     #[mutate]
     async fn detect_front_running(&mut self, entity_id: String, symbol: String, client_trade_timestamp: u64, prop_trade_timestamp: u64) -> Result<AnomalyResult, String> {
+        self.maintenance_guard()?;
         // Cross-parameter resolution
         let (resolved_entity, resolved_symbol) = self.resolve_from_cache(&entity_id, &symbol);
-        
+
+        if !self.detector_enabled("front_running", &resolved_symbol) {
+            return Ok(AnomalyResult {
+                entity_id: resolved_entity,
+                symbol: resolved_symbol,
+                anomaly_type: "FRONT_RUNNING".to_string(),
+                confidence_score: 0,
+                details: "".to_string(),
+                timestamp: 0,
+                supporting_evidence: "".to_string(),
+                disabled: true,
+            });
+        }
+
         // Update cache
-        self.update_cache("detect_front_running", &resolved_entity, &resolved_symbol, 
+        self.update_cache("detect_front_running", &resolved_entity, &resolved_symbol,
             &format!("Check front running for {} on {}", resolved_entity, resolved_symbol));
         
         let client_ts = client_trade_timestamp;
@@ -788,7 +2207,6 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         if is_suspicious {
             self.maybe_push_alert(
                 "FRONT_RUNNING",
-                "CRITICAL",
                 90,
                 &resolved_entity,
                 &resolved_symbol,
@@ -797,7 +2215,6 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         } else {
             self.maybe_push_alert(
                 "FRONT_RUNNING_CHECK",
-                "INFO",
                 10,
                 &resolved_entity,
                 &resolved_symbol,
@@ -823,35 +2240,65 @@ impl AnomalyDetection for AnomalyDetectionContractState {
             details: format!("Trade gap: {}s", diff),
             timestamp: prop_ts,
             supporting_evidence: "Prop desk trade executed immediately prior to large client order".to_string(),
+            disabled: false,
         })
     }
 
     #[mutate]
     async fn analyze_volume_anomaly(&mut self, symbol: String, interval: String) -> Result<AnomalyResult, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
-        
-        self.update_cache("analyze_volume_anomaly", "", &resolved_symbol, 
-            &format!("Check volume anomaly on {} with {} interval", resolved_symbol, interval));
+
+        if !self.detector_enabled("volume_anomaly", &resolved_symbol) {
+            return Ok(AnomalyResult {
+                entity_id: "MARKET".to_string(),
+                symbol: resolved_symbol,
+                anomaly_type: "VOLUME_SPIKE".to_string(),
+                confidence_score: 0,
+                details: "".to_string(),
+                timestamp: 0,
+                supporting_evidence: "".to_string(),
+                disabled: true,
+            });
+        }
+
+        self.update_cache("analyze_volume_anomaly", "", &resolved_symbol,
+            &format!("Check volume anomaly on {} with {} interval", resolved_symbol, interval));
         
         let quote = self.get_quote(&resolved_symbol).await?;
-        
+
         let volume: u64 = quote.volume.parse().unwrap_or(0);
-        
-        let is_anomaly = volume > 1000000;
-        
+
+        // Scale against this symbol's own average daily volume and liquidity-
+        // class ratio threshold instead of a flat 1M-share constant - 1M shares
+        // is nothing for a mega-cap and everything for a microcap. Falls back
+        // to the old flat constant if trade_data_contract_id isn't configured
+        // or the classification call fails.
+        let trade_data_contract_id = self.secrets.config().trade_data_contract_id.clone();
+        let is_anomaly = if trade_data_contract_id.is_empty() {
+            volume > 1_000_000
+        } else {
+            match TradeDataMcp::new(trade_data_contract_id).get_liquidity_class(resolved_symbol.clone()) {
+                Ok(classification) if classification.avg_daily_volume > 0 => {
+                    let ratio = volume as f64 / classification.avg_daily_volume as f64;
+                    let threshold = classification.volume_ratio_threshold.parse::<f64>().unwrap_or(2.5);
+                    ratio > threshold
+                }
+                _ => volume > 1_000_000,
+            }
+        };
+
         if is_anomaly {
             self.maybe_push_alert(
                 "VOLUME_SPIKE",
-                "MEDIUM",
-                60,
+                90,
                 "MARKET",
                 &resolved_symbol,
-                &format!("Volume spike detected: {} volume > 1M", volume),
+                &format!("Volume spike detected: {} volume {} above liquidity-scaled threshold", resolved_symbol, volume),
             );
         } else {
              self.maybe_push_alert(
                 "VOLUME_CHECK",
-                "INFO",
                 10,
                 "MARKET",
                 &resolved_symbol,
@@ -877,11 +2324,13 @@ impl AnomalyDetection for AnomalyDetectionContractState {
             details: format!("Current Volume: {}", volume),
             timestamp: 0,
             supporting_evidence: "Volume analysis from Alpha Vantage".to_string(),
+            disabled: false,
         })
     }
 
     #[mutate]
     async fn check_rsi_levels(&mut self, symbol: String) -> Result<String, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
         
         self.update_cache("check_rsi_levels", "", &resolved_symbol, 
@@ -892,8 +2341,7 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         if rsi > 70.0 {
             self.maybe_push_alert(
                 "RSI_OVERBOUGHT",
-                "HIGH",
-                70,
+                90,
                 "MARKET",
                 &resolved_symbol,
                 &format!("RSI Overbought: {:.2} > 70", rsi),
@@ -910,8 +2358,7 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         } else if rsi < 30.0 {
             self.maybe_push_alert(
                 "RSI_OVERSOLD",
-                "MEDIUM",
-                50,
+                90,
                 "MARKET",
                 &resolved_symbol,
                 &format!("RSI Oversold: {:.2} < 30", rsi),
@@ -928,7 +2375,6 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         } else {
             self.maybe_push_alert(
                 "RSI_CHECK",
-                "INFO",
                 10,
                 "MARKET",
                 &resolved_symbol,
@@ -949,10 +2395,245 @@ impl AnomalyDetection for AnomalyDetectionContractState {
     #[query]
     async fn scan_entity_anomalies(&self, entity_id: String) -> Result<Vec<AnomalyResult>, String> {
         let resolved_entity = self.resolve_entity(&entity_id);
-        
+
         Ok(vec![])
     }
 
+    /// Morning sweep: pull the day's top gainers/losers and flag each one on flat
+    /// thresholds (|change| > 10% as pump/dump, volume > 1M shares as a volume
+    /// anomaly), so analysts get a proactive scan instead of waiting for a symbol
+    /// to be flagged. The benchmark and liquidity scaling that detect_pump_dump
+    /// and analyze_volume_anomaly apply is not used here.
+    #[mutate]
+    async fn scan_market_movers(&mut self) -> Result<MarketMoversSummary, String> {
+        self.maintenance_guard()?;
+        self.update_cache("scan_market_movers", "", "",
+            "Scan today's top market movers for pump-and-dump and volume anomalies");
+
+        let top_movers = self.fetch_top_movers().await?;
+
+        let mut candidates: Vec<TopMoverEntry> = Vec::new();
+        candidates.extend(top_movers.top_gainers.into_iter().take(10));
+        candidates.extend(top_movers.top_losers.into_iter().take(10));
+
+        let mut movers = Vec::new();
+        let mut flagged_count = 0u32;
+
+        for entry in candidates {
+            let resolved_symbol = self.resolve_symbol(&entry.ticker);
+            let change_pct: f64 = entry.change_percentage.trim_end_matches('%').parse().unwrap_or(0.0);
+            let volume: u64 = entry.volume.parse().unwrap_or(0);
+
+            let is_pump_dump = change_pct.abs() > 10.0;
+            let is_volume_anomaly = volume > 1_000_000;
+
+            if is_pump_dump || is_volume_anomaly {
+                flagged_count += 1;
+            }
+
+            movers.push(MarketMoverResult {
+                symbol: resolved_symbol,
+                change_percent: entry.change_percentage,
+                volume,
+                is_pump_dump,
+                is_volume_anomaly,
+            });
+        }
+
+        self.maybe_push_alert(
+            "DAILY_MOVERS_SURVEILLANCE",
+            if flagged_count > 0 { 70 } else { 10 },
+            "MARKET",
+            "ALL",
+            &format!("Daily movers sweep: {} of {} scanned symbols flagged for pump/dump or volume anomalies", flagged_count, movers.len()),
+        );
+
+        self.push_history(
+            "scan_market_movers",
+            &format!("scanned={}", movers.len()),
+            &format!("flagged={}", flagged_count),
+            if flagged_count > 0 { "ALERT" } else { "OK" },
+            "MARKET",
+            "ALL",
+        );
+
+        Ok(MarketMoversSummary {
+            movers,
+            flagged_count,
+            scan_timestamp: 0,
+        })
+    }
+
+    #[query]
+    fn get_severity_matrix(&self) -> Vec<SeverityMatrixEntry> {
+        self.severity_matrix.clone()
+    }
+
+    #[mutate]
+    fn set_severity_matrix(&mut self, entries: Vec<SeverityMatrixEntry>) -> Vec<SeverityMatrixEntry> {
+        self.severity_matrix = entries;
+        self.severity_matrix.clone()
+    }
+
+    #[query]
+    fn get_wash_trade_rules(&self) -> Vec<WashTradeRule> {
+        self.wash_trade_rules.clone()
+    }
+
+    #[mutate]
+    fn set_wash_trade_rules(&mut self, rules: Vec<WashTradeRule>) -> Vec<WashTradeRule> {
+        self.wash_trade_rules = rules;
+        self.wash_trade_rules.clone()
+    }
+
+    /// Validates expression_json (feature/operator allow-list, well-formed
+    /// cmp/and/or/not tree) via validate_expr, then upserts by name so
+    /// evaluate_rules picks up the new/changed rule on its next call
+    #[mutate]
+    fn add_rule(&mut self, name: String, expression_json: String) -> Result<DetectionRule, String> {
+        let parsed: serde_json::Value = serde_json::from_str(&expression_json)
+            .map_err(|e| format!("expression_json is not valid JSON: {}", e))?;
+        validate_expr(&parsed)?;
+
+        let rule = DetectionRule {
+            name: name.clone(),
+            expression_json,
+            enabled: true,
+            created_at: 0,
+        };
+
+        if let Some(existing) = self.custom_rules.iter_mut().find(|r| r.name == name) {
+            *existing = rule.clone();
+        } else {
+            self.custom_rules.push(rule.clone());
+        }
+
+        Ok(rule)
+    }
+
+    #[query]
+    fn get_rules(&self) -> Vec<DetectionRule> {
+        self.custom_rules.clone()
+    }
+
+    /// Gathers price_change_pct/rsi from Alpha Vantage/TAAPI, volume_ratio from
+    /// trade_data_mcp's detect_volume_anomaly, and is_insider/window_closed from
+    /// entity_relationship_mcp's check_insider_status into one feature map, then
+    /// runs every enabled custom rule's expression against it
+    #[mutate]
+    async fn evaluate_rules(&mut self, symbol: String, entity_id: String) -> Result<Vec<RuleEvaluation>, String> {
+        #[derive(Debug, Serialize)]
+        struct CheckInsiderStatusArgs {
+            entity_id: String,
+            company_symbol: String,
+        }
+        #[derive(Debug, Deserialize)]
+        struct InsiderStatus {
+            is_insider: bool,
+            window_status: String,
+        }
+
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+
+        let mut features: HashMap<String, f64> = HashMap::new();
+
+        let quote = self.get_quote(&resolved_symbol).await?;
+        let price_change_pct: f64 = quote.change_percent.trim_end_matches('%').parse().unwrap_or(0.0);
+        features.insert("price_change_pct".to_string(), price_change_pct);
+
+        let rsi = self.get_rsi(&resolved_symbol).await?;
+        features.insert("rsi".to_string(), rsi);
+
+        let config = self.secrets.config();
+        if !config.trade_data_contract_id.is_empty() {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            if let Ok(volume_anomaly) = trade_data_mcp.detect_volume_anomaly(resolved_symbol.clone()) {
+                let volume_ratio: f64 = volume_anomaly.volume_ratio.parse().unwrap_or(0.0);
+                features.insert("volume_ratio".to_string(), volume_ratio);
+            }
+        }
+
+        if !config.entity_relationship_contract_id.is_empty() {
+            let args = serde_json::to_string(&CheckInsiderStatusArgs {
+                entity_id: entity_id.clone(),
+                company_symbol: resolved_symbol.clone(),
+            }).unwrap();
+            if let Ok(status) = Runtime::call_contract::<InsiderStatus>(
+                config.entity_relationship_contract_id.clone(),
+                "check_insider_status".to_string(),
+                Some(args),
+            ) {
+                features.insert("is_insider".to_string(), if status.is_insider { 1.0 } else { 0.0 });
+                features.insert("window_closed".to_string(), if status.window_status == "OPEN" { 0.0 } else { 1.0 });
+            }
+        }
+
+        let mut results = Vec::new();
+        for rule in self.custom_rules.iter().filter(|r| r.enabled) {
+            let evaluation = match serde_json::from_str::<serde_json::Value>(&rule.expression_json)
+                .map_err(|e| e.to_string())
+                .and_then(|expr| eval_expr(&expr, &features))
+            {
+                Ok(triggered) => RuleEvaluation { rule_name: rule.name.clone(), triggered, error: "".to_string() },
+                Err(e) => RuleEvaluation { rule_name: rule.name.clone(), triggered: false, error: e },
+            };
+            results.push(evaluation);
+        }
+
+        self.push_history(
+            "evaluate_rules",
+            &format!("symbol={}", resolved_symbol),
+            &format!("rules_evaluated={}", results.len()),
+            "SUCCESS",
+            &entity_id,
+            &resolved_symbol,
+        );
+
+        Ok(results)
+    }
+
+    /// Upserts the flag for (detector, symbol_or_all); pass "ALL" as symbol_or_all
+    /// to cover every symbol unless a more specific per-symbol flag exists
+    #[mutate]
+    fn set_detector_enabled(&mut self, detector: String, symbol_or_all: String, enabled: bool) -> DetectorFlag {
+        if let Some(flag) = self.detector_flags.iter_mut().find(|f| f.detector.eq_ignore_ascii_case(&detector) && f.symbol.eq_ignore_ascii_case(&symbol_or_all)) {
+            flag.enabled = enabled;
+            return flag.clone();
+        }
+        let flag = DetectorFlag { detector, symbol: symbol_or_all, enabled };
+        self.detector_flags.push(flag.clone());
+        flag
+    }
+
+    #[query]
+    fn get_detector_flags(&self) -> Vec<DetectorFlag> {
+        self.detector_flags.clone()
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    async fn flush_history(&mut self) -> Result<u32, String> {
+        let before = self.history_buffer.len();
+        self.flush_history_buffer();
+        Ok((before - self.history_buffer.len()) as u32)
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -1035,11 +2716,193 @@ impl AnomalyDetection for AnomalyDetectionContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "resolve_reference",
+      "description": "Resolve a partial entity or symbol reference against recent query context, returning a confidence score and up to 3 alternative candidates\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "kind": {
+            "type": "string",
+            "description": "\"entity\" or \"symbol\"\n"
+          },
+          "partial": {
+            "type": "string",
+            "description": "Partial or misspelled reference to resolve\n"
+          }
+        },
+        "required": [
+          "kind",
+          "partial"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "detect_coordinated_trading",
+      "description": "Detect coordinated trading by a UPSI's accessors and their 1-hop relations during its active window\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "upsi_id": {
+            "type": "string",
+            "description": "UPSI ID whose accessors to check\n"
+          }
+        },
+        "required": [
+          "upsi_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "detect_excessive_turnover",
+      "description": "Detect an account churning intraday volume far past what its net (delivered) position on a symbol requires\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "account_id": {
+            "type": "string",
+            "description": "Trading account ID\n"
+          },
+          "symbol": {
+            "type": "string",
+            "description": "Stock symbol\n"
+          },
+          "window_days": {
+            "type": "integer",
+            "description": "Informational only - no persisted per-day trade store to window against\n"
+          }
+        },
+        "required": [
+          "account_id",
+          "symbol",
+          "window_days"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_behavior_deviation_report",
+      "description": "Compare an entity's trading in the 10 days before an event against its 6-month baseline (frequency, size, direction) and attach the result to a case if the deviation is significant\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity ID - supports fuzzy matching\n"
+          },
+          "event_timestamp": {
+            "type": "integer",
+            "description": "Epoch milliseconds UTC marking the event to compare trading around\n"
+          }
+        },
+        "required": [
+          "entity_id",
+          "event_timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "detect_quote_stuffing",
+      "description": "Detect order-message bursts (quote stuffing) around a symbol from message rate and order-to-trade ratio\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "symbol": {
+            "type": "string",
+            "description": "Stock symbol\n"
+          },
+          "window_seconds": {
+            "type": "integer",
+            "description": "Lookback window in seconds\n"
+          }
+        },
+        "required": [
+          "symbol",
+          "window_seconds"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_quote_stuffing_rules",
+      "description": "Get the configurable per-symbol quote-stuffing burst thresholds\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_quote_stuffing_rules",
+      "description": "Replace the quote-stuffing rule set, so compliance can tune per-symbol burst thresholds without a code change\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "rules": {
+            "type": "array",
+            "description": "New rule set (symbol, message_rate_threshold, order_to_trade_ratio_threshold)\n"
+          }
+        },
+        "required": [
+          "rules"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_pump_dump_benchmark_rules",
+      "description": "Get the configurable per-symbol beta/excess-move thresholds used to benchmark-adjust the pump-dump check\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_pump_dump_benchmark_rules",
+      "description": "Replace the pump-dump benchmark rule set, so compliance can tune per-symbol beta and excess-move thresholds without a code change\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "rules": {
+            "type": "array",
+            "description": "New rule set (symbol, beta, excess_move_threshold)\n"
+          }
+        },
+        "required": [
+          "rules"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
       "name": "detect_pump_dump",
-      "description": "Detect Pump & Dump schemes for a stock\n",
+      "description": "Detect Pump & Dump schemes for a stock, adjusted for the broader market/sector benchmark move over the same window\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -1150,6 +3013,168 @@ impl AnomalyDetection for AnomalyDetectionContractState {
         ]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "scan_market_movers",
+      "description": "Scan today's top gainers/losers for pump-and-dump and volume anomalies\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_severity_matrix",
+      "description": "Get the configurable (alert_type, confidence band) -> (severity, risk_score) matrix\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_severity_matrix",
+      "description": "Replace the severity matrix, so the compliance team can retune alerting without a code change\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entries": {
+            "type": "array",
+            "description": "Full replacement list of (alert_type, confidence band) -> (severity, risk_score) entries\n"
+          }
+        },
+        "required": [
+          "entries"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_wash_trade_rules",
+      "description": "Get the configurable symbol-basket/correlated-instrument and reversal-window rules used to find offsetting trades\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_wash_trade_rules",
+      "description": "Replace the wash trade rule set, so compliance can tune correlated-symbol baskets and reversal windows without a code change\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "rules": {
+            "type": "array",
+            "description": "Full replacement list of symbol -> (correlated_symbols, reversal_window_minutes) rules\n"
+          }
+        },
+        "required": [
+          "rules"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_detector_enabled",
+      "description": "Enable/disable a detector for one symbol or all symbols; disabled detectors return a typed disabled result instead of running\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "detector": {
+            "type": "string",
+            "description": "One of: spoofing, wash_trading, pump_dump, front_running, volume_anomaly, coordinated_trading, excessive_turnover, quote_stuffing\n"
+          },
+          "symbol_or_all": {
+            "type": "string",
+            "description": "Ticker symbol, or ALL to cover every symbol\n"
+          },
+          "enabled": {"type": "boolean"}
+        },
+        "required": ["detector", "symbol_or_all", "enabled"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_detector_flags",
+      "description": "List all detector feature-flag overrides currently in effect\n",
+      "parameters": {
+        "type": "object",
+        "properties": {}
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_circuit_status",
+      "description": "Get the outbound rate-limiter/circuit-breaker status for a host\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "host": {
+            "type": "string",
+            "description": "Host to check, e.g. the Alpha Vantage or TAAPI endpoint URL\n"
+          }
+        },
+        "required": [
+          "host"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_maintenance_mode",
+      "description": "Enable/disable maintenance mode; while enabled, mutating methods return an error instead of writing partial state\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "enabled": {
+            "type": "boolean",
+            "description": "Whether maintenance mode should be on\n"
+          },
+          "message": {
+            "type": "string",
+            "description": "Operator-facing reason shown in the maintenance error and status banner\n"
+          }
+        },
+        "required": [
+          "enabled",
+          "message"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_maintenance_status",
+      "description": "Get the current maintenance-mode banner (enabled flag and message)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
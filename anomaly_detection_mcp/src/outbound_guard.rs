@@ -0,0 +1,110 @@
+
+use serde::{Deserialize, Serialize};
+use weil_macros::WeilType;
+
+/// Per-host token bucket + circuit breaker so one flaky upstream can't burn
+/// the whole per-block execution budget on retries.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct HostCircuit {
+    pub host: String,
+    pub tokens: u32,
+    pub max_tokens: u32,
+    pub consecutive_failures: u32,
+    pub trip_after: u32,
+    pub tripped: bool,
+    pub cooldown_ticks_remaining: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CircuitStatus {
+    pub host: String,
+    pub tripped: bool,
+    pub tokens_available: u32,
+    pub consecutive_failures: u32,
+    pub cooldown_ticks_remaining: u32,
+}
+
+const DEFAULT_MAX_TOKENS: u32 = 20;
+const DEFAULT_TRIP_AFTER: u32 = 5;
+const DEFAULT_COOLDOWN_TICKS: u32 = 10;
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct OutboundGuard {
+    hosts: Vec<HostCircuit>,
+}
+
+impl OutboundGuard {
+    fn find_or_init(&mut self, host: &str) -> usize {
+        if let Some(idx) = self.hosts.iter().position(|h| h.host == host) {
+            return idx;
+        }
+        self.hosts.push(HostCircuit {
+            host: host.to_string(),
+            tokens: DEFAULT_MAX_TOKENS,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            consecutive_failures: 0,
+            trip_after: DEFAULT_TRIP_AFTER,
+            tripped: false,
+            cooldown_ticks_remaining: 0,
+        });
+        self.hosts.len() - 1
+    }
+
+    /// Reserve a token for a call to `host`, or reject if the circuit is open.
+    pub fn check(&mut self, host: &str) -> Result<(), String> {
+        let idx = self.find_or_init(host);
+        let circuit = &mut self.hosts[idx];
+
+        if circuit.tripped {
+            if circuit.cooldown_ticks_remaining > 0 {
+                circuit.cooldown_ticks_remaining -= 1;
+                return Err(format!("circuit open for host {} ({} ticks remaining)", host, circuit.cooldown_ticks_remaining));
+            }
+            circuit.tripped = false;
+            circuit.consecutive_failures = 0;
+            circuit.tokens = circuit.max_tokens;
+        }
+
+        if circuit.tokens == 0 {
+            circuit.tokens = 1; // slow refill so the host isn't starved forever
+        }
+        circuit.tokens -= 1;
+        Ok(())
+    }
+
+    /// Record the outcome of a call so the breaker can trip after N failures.
+    pub fn record_result(&mut self, host: &str, success: bool) {
+        let idx = self.find_or_init(host);
+        let circuit = &mut self.hosts[idx];
+
+        if success {
+            circuit.consecutive_failures = 0;
+            circuit.tokens = (circuit.tokens + 1).min(circuit.max_tokens);
+        } else {
+            circuit.consecutive_failures += 1;
+            if circuit.consecutive_failures >= circuit.trip_after {
+                circuit.tripped = true;
+                circuit.cooldown_ticks_remaining = DEFAULT_COOLDOWN_TICKS;
+            }
+        }
+    }
+
+    pub fn status(&self, host: &str) -> CircuitStatus {
+        match self.hosts.iter().find(|h| h.host == host) {
+            Some(c) => CircuitStatus {
+                host: c.host.clone(),
+                tripped: c.tripped,
+                tokens_available: c.tokens,
+                consecutive_failures: c.consecutive_failures,
+                cooldown_ticks_remaining: c.cooldown_ticks_remaining,
+            },
+            None => CircuitStatus {
+                host: host.to_string(),
+                tripped: false,
+                tokens_available: DEFAULT_MAX_TOKENS,
+                consecutive_failures: 0,
+                cooldown_ticks_remaining: 0,
+            },
+        }
+    }
+}
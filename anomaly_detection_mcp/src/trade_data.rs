@@ -0,0 +1,140 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct TradeDataMcp {
+    contract_id: String,
+}
+
+impl TradeDataMcp {
+    pub fn new(contract_id: String) -> Self {
+        TradeDataMcp { contract_id }
+    }
+}
+
+// ===== Response Types =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Trade {
+    pub trade_id: String,
+    pub symbol: String,
+    pub account_id: String,
+    pub trade_type: String,
+    pub quantity: u64,
+    pub price: String,
+    pub value: String,
+    pub exchange: String,
+    pub segment: String,
+    pub timestamp: u64,
+    pub order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VolumeAnomaly {
+    pub symbol: String,
+    pub current_volume: u64,
+    pub avg_volume_30d: u64,
+    pub volume_ratio: String,
+    pub is_anomaly: bool,
+    pub anomaly_score: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiquidityClassification {
+    pub symbol: String,
+    pub avg_daily_volume: u64,
+    pub days_sampled: u32,
+    pub liquidity_class: String,
+    pub volume_ratio_threshold: String,
+    pub price_move_threshold_pct: String,
+}
+
+impl TradeDataMcp {
+    pub fn detect_volume_anomaly(&self, symbol: String) -> Result<VolumeAnomaly> {
+        #[derive(Debug, Serialize)]
+        struct DetectVolumeAnomalyArgs {
+            symbol: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&DetectVolumeAnomalyArgs { symbol })?);
+
+        let resp = Runtime::call_contract::<VolumeAnomaly>(
+            self.contract_id.clone(),
+            "detect_volume_anomaly".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn get_trades_by_symbol(&self, symbol: String, limit: u32) -> Result<Vec<Trade>> {
+        #[derive(Debug, Serialize)]
+        struct GetTradesBySymbolArgs {
+            symbol: String,
+            limit: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetTradesBySymbolArgs { symbol, limit })?);
+
+        let resp = Runtime::call_contract::<Vec<Trade>>(
+            self.contract_id.clone(),
+            "get_trades_by_symbol".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn get_trades_by_account(&self, account_id: String, limit: u32) -> Result<Vec<Trade>> {
+        #[derive(Debug, Serialize)]
+        struct GetTradesByAccountArgs {
+            account_id: String,
+            limit: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetTradesByAccountArgs { account_id, limit })?);
+
+        let resp = Runtime::call_contract::<Vec<Trade>>(
+            self.contract_id.clone(),
+            "get_trades_by_account".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn get_trades_by_accounts(&self, account_ids: String, symbol: String) -> Result<Vec<Trade>> {
+        #[derive(Debug, Serialize)]
+        struct GetTradesByAccountsArgs {
+            account_ids: String,
+            symbol: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetTradesByAccountsArgs { account_ids, symbol })?);
+
+        let resp = Runtime::call_contract::<Vec<Trade>>(
+            self.contract_id.clone(),
+            "get_trades_by_accounts".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn get_liquidity_class(&self, symbol: String) -> Result<LiquidityClassification> {
+        #[derive(Debug, Serialize)]
+        struct GetLiquidityClassArgs {
+            symbol: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetLiquidityClassArgs { symbol })?);
+
+        let resp = Runtime::call_contract::<LiquidityClassification>(
+            self.contract_id.clone(),
+            "get_liquidity_class".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}
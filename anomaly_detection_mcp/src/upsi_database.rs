@@ -0,0 +1,106 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct UpsiDatabaseMcp {
+    contract_id: String,
+}
+
+impl UpsiDatabaseMcp {
+    pub fn new(contract_id: String) -> Self {
+        UpsiDatabaseMcp { contract_id }
+    }
+}
+
+// ===== Response Types =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UPSIRecord {
+    pub upsi_id: String,
+    pub company_symbol: String,
+    pub upsi_type: String,
+    pub description: String,
+    pub nature: String,
+    pub created_date: u64,
+    pub public_date: u64,
+    pub is_public: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UPSIAccessLog {
+    pub access_id: String,
+    pub upsi_id: String,
+    pub accessor_entity_id: String,
+    pub accessor_name: String,
+    pub accessor_designation: String,
+    pub access_timestamp: u64,
+    pub access_reason: String,
+    pub access_mode: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaginatedAccessLogs {
+    pub items: Vec<UPSIAccessLog>,
+    pub total_count: u32,
+    pub next_offset: u32,
+    pub has_more: bool,
+}
+
+impl UpsiDatabaseMcp {
+    pub fn get_upsi(&self, upsi_id: String) -> Result<UPSIRecord> {
+        #[derive(Debug, Serialize)]
+        struct GetUpsiArgs {
+            upsi_id: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetUpsiArgs { upsi_id })?);
+
+        let resp = Runtime::call_contract::<UPSIRecord>(
+            self.contract_id.clone(),
+            "get_upsi".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    /// upsi_database_mcp paginates this; fetches pages of `page_size` until
+    /// has_more is false and returns every accessor across all pages, since
+    /// coordinated-trading detection needs the full accessor set anyway
+    pub fn get_upsi_accessors(&self, upsi_id: String) -> Result<Vec<UPSIAccessLog>> {
+        const PAGE_SIZE: u32 = 200;
+        let mut offset = 0u32;
+        let mut all = Vec::new();
+        loop {
+            #[derive(Debug, Serialize)]
+            struct GetUpsiAccessorsArgs {
+                upsi_id: String,
+                limit: u32,
+                offset: u32,
+            }
+
+            let serialized_args = Some(serde_json::to_string(&GetUpsiAccessorsArgs {
+                upsi_id: upsi_id.clone(),
+                limit: PAGE_SIZE,
+                offset,
+            })?);
+
+            let page = Runtime::call_contract::<PaginatedAccessLogs>(
+                self.contract_id.clone(),
+                "get_upsi_accessors".to_string(),
+                serialized_args,
+            )?;
+
+            let has_more = page.has_more;
+            let next_offset = page.next_offset;
+            all.extend(page.items);
+
+            if !has_more {
+                break;
+            }
+            offset = next_offset;
+        }
+
+        Ok(all)
+    }
+}
@@ -44,6 +44,15 @@ pub struct EntityConnection {
 }
 
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConnectedEntitiesPage {
+    pub connections: Vec<EntityConnection>,
+    pub page: u32,
+    pub page_size: u32,
+    pub has_more: bool,
+}
+
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InsiderStatus {
     pub entity_id: String,
@@ -153,17 +162,19 @@ impl EntityRelationshipProxy {
         Ok(resp)
     }
 
-    pub fn get_connected_entities(&self, entity_id: String, max_hops: u32) -> Result<Vec<EntityConnection>> {
+    pub fn get_connected_entities(&self, entity_id: String, max_hops: u32, page: Option<u32>, page_size: Option<u32>) -> Result<ConnectedEntitiesPage> {
 
         #[derive(Debug, Serialize)]
         struct get_connected_entitiesArgs {
             entity_id: String,
             max_hops: u32,
+            page: Option<u32>,
+            page_size: Option<u32>,
         }
 
-        let serialized_args = Some(serde_json::to_string(&get_connected_entitiesArgs { entity_id, max_hops }).unwrap());
+        let serialized_args = Some(serde_json::to_string(&get_connected_entitiesArgs { entity_id, max_hops, page, page_size }).unwrap());
 
-        let resp = Runtime::call_contract::<Vec<EntityConnection>>(
+        let resp = Runtime::call_contract::<ConnectedEntitiesPage>(
             self.contract_id.to_string(),
             "get_connected_entities".to_string(),
             serialized_args,
@@ -247,4 +258,26 @@ impl EntityRelationshipProxy {
         Ok(resp)
     }
 
+    pub fn sync_insider_relationship(&self, entity_id: String, company_symbol: String, designation: String, effective_from: u64, active: bool) -> Result<InsiderStatus> {
+
+        #[derive(Debug, Serialize)]
+        struct sync_insider_relationshipArgs {
+            entity_id: String,
+            company_symbol: String,
+            designation: String,
+            effective_from: u64,
+            active: bool,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&sync_insider_relationshipArgs { entity_id, company_symbol, designation, effective_from, active }).unwrap());
+
+        let resp = Runtime::call_contract::<InsiderStatus>(
+            self.contract_id.to_string(),
+            "sync_insider_relationship".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
 }
@@ -0,0 +1,45 @@
+
+use serde::{Deserialize, Serialize};
+use weil_macros::WeilType;
+
+/// One cached mutating-call result, keyed by the caller-supplied
+/// idempotency_key, so an agent's retried call returns the original result
+/// instead of creating a duplicate alert/case. ticks_remaining counts down on
+/// every cache access rather than wall-clock time - this contract has no wall
+/// clock, same idea as OutboundGuard's cooldown_ticks_remaining.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CachedIdempotentResult {
+    pub key: String,
+    pub value: String,
+    pub ticks_remaining: u32,
+}
+
+const DEFAULT_TTL_TICKS: u32 = 50;
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct IdempotencyCache {
+    entries: Vec<CachedIdempotentResult>,
+}
+
+impl IdempotencyCache {
+    fn tick(&mut self) {
+        for entry in self.entries.iter_mut() {
+            if entry.ticks_remaining > 0 {
+                entry.ticks_remaining -= 1;
+            }
+        }
+        self.entries.retain(|e| e.ticks_remaining > 0);
+    }
+
+    /// Look up a prior result for `key`, if still within its TTL
+    pub fn get(&mut self, key: &str) -> Option<String> {
+        self.tick();
+        self.entries.iter().find(|e| e.key == key).map(|e| e.value.clone())
+    }
+
+    /// Remember `value` (a serialized result) under `key` for DEFAULT_TTL_TICKS accesses
+    pub fn put(&mut self, key: &str, value: String) {
+        self.entries.retain(|e| e.key != key);
+        self.entries.push(CachedIdempotentResult { key: key.to_string(), value, ticks_remaining: DEFAULT_TTL_TICKS });
+    }
+}
@@ -0,0 +1,46 @@
+
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use weil_rs::runtime::Runtime;
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimelineEvent {
+    pub timestamp: String,
+    pub day: String,
+    pub actor: String,
+    pub description: String,
+}
+
+
+pub struct JiraProxy {
+    contract_id: String,
+}
+
+impl JiraProxy {
+    pub fn new(contract_id: String) -> Self {
+        JiraProxy {
+            contract_id,
+        }
+    }
+}
+
+impl JiraProxy {
+    pub fn get_case_events(&self, case_id: String) -> Result<Vec<TimelineEvent>> {
+
+        #[derive(Debug, Serialize)]
+        struct get_case_eventsArgs {
+            case_id: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&get_case_eventsArgs { case_id }).unwrap());
+
+        let resp = Runtime::call_contract::<Vec<TimelineEvent>>(
+            self.contract_id.to_string(),
+            "get_case_events".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}
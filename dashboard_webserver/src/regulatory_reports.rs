@@ -21,6 +21,8 @@ pub struct RegulatoryReportsConfig {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct STRReport {
     pub str_id: String,
+    pub case_id: String,
+    pub submitted: bool,
     pub report_date: String,
     pub suspicious_entity_id: String,
     pub suspicious_entity_name: String,
@@ -76,6 +78,19 @@ pub struct ReportResult {
     pub risk_score: u32,
     pub success: bool,
     pub error: String,
+    pub duplicate_of: String,
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EsmStageMove {
+    pub move_id: String,
+    pub symbol: String,
+    pub from_stage: String,
+    pub to_stage: String,
+    pub variation_pct: u32,
+    pub reason: String,
+    pub evaluated_at: u64,
 }
 
 
@@ -126,7 +141,7 @@ impl RegulatoryReportsProxy {
         Ok(resp)
     }
 
-    pub fn generate_str(&self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String) -> Result<ReportResult> {
+    pub fn generate_str(&self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String, force_new: bool) -> Result<ReportResult> {
 
         #[derive(Debug, Serialize)]
         struct generate_strArgs {
@@ -134,9 +149,10 @@ impl RegulatoryReportsProxy {
             entity_id: String,
             suspicious_activity_type: String,
             suspicion_reason: String,
+            force_new: bool,
         }
 
-        let serialized_args = Some(serde_json::to_string(&generate_strArgs { case_id, entity_id, suspicious_activity_type, suspicion_reason }).unwrap());
+        let serialized_args = Some(serde_json::to_string(&generate_strArgs { case_id, entity_id, suspicious_activity_type, suspicion_reason, force_new }).unwrap());
 
         let resp = Runtime::call_contract::<ReportResult>(
             self.contract_id.to_string(),
@@ -258,14 +274,33 @@ impl RegulatoryReportsProxy {
         Ok(resp)
     }
 
-    pub fn submit_str(&self, str_id: String) -> Result<ReportResult> {
+    pub fn get_reports_for_case(&self, case_id: String) -> Result<Vec<STRReport>> {
+
+        #[derive(Debug, Serialize)]
+        struct get_reports_for_caseArgs {
+            case_id: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&get_reports_for_caseArgs { case_id }).unwrap());
+
+        let resp = Runtime::call_contract::<Vec<STRReport>>(
+            self.contract_id.to_string(),
+            "get_reports_for_case".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn submit_str(&self, str_id: String, requested_by: String) -> Result<ReportResult> {
 
         #[derive(Debug, Serialize)]
         struct submit_strArgs {
             str_id: String,
+            requested_by: String,
         }
 
-        let serialized_args = Some(serde_json::to_string(&submit_strArgs { str_id }).unwrap());
+        let serialized_args = Some(serde_json::to_string(&submit_strArgs { str_id, requested_by }).unwrap());
 
         let resp = Runtime::call_contract::<ReportResult>(
             self.contract_id.to_string(),
@@ -313,4 +348,22 @@ impl RegulatoryReportsProxy {
         Ok(resp)
     }
 
+    pub fn get_esm_stage_history(&self, symbol: String) -> Result<Vec<EsmStageMove>> {
+
+        #[derive(Debug, Serialize)]
+        struct get_esm_stage_historyArgs {
+            symbol: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&get_esm_stage_historyArgs { symbol }).unwrap());
+
+        let resp = Runtime::call_contract::<Vec<EsmStageMove>>(
+            self.contract_id.to_string(),
+            "get_esm_stage_history".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
 }
@@ -73,10 +73,11 @@ impl SlackNotifierProxy {
         Ok(resp)
     }
 
-    pub fn send_alert(&self, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult> {
+    pub fn send_alert(&self, alert_id: String, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult> {
 
         #[derive(Debug, Serialize)]
         struct send_alertArgs {
+            alert_id: String,
             alert_type: String,
             severity: String,
             symbol: String,
@@ -85,7 +86,7 @@ impl SlackNotifierProxy {
             risk_score: u32,
         }
 
-        let serialized_args = Some(serde_json::to_string(&send_alertArgs { alert_type, severity, symbol, entity_id, description, risk_score }).unwrap());
+        let serialized_args = Some(serde_json::to_string(&send_alertArgs { alert_id, alert_type, severity, symbol, entity_id, description, risk_score }).unwrap());
 
         let resp = Runtime::call_contract::<NotificationResult>(
             self.contract_id.to_string(),
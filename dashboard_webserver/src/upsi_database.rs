@@ -38,6 +38,25 @@ pub struct UPSIAccessLog {
 }
 
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CsvImportRowError {
+    pub row_number: u32,
+    pub reason: String,
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CsvImportSummary {
+    pub upsi_id: String,
+    pub complete: bool,
+    pub chunks_received: u32,
+    pub total_chunks: u32,
+    pub rows_imported: u32,
+    pub rows_failed: u32,
+    pub errors: Vec<CsvImportRowError>,
+}
+
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TradingWindowStatus {
     pub company_symbol: String,
@@ -212,4 +231,47 @@ impl UPSIDatabaseProxy {
         Ok(resp)
     }
 
+    pub fn create_upsi(&self, company_symbol: String, upsi_type: String, description: String, nature: String, tenant_id: String) -> Result<UPSIRecord> {
+
+        #[derive(Debug, Serialize)]
+        struct create_upsiArgs {
+            company_symbol: String,
+            upsi_type: String,
+            description: String,
+            nature: String,
+            tenant_id: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&create_upsiArgs { company_symbol, upsi_type, description, nature, tenant_id }).unwrap());
+
+        let resp = Runtime::call_contract::<UPSIRecord>(
+            self.contract_id.to_string(),
+            "create_upsi".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn import_access_logs_csv(&self, upsi_id: String, csv_chunk: String, chunk_index: u32, total_chunks: u32) -> Result<CsvImportSummary> {
+
+        #[derive(Debug, Serialize)]
+        struct import_access_logs_csvArgs {
+            upsi_id: String,
+            csv_chunk: String,
+            chunk_index: u32,
+            total_chunks: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&import_access_logs_csvArgs { upsi_id, csv_chunk, chunk_index, total_chunks }).unwrap());
+
+        let resp = Runtime::call_contract::<CsvImportSummary>(
+            self.contract_id.to_string(),
+            "import_access_logs_csv".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
 }
@@ -6,6 +6,13 @@ use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
 use weil_rs::runtime::Runtime;
 
+mod fuzzy_match;
+mod http_fixtures;
+mod outbound_guard;
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
+mod result_cache;
+pub use result_cache::ResultCache;
+
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
@@ -14,10 +21,39 @@ pub struct EntityRelationshipConfig {
     pub neo4j_uri: String,
     pub neo4j_user: String,
     pub neo4j_password: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert's caller_token
+    pub dashboard_caller_token: String,
+    /// How long a cached Neo4j query result stays valid, in cache-access ticks
+    /// rather than wall-clock seconds (this contract has no wall clock) - each
+    /// call to a cached read method ages every entry by one tick. 0 disables
+    /// caching entirely.
+    pub cache_ttl_ticks: u32,
+    /// Where export_graph_snapshot/restore_from_snapshot read and write
+    /// snapshot chunks and manifests, since Neo4j Aura's free tier has no
+    /// built-in backup story
+    pub supabase_url: String,
+    pub supabase_service_key: String,
+    pub supabase_bucket: String,
+    /// "live" (default): call Neo4j for real. "record": call it for real and
+    /// save the response as a fixture. "playback": skip the network and return
+    /// the previously recorded fixture, erroring if none exists - see
+    /// http_fixtures for the whole scheme. Only covers execute_cypher's
+    /// single-statement path, not execute_cypher_tx's multi-step transaction
+    pub http_fixture_mode: String,
 }
 
 // ===== DATA STRUCTURES =====
 
+/// Rotation metadata for a sensitive config field - never the value itself,
+/// so operators can confirm a rotation took effect without exposing the secret
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SecretVersionEntry {
+    pub field_name: String,
+    pub version: u32,
+    pub rotated_at: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct Entity {
     pub entity_id: String,
@@ -46,6 +82,51 @@ pub struct EntityConnection {
     pub relationship_types: String,
 }
 
+/// A relationship whose strength differs between the two diffed timestamps
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct RelationshipStrengthChange {
+    pub source_entity_id: String,
+    pub target_entity_id: String,
+    pub relationship_type: String,
+    pub strength_at_a: u32,
+    pub strength_at_b: u32,
+}
+
+/// Relies on the r.since / r.ended_at / r.previous_strength edge properties;
+/// relationships seeded without them are treated as always present with a
+/// constant strength, so they never show up here - only relationships written
+/// by a caller that records those properties make this diff meaningful
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct EntityNetworkDiff {
+    pub entity_id: String,
+    pub ts_a: u64,
+    pub ts_b: u64,
+    pub added: Vec<Relationship>,
+    pub removed: Vec<Relationship>,
+    pub strength_changed: Vec<RelationshipStrengthChange>,
+}
+
+/// One page of get_connected_entities results; has_more tells the caller
+/// whether a further page (page + 1) would return any more connections
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ConnectedEntitiesPage {
+    pub connections: Vec<EntityConnection>,
+    pub page: u32,
+    pub page_size: u32,
+    pub has_more: bool,
+}
+
+/// Human-readable rendering of are_entities_connected's shortest path, for
+/// dropping straight into an STR suspicion narrative
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ConnectionExplanation {
+    pub entity_id_1: String,
+    pub entity_id_2: String,
+    pub hops: u32,
+    pub connection_path: String,
+    pub narrative: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct InsiderStatus {
     pub entity_id: String,
@@ -56,6 +137,90 @@ pub struct InsiderStatus {
     pub window_status: String,
 }
 
+/// One company in the same corporate group as the queried entity/group, reached
+/// by walking GROUP/SUBSIDIARY edges from the root company
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct GroupCompany {
+    pub company_symbol: String,
+    pub relation_to_root: String,
+    pub hops: u32,
+}
+
+/// One insider-status hit found while checking an entity and its family across
+/// every company in the entity's corporate group
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CrossCompanyInsiderHit {
+    pub company_symbol: String,
+    pub insider_entity_id: String,
+    pub relation_to_queried_entity: String,
+    pub insider_type: String,
+    pub designation: String,
+}
+
+/// Board resolutions, shareholding disclosures, KYC docs, etc. attached to an
+/// entity as evidence. Merged into the graph on hash rather than a generated
+/// counter ID, so re-attaching the same file (same hash) is idempotent.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DocumentMetadata {
+    pub doc_id: String,
+    pub entity_id: String,
+    pub doc_type: String,
+    pub storage_url: String,
+    pub hash: String,
+    pub attached_at: u64,
+}
+
+/// One chunk of a graph snapshot - either every field of an Entity or a
+/// Relationship, one JSON object per line, uploaded to Supabase storage under
+/// SnapshotManifest.chunks
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SnapshotChunkRef {
+    pub file_path: String,
+    pub record_count: u32,
+    pub record_type: String,
+}
+
+/// Written to storage alongside the chunks it lists, and re-read by
+/// restore_from_snapshot to know what to replay. Neo4j Aura's free tier has no
+/// built-in backup/restore, so this manifest plus its chunks is the only
+/// recovery path for the insider register.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SnapshotManifest {
+    pub snapshot_id: String,
+    pub created_at: u64,
+    pub entity_count: u32,
+    pub relationship_count: u32,
+    pub chunks: Vec<SnapshotChunkRef>,
+    pub manifest_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct RestoreSummary {
+    pub snapshot_id: String,
+    pub entities_restored: u32,
+    pub relationships_restored: u32,
+    pub chunks_processed: u32,
+}
+
+/// One runner-up candidate resolve_reference didn't pick, with its own confidence
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceCandidate {
+    pub value: String,
+    pub confidence: u32,
+}
+
+/// resolve_reference's result: the resolved value plus a 0-100 confidence
+/// score and up to 3 runner-up candidates, so a caller can ask a clarifying
+/// question instead of silently acting on a low-confidence fuzzy match
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceResolution {
+    pub kind: String,
+    pub query: String,
+    pub resolved_value: String,
+    pub confidence: u32,
+    pub alternatives: Vec<ReferenceCandidate>,
+}
+
 // ===== CONTEXT CACHE STRUCTURES =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
@@ -111,76 +276,346 @@ struct Neo4jError {
     message: String,
 }
 
+// Neo4j Query API v2 explicit transaction (tx/begin, tx/{id}, tx/{id}/commit,
+// tx/{id}/rollback) response for the begin call - the only one we need a typed
+// shape for, since the run/commit calls reuse Neo4jResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct Neo4jTxBeginResponse {
+    transaction: Neo4jTxInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Neo4jTxInfo {
+    id: String,
+}
+
 // ===== TRAIT DEFINITION =====
 
 trait EntityRelationship {
     fn new() -> Result<Self, String> where Self: Sized;
     async fn get_context(&mut self) -> QueryContext;
+    /// kind: "entity" or "company" - see ReferenceResolution's doc comment
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String>;
     async fn get_entity(&mut self, entity_id: String) -> Result<Entity, String>;
+    async fn get_entity_by_pan(&mut self, pan: String) -> Result<Entity, String>;
     async fn search_entities(&mut self, search_query: String, limit: u32) -> Result<Vec<Entity>, String>;
     async fn get_relationships(&mut self, entity_id: String) -> Result<Vec<Relationship>, String>;
-    async fn get_connected_entities(&mut self, entity_id: String, max_hops: u32) -> Result<Vec<EntityConnection>, String>;
+    async fn diff_entity_network(&mut self, entity_id: String, ts_a: u64, ts_b: u64) -> Result<EntityNetworkDiff, String>;
+    async fn get_connected_entities(&mut self, entity_id: String, max_hops: u32, page: Option<u32>, page_size: Option<u32>) -> Result<ConnectedEntitiesPage, String>;
     async fn check_insider_status(&mut self, entity_id: String, company_symbol: String) -> Result<InsiderStatus, String>;
     async fn get_company_insiders(&mut self, company_symbol: String) -> Result<Vec<InsiderStatus>, String>;
     async fn are_entities_connected(&mut self, entity_id_1: String, entity_id_2: String, max_hops: u32) -> Result<EntityConnection, String>;
+    async fn explain_connection(&mut self, entity_id_1: String, entity_id_2: String) -> Result<ConnectionExplanation, String>;
     async fn get_family_members(&mut self, entity_id: String) -> Result<Vec<Entity>, String>;
+    async fn get_group_companies(&mut self, entity_id_or_group: String) -> Result<Vec<GroupCompany>, String>;
+    async fn check_cross_company_insider(&mut self, entity_id: String) -> Result<Vec<CrossCompanyInsiderHit>, String>;
+    async fn sync_insider_relationship(&mut self, entity_id: String, company_symbol: String, designation: String, effective_from: u64, active: bool) -> Result<InsiderStatus, String>;
+    // Attach a document (board resolution, shareholding disclosure, KYC doc,
+    // etc.) as evidence linked to an entity. Merged on hash, so re-attaching the
+    // same file is idempotent rather than creating a duplicate node.
+    async fn attach_document(&mut self, entity_id: String, doc_type: String, storage_url: String, hash: String, attached_at: u64) -> Result<DocumentMetadata, String>;
+    async fn list_documents(&mut self, entity_id: String) -> Result<Vec<DocumentMetadata>, String>;
+    // Pages through every Entity and Relationship in Neo4j, writes them as
+    // chunked JSONL to Supabase storage, and records a manifest listing the
+    // chunks - Neo4j Aura's free tier has no built-in backup/restore
+    async fn export_graph_snapshot(&mut self, snapshot_id: String, created_at: u64) -> Result<SnapshotManifest, String>;
+    // Re-reads a snapshot's manifest from storage and MERGEs every entity and
+    // relationship chunk back into Neo4j
+    async fn restore_from_snapshot(&mut self, snapshot_id: String) -> Result<RestoreSummary, String>;
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String>;
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely migrate the
+/// Supabase schema / Neo4j graph without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct EntityRelationshipContractState {
     secrets: Secrets<EntityRelationshipConfig>,
     query_cache: QueryContext,
+    outbound_guard: OutboundGuard,
+    result_cache: ResultCache,
+    secret_versions: Vec<SecretVersionEntry>,
+    maintenance: MaintenanceStatus,
+    http_fixtures: Vec<http_fixtures::HttpFixture>,
 }
 
 impl EntityRelationshipContractState {
-    /// Execute a Cypher query against Neo4j Aura using Query API v2
-    async fn execute_cypher(&self, cypher: &str) -> Result<Neo4jResponse, String> {
+    /// Reject mutating calls while maintenance mode is on, so an in-flight schema
+    /// migration in Supabase/Neo4j can't race a concurrent tool call into a
+    /// half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Execute a Cypher query against Neo4j Aura using Query API v2, gated by the
+    /// outbound circuit breaker so a flaky Aura instance can't be hammered with retries
+    async fn execute_cypher(&mut self, cypher: &str) -> Result<Neo4jResponse, String> {
         let config = self.secrets.config();
-        
+        let host = config.neo4j_uri.clone();
+        let mode = config.http_fixture_mode.clone();
+        let key = http_fixtures::fixture_key("POST", &host, cypher);
+
+        if mode == "playback" {
+            let response_text = match http_fixtures::find(&self.http_fixtures, &key) {
+                Some(f) if (200..300).contains(&f.status) => f.body.clone(),
+                Some(f) => return Err(format!("HTTP {} (fixture): {}", f.status, f.body)),
+                None => return Err(format!("No recorded HTTP fixture for {}", key)),
+            };
+            return serde_json::from_str(&response_text)
+                .map_err(|e| format!("Failed to parse Neo4j response: {} - Body: {}", e, response_text));
+        }
+
+        self.outbound_guard.check(&host)?;
+
         let uri = config.neo4j_uri
             .replace("neo4j+s://", "https://")
             .replace("neo4j://", "http://");
         let url = format!("{}/db/neo4j/query/v2", uri);
-        
+
         let request_body = Neo4jQueryRequest {
             statement: cypher.to_string(),
         };
-        
+
         let body = serde_json::to_string(&request_body)
             .map_err(|e| format!("Failed to serialize request: {}", e))?;
-        
+
         let auth = format!("{}:{}", config.neo4j_user, config.neo4j_password);
         let auth_encoded = base64_encode(&auth);
-        
+
         let mut headers = HashMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         headers.insert("Authorization".to_string(), format!("Basic {}", auth_encoded));
-        
-        let response = HttpClient::request(&url, HttpMethod::Post)
+
+        let response = match HttpClient::request(&url, HttpMethod::Post)
             .headers(headers)
             .body(body)
             .send()
-            .map_err(|e| format!("Neo4j request failed: {:?}", e))?;
-        
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.outbound_guard.record_result(&host, false);
+                if mode == "record" {
+                    http_fixtures::upsert(&mut self.http_fixtures, key, 599, format!("{:?}", e));
+                }
+                return Err(format!("Neo4j request failed: {:?}", e));
+            }
+        };
+
         let status = response.status();
         let response_text = response.text();
-        
+        self.outbound_guard.record_result(&host, (200..300).contains(&status));
+
+        if mode == "record" {
+            http_fixtures::upsert(&mut self.http_fixtures, key, status, response_text.clone());
+        }
+
         if status == 403 {
             return Err(format!("Neo4j authentication failed (403 Forbidden). Check credentials."));
         }
-        
+
         if !(200..300).contains(&status) {
             return Err(format!("Neo4j HTTP {}: {}", status, response_text));
         }
-        
+
         serde_json::from_str(&response_text)
             .map_err(|e| format!("Failed to parse Neo4j response: {} - Body: {}", e, response_text))
     }
-    
+
+    /// Runs multiple Cypher statements as a single explicit transaction against
+    /// the Query API v2 tx endpoints (tx/begin, tx/{id}, tx/{id}/commit), rolling
+    /// back on the first failing statement so a multi-step graph mutation (e.g.
+    /// an entity node, a company node and the INSIDER_OF edge between them) either
+    /// lands as a whole or not at all. Same outbound circuit breaker gating as
+    /// execute_cypher; rollback failures are best-effort since the transaction
+    /// expires server-side regardless.
+    async fn execute_cypher_tx(&mut self, statements: &[String]) -> Result<Neo4jResponse, String> {
+        let config = self.secrets.config();
+        let host = config.neo4j_uri.clone();
+        self.outbound_guard.check(&host)?;
+
+        let uri = config.neo4j_uri
+            .replace("neo4j+s://", "https://")
+            .replace("neo4j://", "http://");
+
+        let auth = format!("{}:{}", config.neo4j_user, config.neo4j_password);
+        let auth_encoded = base64_encode(&auth);
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("Authorization".to_string(), format!("Basic {}", auth_encoded));
+
+        let begin_url = format!("{}/db/neo4j/tx/begin", uri);
+        let begin_response = match HttpClient::request(&begin_url, HttpMethod::Post)
+            .headers(headers.clone())
+            .send()
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.outbound_guard.record_result(&host, false);
+                return Err(format!("Neo4j transaction begin failed: {:?}", e));
+            }
+        };
+
+        let begin_status = begin_response.status();
+        let begin_text = begin_response.text();
+        self.outbound_guard.record_result(&host, (200..300).contains(&begin_status));
+
+        if !(200..300).contains(&begin_status) {
+            return Err(format!("Neo4j tx begin HTTP {}: {}", begin_status, begin_text));
+        }
+
+        let tx: Neo4jTxBeginResponse = serde_json::from_str(&begin_text)
+            .map_err(|e| format!("Failed to parse Neo4j tx begin response: {} - Body: {}", e, begin_text))?;
+        let tx_id = tx.transaction.id;
+
+        let mut last_response = Neo4jResponse { data: None, errors: Vec::new() };
+        for statement in statements {
+            let run_url = format!("{}/db/neo4j/tx/{}", uri, tx_id);
+            let request_body = Neo4jQueryRequest { statement: statement.clone() };
+            let body = serde_json::to_string(&request_body)
+                .map_err(|e| format!("Failed to serialize request: {}", e))?;
+
+            let run_response = match HttpClient::request(&run_url, HttpMethod::Post)
+                .headers(headers.clone())
+                .body(body)
+                .send()
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    self.rollback_tx(&uri, &tx_id, &headers);
+                    return Err(format!("Neo4j statement failed, transaction rolled back: {:?}", e));
+                }
+            };
+
+            let run_status = run_response.status();
+            let run_text = run_response.text();
+            if !(200..300).contains(&run_status) {
+                self.rollback_tx(&uri, &tx_id, &headers);
+                return Err(format!("Neo4j tx statement HTTP {}: {}, transaction rolled back", run_status, run_text));
+            }
+
+            let parsed: Neo4jResponse = serde_json::from_str(&run_text)
+                .map_err(|e| format!("Failed to parse Neo4j tx statement response: {} - Body: {}", e, run_text))?;
+            if !parsed.errors.is_empty() {
+                let reason = parsed.errors[0].message.clone();
+                self.rollback_tx(&uri, &tx_id, &headers);
+                return Err(format!("Neo4j statement error, transaction rolled back: {}", reason));
+            }
+
+            last_response = parsed;
+        }
+
+        let commit_url = format!("{}/db/neo4j/tx/{}/commit", uri, tx_id);
+        let commit_response = match HttpClient::request(&commit_url, HttpMethod::Post)
+            .headers(headers.clone())
+            .send()
+        {
+            Ok(response) => response,
+            Err(e) => return Err(format!("Neo4j transaction commit failed: {:?}", e)),
+        };
+
+        let commit_status = commit_response.status();
+        self.outbound_guard.record_result(&host, (200..300).contains(&commit_status));
+        if !(200..300).contains(&commit_status) {
+            let commit_text = commit_response.text();
+            return Err(format!("Neo4j tx commit HTTP {}: {}", commit_status, commit_text));
+        }
+
+        Ok(last_response)
+    }
+
+    /// Best-effort rollback for execute_cypher_tx - failures here aren't surfaced
+    /// since the caller already has the real failure reason and an uncommitted
+    /// transaction expires server-side on its own
+    fn rollback_tx(&self, uri: &str, tx_id: &str, headers: &HashMap<String, String>) {
+        let rollback_url = format!("{}/db/neo4j/tx/{}/rollback", uri, tx_id);
+        let _ = HttpClient::request(&rollback_url, HttpMethod::Post)
+            .headers(headers.clone())
+            .send();
+    }
+
+    /// Same as execute_cypher, but checks result_cache first under cache_key and
+    /// stores the response there afterward. Neo4j free-tier latency makes
+    /// repeated lookups (e.g. get_company_insiders during a multi-step
+    /// investigation) slow enough that this is worth the staleness tradeoff for
+    /// cache_ttl_ticks calls.
+    async fn execute_cypher_cached(&mut self, cache_key: &str, cypher: &str) -> Result<Neo4jResponse, String> {
+        if let Some(cached) = self.result_cache.get(cache_key) {
+            if let Ok(response) = serde_json::from_str::<Neo4jResponse>(&cached) {
+                return Ok(response);
+            }
+        }
+
+        let response = self.execute_cypher(cypher).await?;
+        if let Ok(serialized) = serde_json::to_string(&response) {
+            let ttl = self.secrets.config().cache_ttl_ticks;
+            self.result_cache.put(cache_key.to_string(), serialized, ttl);
+        }
+        Ok(response)
+    }
+
+    /// Uploads content to Supabase storage at file_path, creating or
+    /// overwriting it (x-upsert) - used by export_graph_snapshot for chunks
+    /// and the manifest alike
+    async fn supabase_storage_upload(&mut self, file_path: &str, content: &str) -> Result<(), String> {
+        let config = self.secrets.config();
+        let url = format!("{}/storage/v1/object/{}/{}", config.supabase_url, config.supabase_bucket, file_path);
+        self.outbound_guard.check(&config.supabase_url)?;
+
+        let mut headers = HashMap::new();
+        headers.insert("apikey".to_string(), config.supabase_service_key.clone());
+        headers.insert("Authorization".to_string(), format!("Bearer {}", config.supabase_service_key));
+        headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
+        headers.insert("x-upsert".to_string(), "true".to_string());
+
+        let response = HttpClient::request(&url, HttpMethod::Post)
+            .headers(headers)
+            .body(content.to_string())
+            .send()
+            .map_err(|e| format!("Snapshot upload to {} failed: {:?}", file_path, e))?;
+
+        let resp_text = response.text();
+        if resp_text.contains("\"error\"") || resp_text.contains("\"statusCode\"") {
+            return Err(format!("Snapshot upload to {} rejected: {}", file_path, resp_text));
+        }
+        Ok(())
+    }
+
+    /// Downloads a file previously written by supabase_storage_upload
+    async fn supabase_storage_download(&mut self, file_path: &str) -> Result<String, String> {
+        let config = self.secrets.config();
+        let url = format!("{}/storage/v1/object/{}/{}", config.supabase_url, config.supabase_bucket, file_path);
+        self.outbound_guard.check(&config.supabase_url)?;
+
+        let mut headers = HashMap::new();
+        headers.insert("apikey".to_string(), config.supabase_service_key.clone());
+        headers.insert("Authorization".to_string(), format!("Bearer {}", config.supabase_service_key));
+
+        let response = HttpClient::request(&url, HttpMethod::Get)
+            .headers(headers)
+            .send()
+            .map_err(|e| format!("Snapshot download of {} failed: {:?}", file_path, e))?;
+
+        Ok(response.text())
+    }
+
     /// Parse entity from Neo4j row
     fn parse_entity(&self, row: &[serde_json::Value]) -> Option<Entity> {
         if row.len() >= 5 {
@@ -231,47 +666,76 @@ impl EntityRelationshipContractState {
         if partial.is_empty() {
             return self.query_cache.last_entity_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_entity_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_entity_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_entity_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()));
+
+        if let Some(m) = fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES) {
+            return m.value;
         }
-        
+
+        let partial_lower = partial.to_lowercase();
         for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.entity_id.is_empty() && query.entity_id.to_lowercase().contains(&partial_lower) {
+            if !query.entity_id.is_empty() && query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
                 return query.entity_id.clone();
             }
-            if query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
-                if !query.entity_id.is_empty() {
-                    return query.entity_id.clone();
-                }
-            }
         }
-        
+
         partial.to_string()
     }
 
+    /// Validate the Indian PAN format: 5 letters, 4 digits, 1 letter (e.g. AAAPL1234C)
+    fn is_valid_pan_format(pan: &str) -> bool {
+        let chars: Vec<char> = pan.chars().collect();
+        chars.len() == 10
+            && chars[0..5].iter().all(|c| c.is_ascii_uppercase())
+            && chars[5..9].iter().all(|c| c.is_ascii_digit())
+            && chars[9].is_ascii_uppercase()
+    }
+
     /// Resolve a partial company_symbol from cache
     /// "RELI" → "RELIANCE", "INF" → "INFY"
     fn resolve_company(&self, partial: &str) -> String {
         if partial.is_empty() {
             return self.query_cache.last_company_symbol.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_company_symbol.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_company_symbol.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_company_symbol.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
+    }
+
+    /// entity_id_or_group may be a company symbol or an entity_id; try it as a
+    /// company symbol first, then fall back to the entity's own INSIDER_OF company,
+    /// then to the resolved symbol as-is (matching the other resolve_* helpers,
+    /// which always return something rather than failing)
+    async fn resolve_group_root(&mut self, entity_id_or_group: &str) -> Result<String, String> {
+        let resolved_company = self.resolve_company(entity_id_or_group);
+        let company_check = self.execute_cypher(&format!(
+            "MATCH (c:Company {{symbol: '{}'}}) RETURN c.symbol LIMIT 1",
+            resolved_company
+        )).await?;
+        if company_check.data.as_ref().map(|d| !d.values.is_empty()).unwrap_or(false) {
+            return Ok(resolved_company);
         }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.company_symbol.is_empty() && query.company_symbol.to_lowercase().contains(&partial_lower) {
-                return query.company_symbol.clone();
-            }
+
+        let resolved_entity = self.resolve_entity(entity_id_or_group);
+        let entity_home = self.execute_cypher(&format!(
+            "MATCH (e:Entity {{entity_id: '{}'}})-[:INSIDER_OF]->(c:Company) RETURN c.symbol LIMIT 1",
+            resolved_entity
+        )).await?;
+        if let Some(symbol) = entity_home.data.as_ref()
+            .and_then(|d| d.values.first())
+            .and_then(|row| row.first())
+            .and_then(|v| v.as_str())
+        {
+            return Ok(symbol.to_string());
         }
-        
-        partial.to_string()
+
+        Ok(resolved_company)
     }
 
     fn resolve_from_cache(&self, entity_partial: &str, company_partial: &str) -> (String, String) {
@@ -325,7 +789,7 @@ impl EntityRelationshipContractState {
             timestamp: 0,
         };
 
-        let args = serde_json::to_string(&alert).unwrap_or_default();
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "push_alert".to_string(),
@@ -419,6 +883,11 @@ impl EntityRelationship for EntityRelationshipContractState {
                 last_entity_id: "ENT-REL-001".to_string(),
                 last_company_symbol: "RELIANCE".to_string(),
             },
+            outbound_guard: OutboundGuard::default(),
+            result_cache: ResultCache::default(),
+            secret_versions: Vec::new(),
+            maintenance: MaintenanceStatus::default(),
+            http_fixtures: Vec::new(),
         })
     }
 
@@ -427,8 +896,35 @@ impl EntityRelationship for EntityRelationshipContractState {
         self.query_cache.clone()
     }
 
+    #[query]
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String> {
+        if partial.is_empty() {
+            return Err("partial must not be empty".to_string());
+        }
+
+        let candidates: Vec<&str> = match kind.as_str() {
+            "entity" => std::iter::once(self.query_cache.last_entity_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()))
+                .collect(),
+            "company" => std::iter::once(self.query_cache.last_company_symbol.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()))
+                .collect(),
+            other => return Err(format!("Unknown reference kind '{}' - expected entity or company", other)),
+        };
+
+        let mut ranked = fuzzy_match::resolve_ranked(&partial, candidates.into_iter(), &fuzzy_match::DEFAULT_STRATEGIES, 4).into_iter();
+        let (resolved_value, confidence) = match ranked.next() {
+            Some(m) => (m.value, (m.score * 100.0).round() as u32),
+            None => (partial.clone(), 0),
+        };
+        let alternatives = ranked.map(|m| ReferenceCandidate { value: m.value, confidence: (m.score * 100.0).round() as u32 }).collect();
+
+        Ok(ReferenceResolution { kind, query: partial, resolved_value, confidence, alternatives })
+    }
+
     #[mutate]
     async fn get_entity(&mut self, entity_id: String) -> Result<Entity, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
         self.update_cache("get_entity", &resolved_entity, "", 
             &format!("Get entity {}", resolved_entity));
@@ -437,8 +933,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH (e:Entity {{entity_id: '{}'}}) RETURN e.entity_id, e.entity_type, e.name, e.pan_number, e.registration_id",
             resolved_entity
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("entity:{}", resolved_entity);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -455,8 +952,42 @@ impl EntityRelationship for EntityRelationshipContractState {
         Err(format!("Entity {} not found", resolved_entity))
     }
 
+    #[mutate]
+    async fn get_entity_by_pan(&mut self, pan: String) -> Result<Entity, String> {
+        self.maintenance_guard()?;
+        let pan = pan.trim().to_uppercase();
+        if !Self::is_valid_pan_format(&pan) {
+            return Err(format!("'{}' is not a valid PAN (expected 5 letters, 4 digits, 1 letter)", pan));
+        }
+        self.update_cache("get_entity_by_pan", "", "",
+            &format!("Get entity by PAN {}", pan));
+
+        let cypher = format!(
+            "MATCH (e:Entity {{pan_number: '{}'}}) RETURN e.entity_id, e.entity_type, e.name, e.pan_number, e.registration_id",
+            pan
+        );
+
+        let cache_key = format!("entity_by_pan:{}", pan);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        if let Some(ref data) = response.data {
+            if let Some(row) = data.values.first() {
+                if let Some(entity) = self.parse_entity(row) {
+                    return Ok(entity);
+                }
+            }
+        }
+
+        Err(format!("No entity found with PAN {}", pan))
+    }
+
     #[mutate]
     async fn search_entities(&mut self, search_query: String, limit: u32) -> Result<Vec<Entity>, String> {
+        self.maintenance_guard()?;
         self.update_cache("search_entities", "", "", 
             &format!("Search for {}", search_query));
         
@@ -485,6 +1016,7 @@ impl EntityRelationship for EntityRelationshipContractState {
 
     #[mutate]
     async fn get_relationships(&mut self, entity_id: String) -> Result<Vec<Relationship>, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
         self.update_cache("get_relationships", &resolved_entity, "", 
             &format!("Get relationships for {}", resolved_entity));
@@ -493,8 +1025,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH (a:Entity {{entity_id: '{}'}})-[r]->(b:Entity) RETURN a.entity_id, b.entity_id, type(r), r.detail, r.strength, r.verified",
             resolved_entity
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("relationships:{}", resolved_entity);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -520,49 +1053,133 @@ impl EntityRelationship for EntityRelationshipContractState {
     }
 
     #[mutate]
-    async fn get_connected_entities(&mut self, entity_id: String, max_hops: u32) -> Result<Vec<EntityConnection>, String> {
+    async fn diff_entity_network(&mut self, entity_id: String, ts_a: u64, ts_b: u64) -> Result<EntityNetworkDiff, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
-        self.update_cache("get_connected_entities", &resolved_entity, "", 
-            &format!("Get connected entities for {}", resolved_entity));
-        
+        self.update_cache("diff_entity_network", &resolved_entity, "",
+            &format!("Diff network for {} between {} and {}", resolved_entity, ts_a, ts_b));
+
         let cypher = format!(
-            "MATCH path = (a:Entity {{entity_id: '{}'}})-[*1..{}]-(b:Entity) WHERE a <> b RETURN DISTINCT b.entity_id, [n IN nodes(path) | n.entity_id] AS path_nodes, length(path) AS hops, [r IN relationships(path) | type(r)] AS rel_types LIMIT 50",
-            resolved_entity, max_hops
+            "MATCH (a:Entity {{entity_id: '{}'}})-[r]->(b:Entity) RETURN a.entity_id, b.entity_id, type(r), r.detail, r.strength, r.verified, coalesce(r.since, 0), coalesce(r.ended_at, 0), coalesce(r.previous_strength, r.strength)",
+            resolved_entity
         );
-        
+
         let response = self.execute_cypher(&cypher).await?;
-        
+
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
         }
-        
-        let mut connections = Vec::new();
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut strength_changed = Vec::new();
+
         if let Some(ref data) = response.data {
             for row in &data.values {
-                if row.len() >= 4 {
-                    let path_nodes: Vec<String> = row[1].as_array()
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                        .unwrap_or_default();
-                    let rel_types: Vec<String> = row[3].as_array()
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                        .unwrap_or_default();
-                    
-                    connections.push(EntityConnection {
-                        entity_id: resolved_entity.clone(),
-                        connected_entity_id: row[0].as_str().unwrap_or("").to_string(),
-                        connection_path: path_nodes.join(" -> "),
-                        hops: row[2].as_u64().unwrap_or(0) as u32,
-                        relationship_types: rel_types.join(","),
-                    });
+                if row.len() < 9 {
+                    continue;
+                }
+
+                let relationship = Relationship {
+                    source_entity_id: row[0].as_str().unwrap_or("").to_string(),
+                    target_entity_id: row[1].as_str().unwrap_or("").to_string(),
+                    relationship_type: row[2].as_str().unwrap_or("").to_string(),
+                    relationship_detail: row[3].as_str().unwrap_or("").to_string(),
+                    strength: row[4].as_u64().unwrap_or(0) as u32,
+                    verified: row[5].as_bool().unwrap_or(false),
+                };
+                let since = row[6].as_u64().unwrap_or(0);
+                let ended_at = row[7].as_u64().unwrap_or(0);
+                let previous_strength = row[8].as_u64().unwrap_or(relationship.strength as u64) as u32;
+
+                let present_at_a = since <= ts_a && (ended_at == 0 || ended_at > ts_a);
+                let present_at_b = since <= ts_b && (ended_at == 0 || ended_at > ts_b);
+
+                if !present_at_a && present_at_b {
+                    added.push(relationship);
+                } else if present_at_a && !present_at_b {
+                    removed.push(relationship);
+                } else if present_at_a && present_at_b && previous_strength != relationship.strength {
+                    strength_changed.push(RelationshipStrengthChange {
+                        source_entity_id: relationship.source_entity_id,
+                        target_entity_id: relationship.target_entity_id,
+                        relationship_type: relationship.relationship_type,
+                        strength_at_a: previous_strength,
+                        strength_at_b: relationship.strength,
+                    });
                 }
             }
         }
-        
-        Ok(connections)
+
+        Ok(EntityNetworkDiff {
+            entity_id: resolved_entity,
+            ts_a,
+            ts_b,
+            added,
+            removed,
+            strength_changed,
+        })
+    }
+
+    #[mutate]
+    async fn get_connected_entities(&mut self, entity_id: String, max_hops: u32, page: Option<u32>, page_size: Option<u32>) -> Result<ConnectedEntitiesPage, String> {
+        self.maintenance_guard()?;
+        if max_hops == 0 || max_hops > 4 {
+            return Err(format!("max_hops must be between 1 and 4 (got {}); wider traversals on a dense graph can time out", max_hops));
+        }
+        let resolved_entity = self.resolve_entity(&entity_id);
+        self.update_cache("get_connected_entities", &resolved_entity, "",
+            &format!("Get connected entities for {}", resolved_entity));
+
+        let pg = page.unwrap_or(0);
+        let pg_size = page_size.unwrap_or(50).min(200);
+        let skip = pg * pg_size;
+
+        let cypher = format!(
+            "MATCH path = (a:Entity {{entity_id: '{}'}})-[*1..{}]-(b:Entity) WHERE a <> b RETURN DISTINCT b.entity_id, [n IN nodes(path) | n.entity_id] AS path_nodes, length(path) AS hops, [r IN relationships(path) | type(r)] AS rel_types SKIP {} LIMIT {}",
+            resolved_entity, max_hops, skip, pg_size + 1
+        );
+
+        let cache_key = format!("connected:{}:{}:{}:{}", resolved_entity, max_hops, pg, pg_size);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        let mut connections = Vec::new();
+        if let Some(ref data) = response.data {
+            for row in &data.values {
+                if row.len() >= 4 {
+                    let path_nodes: Vec<String> = row[1].as_array()
+                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default();
+                    let rel_types: Vec<String> = row[3].as_array()
+                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default();
+
+                    connections.push(EntityConnection {
+                        entity_id: resolved_entity.clone(),
+                        connected_entity_id: row[0].as_str().unwrap_or("").to_string(),
+                        connection_path: path_nodes.join(" -> "),
+                        hops: row[2].as_u64().unwrap_or(0) as u32,
+                        relationship_types: rel_types.join(","),
+                    });
+                }
+            }
+        }
+
+        let has_more = connections.len() as u32 > pg_size;
+        if has_more {
+            connections.truncate(pg_size as usize);
+        }
+
+        Ok(ConnectedEntitiesPage { connections, page: pg, page_size: pg_size, has_more })
     }
 
     #[mutate]
     async fn check_insider_status(&mut self, entity_id: String, company_symbol: String) -> Result<InsiderStatus, String> {
+        self.maintenance_guard()?;
         let (resolved_entity, resolved_company) = self.resolve_from_cache(&entity_id, &company_symbol);
         self.update_cache("check_insider_status", &resolved_entity, &resolved_company, 
             &format!("Check if {} is {} insider", resolved_entity, resolved_company));
@@ -571,8 +1188,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH (e:Entity {{entity_id: '{}'}})-[r:INSIDER_OF]->(c:Company {{symbol: '{}'}}) RETURN e.entity_id, c.symbol, true, r.insider_type, r.designation, r.window_status",
             resolved_entity, resolved_company
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("insider_status:{}:{}", resolved_entity, resolved_company);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -618,6 +1236,7 @@ impl EntityRelationship for EntityRelationshipContractState {
 
     #[mutate]
     async fn get_company_insiders(&mut self, company_symbol: String) -> Result<Vec<InsiderStatus>, String> {
+        self.maintenance_guard()?;
         let resolved_company = self.resolve_company(&company_symbol);
         self.update_cache("get_company_insiders", "", &resolved_company, 
             &format!("Get insiders for {}", resolved_company));
@@ -626,8 +1245,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH (e:Entity)-[r:INSIDER_OF]->(c:Company {{symbol: '{}'}}) RETURN e.entity_id, c.symbol, true, r.insider_type, r.designation, r.window_status",
             resolved_company
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("company_insiders:{}", resolved_company);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -654,6 +1274,7 @@ impl EntityRelationship for EntityRelationshipContractState {
 
     #[mutate]
     async fn are_entities_connected(&mut self, entity_id_1: String, entity_id_2: String, max_hops: u32) -> Result<EntityConnection, String> {
+        self.maintenance_guard()?;
         let resolved_entity_1 = self.resolve_entity(&entity_id_1);
         let resolved_entity_2 = self.resolve_entity(&entity_id_2);
         self.update_cache("are_entities_connected", &resolved_entity_1, "", 
@@ -663,8 +1284,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH path = shortestPath((a:Entity {{entity_id: '{}'}})-[*1..{}]-(b:Entity {{entity_id: '{}'}})) RETURN [n IN nodes(path) | n.entity_id] AS path_nodes, length(path) AS hops, [r IN relationships(path) | type(r)] AS rel_types",
             resolved_entity_1, max_hops, resolved_entity_2
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("connection:{}:{}:{}", resolved_entity_1, resolved_entity_2, max_hops);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -694,8 +1316,73 @@ impl EntityRelationship for EntityRelationshipContractState {
         Err(format!("No path found between {} and {} within {} hops", resolved_entity_1, resolved_entity_2, max_hops))
     }
 
+    /// Same shortest path as are_entities_connected, enriched with node names/types
+    /// and edge details and rendered as a sentence chain. Phrasing quality follows
+    /// whatever relationship_detail was authored as (e.g. "spouse of"); this joins
+    /// it into "A is <detail> B" rather than attempting real NLG.
+    #[mutate]
+    async fn explain_connection(&mut self, entity_id_1: String, entity_id_2: String) -> Result<ConnectionExplanation, String> {
+        self.maintenance_guard()?;
+        let resolved_entity_1 = self.resolve_entity(&entity_id_1);
+        let resolved_entity_2 = self.resolve_entity(&entity_id_2);
+        self.update_cache("explain_connection", &resolved_entity_1, "",
+            &format!("Explain connection between {} and {}", resolved_entity_1, resolved_entity_2));
+
+        const MAX_HOPS: u32 = 4; // matches get_connected_entities' traversal cap
+
+        let cypher = format!(
+            "MATCH path = shortestPath((a:Entity {{entity_id: '{}'}})-[*1..{}]-(b:Entity {{entity_id: '{}'}})) RETURN [n IN nodes(path) | n.entity_id] AS ids, [n IN nodes(path) | n.name] AS names, [n IN nodes(path) | n.entity_type] AS types, [r IN relationships(path) | type(r)] AS rel_types, [r IN relationships(path) | r.detail] AS rel_details, length(path) AS hops",
+            resolved_entity_1, MAX_HOPS, resolved_entity_2
+        );
+
+        let cache_key = format!("explain:{}:{}", resolved_entity_1, resolved_entity_2);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        let row = response.data.as_ref()
+            .and_then(|d| d.values.first())
+            .ok_or_else(|| format!("No path found between {} and {} within {} hops", resolved_entity_1, resolved_entity_2, MAX_HOPS))?;
+
+        if row.len() < 6 {
+            return Err(format!("Unexpected path result shape for {} to {}", resolved_entity_1, resolved_entity_2));
+        }
+
+        let as_strings = |v: &serde_json::Value| -> Vec<String> {
+            v.as_array().map(|arr| arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()).unwrap_or_default()
+        };
+        let ids = as_strings(&row[0]);
+        let names = as_strings(&row[1]);
+        let types = as_strings(&row[2]);
+        let rel_types = as_strings(&row[3]);
+        let rel_details = as_strings(&row[4]);
+        let hops = row[5].as_u64().unwrap_or(0) as u32;
+
+        let display = |i: usize| -> String {
+            let name = names.get(i).filter(|n| !n.is_empty()).cloned().unwrap_or_else(|| ids.get(i).cloned().unwrap_or_default());
+            let entity_type = types.get(i).cloned().unwrap_or_default();
+            format!("{} ({})", name, entity_type)
+        };
+
+        let mut sentences = Vec::new();
+        for i in 0..rel_types.len() {
+            let rel_label = rel_details.get(i).filter(|d| !d.is_empty()).cloned().unwrap_or_else(|| rel_types[i].clone());
+            sentences.push(format!("{} is {} {}", display(i), rel_label, display(i + 1)));
+        }
+
+        Ok(ConnectionExplanation {
+            entity_id_1: resolved_entity_1,
+            entity_id_2: resolved_entity_2,
+            hops,
+            connection_path: ids.join(" -> "),
+            narrative: format!("{}.", sentences.join("; ")),
+        })
+    }
+
     #[mutate]
     async fn get_family_members(&mut self, entity_id: String) -> Result<Vec<Entity>, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
         self.update_cache("get_family_members", &resolved_entity, "", 
             &format!("Get family members of {}", resolved_entity));
@@ -704,8 +1391,9 @@ impl EntityRelationship for EntityRelationshipContractState {
             "MATCH (a:Entity {{entity_id: '{}'}})-[:FAMILY]-(b:Entity) RETURN b.entity_id, b.entity_type, b.name, b.pan_number, b.registration_id",
             resolved_entity
         );
-        
-        let response = self.execute_cypher(&cypher).await?;
+
+        let cache_key = format!("family:{}", resolved_entity);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
         
         if !response.errors.is_empty() {
             return Err(response.errors[0].message.clone());
@@ -723,6 +1411,507 @@ impl EntityRelationship for EntityRelationshipContractState {
         Ok(entities)
     }
 
+    /// Resolves entity_id_or_group to a root company, then walks GROUP/SUBSIDIARY
+    /// edges up to 3 hops out to find the rest of the corporate group
+    #[mutate]
+    async fn get_group_companies(&mut self, entity_id_or_group: String) -> Result<Vec<GroupCompany>, String> {
+        self.maintenance_guard()?;
+        let root_symbol = self.resolve_group_root(&entity_id_or_group).await?;
+        self.update_cache("get_group_companies", "", &root_symbol,
+            &format!("Get group companies for {}", root_symbol));
+
+        let mut companies = vec![GroupCompany {
+            company_symbol: root_symbol.clone(),
+            relation_to_root: "SELF".to_string(),
+            hops: 0,
+        }];
+
+        let cypher = format!(
+            "MATCH path = (root:Company {{symbol: '{}'}})-[:GROUP|SUBSIDIARY*1..3]-(other:Company) WHERE root <> other RETURN DISTINCT other.symbol, [r IN relationships(path) | type(r)] AS rel_types, length(path) AS hops",
+            root_symbol
+        );
+
+        let cache_key = format!("group:{}", root_symbol);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        if let Some(ref data) = response.data {
+            for row in &data.values {
+                if row.len() >= 3 {
+                    let symbol = row[0].as_str().unwrap_or("").to_string();
+                    if symbol.is_empty() {
+                        continue;
+                    }
+                    let rel_types: Vec<String> = row[1].as_array()
+                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default();
+                    let relation = if rel_types.iter().any(|t| t == "SUBSIDIARY") { "SUBSIDIARY" } else { "GROUP" };
+                    companies.push(GroupCompany {
+                        company_symbol: symbol,
+                        relation_to_root: relation.to_string(),
+                        hops: row[2].as_u64().unwrap_or(0) as u32,
+                    });
+                }
+            }
+        }
+
+        Ok(companies)
+    }
+
+    /// Checks the entity and its FAMILY-linked relatives for INSIDER_OF status at
+    /// their own company and at every other company reachable via GROUP/SUBSIDIARY
+    /// edges from it - insiders at a parent often trade the listed subsidiary
+    #[mutate]
+    async fn check_cross_company_insider(&mut self, entity_id: String) -> Result<Vec<CrossCompanyInsiderHit>, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+        self.update_cache("check_cross_company_insider", &resolved_entity, "",
+            &format!("Check cross-company insider status for {}", resolved_entity));
+
+        let cypher = format!(
+            "MATCH (root:Entity {{entity_id: '{}'}}) \
+             OPTIONAL MATCH (root)-[:FAMILY]-(relative:Entity) \
+             WITH root, collect(DISTINCT relative) AS relatives \
+             UNWIND ([root] + relatives) AS person \
+             MATCH (person)-[r:INSIDER_OF]->(home:Company) \
+             OPTIONAL MATCH (home)-[:GROUP|SUBSIDIARY*1..3]-(grp:Company) \
+             RETURN person.entity_id, (person = root), home.symbol, r.insider_type, r.designation, collect(DISTINCT grp.symbol)",
+            resolved_entity
+        );
+
+        let cache_key = format!("cross_company:{}", resolved_entity);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        let mut hits = Vec::new();
+        if let Some(ref data) = response.data {
+            for row in &data.values {
+                if row.len() < 6 {
+                    continue;
+                }
+                let person_id = row[0].as_str().unwrap_or("").to_string();
+                let relation = if row[1].as_bool().unwrap_or(false) { "SELF" } else { "FAMILY" };
+                let home_symbol = row[2].as_str().unwrap_or("").to_string();
+                let insider_type = row[3].as_str().unwrap_or("").to_string();
+                let designation = row[4].as_str().unwrap_or("").to_string();
+                if person_id.is_empty() || home_symbol.is_empty() {
+                    continue;
+                }
+
+                hits.push(CrossCompanyInsiderHit {
+                    company_symbol: home_symbol.clone(),
+                    insider_entity_id: person_id.clone(),
+                    relation_to_queried_entity: relation.to_string(),
+                    insider_type: insider_type.clone(),
+                    designation: designation.clone(),
+                });
+
+                let group_symbols: Vec<String> = row[5].as_array()
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                    .unwrap_or_default();
+                for group_symbol in group_symbols {
+                    if group_symbol.is_empty() || group_symbol == home_symbol {
+                        continue;
+                    }
+                    hits.push(CrossCompanyInsiderHit {
+                        company_symbol: group_symbol,
+                        insider_entity_id: person_id.clone(),
+                        relation_to_queried_entity: relation.to_string(),
+                        insider_type: insider_type.clone(),
+                        designation: designation.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(hits)
+    }
+
+    /// Create or revoke an INSIDER_OF edge, driven by the designated-person register in
+    /// upsi_database_mcp so Neo4j stays the single source of truth for who is an insider
+    #[mutate]
+    async fn sync_insider_relationship(&mut self, entity_id: String, company_symbol: String, designation: String, effective_from: u64, active: bool) -> Result<InsiderStatus, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+        let resolved_company = self.resolve_company(&company_symbol);
+        self.update_cache("sync_insider_relationship", &resolved_entity, &resolved_company,
+            &format!("Sync insider relationship for {} on {}", resolved_entity, resolved_company));
+
+        // Invalidate the cached reads keyed by this entity or company: insider_status
+        // and cross_company by entity_id, company_insiders by company_symbol. Cached
+        // cross_company results of the entity's FAMILY relatives are not invalidated.
+        self.result_cache.invalidate(&format!("insider_status:{}", resolved_entity));
+        self.result_cache.invalidate(&format!("cross_company:{}", resolved_entity));
+        self.result_cache.invalidate(&format!("company_insiders:{}", resolved_company));
+
+        // Split into an entity-node step, a company-node step and the edge step
+        // itself, and run all three as one explicit transaction so a mid-way
+        // failure (e.g. the edge write) doesn't leave a dangling entity/company
+        // node behind - MERGE alone made each node implicitly atomic with the
+        // edge, but not the three writes together.
+        let entity_statement = format!("MERGE (e:Entity {{entity_id: '{}'}})", resolved_entity);
+        let company_statement = format!("MERGE (c:Company {{symbol: '{}'}})", resolved_company);
+        let edge_statement = if active {
+            format!(
+                "MATCH (e:Entity {{entity_id: '{}'}}), (c:Company {{symbol: '{}'}}) MERGE (e)-[r:INSIDER_OF]->(c) SET r.insider_type = 'DESIGNATED_PERSON', r.designation = '{}', r.effective_from = {}, r.window_status = coalesce(r.window_status, 'OPEN') RETURN e.entity_id, c.symbol, true, r.insider_type, r.designation, r.window_status",
+                resolved_entity, resolved_company, designation, effective_from
+            )
+        } else {
+            format!(
+                "MATCH (e:Entity {{entity_id: '{}'}})-[r:INSIDER_OF]->(c:Company {{symbol: '{}'}}) DELETE r RETURN e.entity_id, c.symbol, false, '', '', ''",
+                resolved_entity, resolved_company
+            )
+        };
+
+        let response = self.execute_cypher_tx(&[entity_statement, company_statement, edge_statement])
+            .await
+            .map_err(|e| format!("Failed to sync insider relationship: {}", e))?;
+
+        if let Some(ref data) = response.data {
+            if let Some(row) = data.values.first() {
+                if row.len() >= 6 {
+                    return Ok(InsiderStatus {
+                        entity_id: row[0].as_str().unwrap_or(&resolved_entity).to_string(),
+                        company_symbol: row[1].as_str().unwrap_or(&resolved_company).to_string(),
+                        is_insider: active,
+                        insider_type: row[3].as_str().unwrap_or("").to_string(),
+                        designation: row[4].as_str().unwrap_or("").to_string(),
+                        window_status: row[5].as_str().unwrap_or("OPEN").to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(InsiderStatus {
+            entity_id: resolved_entity,
+            company_symbol: resolved_company,
+            is_insider: active,
+            insider_type: if active { "DESIGNATED_PERSON".to_string() } else { "".to_string() },
+            designation,
+            window_status: "N/A".to_string(),
+        })
+    }
+
+    /// Merges a Document node keyed on hash (idempotent re-attach) and links it
+    /// from the entity via HAS_DOCUMENT, in one explicit transaction so a
+    /// mid-way failure can't leave a dangling Document node behind - same
+    /// pattern as sync_insider_relationship.
+    #[mutate]
+    async fn attach_document(&mut self, entity_id: String, doc_type: String, storage_url: String, hash: String, attached_at: u64) -> Result<DocumentMetadata, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+        self.update_cache("attach_document", &resolved_entity, "",
+            &format!("Attach {} document to {}", doc_type, resolved_entity));
+
+        self.result_cache.invalidate(&format!("documents:{}", resolved_entity));
+
+        let entity_statement = format!("MERGE (e:Entity {{entity_id: '{}'}})", resolved_entity);
+        let doc_statement = format!(
+            "MERGE (d:Document {{hash: '{}'}}) SET d.doc_type = '{}', d.storage_url = '{}', d.attached_at = {}",
+            hash, doc_type, storage_url, attached_at
+        );
+        let edge_statement = format!(
+            "MATCH (e:Entity {{entity_id: '{}'}}), (d:Document {{hash: '{}'}}) MERGE (e)-[:HAS_DOCUMENT]->(d) RETURN e.entity_id, d.hash, d.doc_type, d.storage_url, d.attached_at",
+            resolved_entity, hash
+        );
+
+        let response = self.execute_cypher_tx(&[entity_statement, doc_statement, edge_statement])
+            .await
+            .map_err(|e| format!("Failed to attach document: {}", e))?;
+
+        if let Some(ref data) = response.data {
+            if let Some(row) = data.values.first() {
+                if row.len() >= 5 {
+                    return Ok(DocumentMetadata {
+                        doc_id: row[1].as_str().unwrap_or(&hash).to_string(),
+                        entity_id: row[0].as_str().unwrap_or(&resolved_entity).to_string(),
+                        doc_type: row[2].as_str().unwrap_or(&doc_type).to_string(),
+                        storage_url: row[3].as_str().unwrap_or(&storage_url).to_string(),
+                        hash: row[1].as_str().unwrap_or(&hash).to_string(),
+                        attached_at: row[4].as_u64().unwrap_or(attached_at),
+                    });
+                }
+            }
+        }
+
+        Ok(DocumentMetadata {
+            doc_id: hash.clone(),
+            entity_id: resolved_entity,
+            doc_type,
+            storage_url,
+            hash,
+            attached_at,
+        })
+    }
+
+    /// Every document attached to an entity, most recently attached first
+    #[mutate]
+    async fn list_documents(&mut self, entity_id: String) -> Result<Vec<DocumentMetadata>, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+        self.update_cache("list_documents", &resolved_entity, "",
+            &format!("List documents attached to {}", resolved_entity));
+
+        let cypher = format!(
+            "MATCH (e:Entity {{entity_id: '{}'}})-[:HAS_DOCUMENT]->(d:Document) RETURN e.entity_id, d.hash, d.doc_type, d.storage_url, d.attached_at ORDER BY d.attached_at DESC",
+            resolved_entity
+        );
+
+        let cache_key = format!("documents:{}", resolved_entity);
+        let response = self.execute_cypher_cached(&cache_key, &cypher).await?;
+
+        if !response.errors.is_empty() {
+            return Err(response.errors[0].message.clone());
+        }
+
+        let mut documents = Vec::new();
+        if let Some(ref data) = response.data {
+            for row in &data.values {
+                if row.len() >= 5 {
+                    documents.push(DocumentMetadata {
+                        doc_id: row[1].as_str().unwrap_or("").to_string(),
+                        entity_id: row[0].as_str().unwrap_or("").to_string(),
+                        doc_type: row[2].as_str().unwrap_or("").to_string(),
+                        storage_url: row[3].as_str().unwrap_or("").to_string(),
+                        hash: row[1].as_str().unwrap_or("").to_string(),
+                        attached_at: row[4].as_u64().unwrap_or(0),
+                    });
+                }
+            }
+        }
+
+        Ok(documents)
+    }
+
+    /// Pages through every Entity, then every Relationship, 200 records at a
+    /// time, writes each page as a JSONL chunk to Supabase storage, and
+    /// finishes by uploading a manifest listing every chunk. Neo4j Aura's
+    /// free tier has no built-in backup/restore, so this manifest plus its
+    /// chunks is the only recovery path for the insider register.
+    #[mutate]
+    async fn export_graph_snapshot(&mut self, snapshot_id: String, created_at: u64) -> Result<SnapshotManifest, String> {
+        self.maintenance_guard()?;
+        if snapshot_id.trim().is_empty() {
+            return Err("snapshot_id must not be empty".to_string());
+        }
+        self.update_cache("export_graph_snapshot", "", "",
+            &format!("Export graph snapshot {}", snapshot_id));
+
+        let chunk_size: u32 = 200;
+        let mut chunks = Vec::new();
+
+        let mut entity_count = 0u32;
+        let mut skip = 0u32;
+        loop {
+            let cypher = format!(
+                "MATCH (e:Entity) RETURN e.entity_id, e.entity_type, e.name, e.pan_number, e.registration_id SKIP {} LIMIT {}",
+                skip, chunk_size
+            );
+            let response = self.execute_cypher(&cypher).await?;
+            if !response.errors.is_empty() {
+                return Err(response.errors[0].message.clone());
+            }
+            let rows: Vec<Entity> = response.data.as_ref()
+                .map(|d| d.values.iter().filter_map(|row| self.parse_entity(row)).collect())
+                .unwrap_or_default();
+            if rows.is_empty() {
+                break;
+            }
+            let count = rows.len() as u32;
+            let jsonl = rows.iter()
+                .filter_map(|e| serde_json::to_string(e).ok())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let file_path = format!("snapshots/{}/entities_{:04}.jsonl", snapshot_id, chunks.len());
+            self.supabase_storage_upload(&file_path, &jsonl).await?;
+            chunks.push(SnapshotChunkRef { file_path, record_count: count, record_type: "entity".to_string() });
+            entity_count += count;
+            if count < chunk_size {
+                break;
+            }
+            skip += chunk_size;
+        }
+
+        let mut relationship_count = 0u32;
+        skip = 0;
+        loop {
+            let cypher = format!(
+                "MATCH (a:Entity)-[r]->(b:Entity) RETURN a.entity_id, b.entity_id, type(r), r.detail, r.strength, r.verified SKIP {} LIMIT {}",
+                skip, chunk_size
+            );
+            let response = self.execute_cypher(&cypher).await?;
+            if !response.errors.is_empty() {
+                return Err(response.errors[0].message.clone());
+            }
+            let rows: Vec<Relationship> = response.data.as_ref()
+                .map(|d| d.values.iter().filter_map(|row| {
+                    if row.len() >= 6 {
+                        Some(Relationship {
+                            source_entity_id: row[0].as_str().unwrap_or("").to_string(),
+                            target_entity_id: row[1].as_str().unwrap_or("").to_string(),
+                            relationship_type: row[2].as_str().unwrap_or("").to_string(),
+                            relationship_detail: row[3].as_str().unwrap_or("").to_string(),
+                            strength: row[4].as_u64().unwrap_or(0) as u32,
+                            verified: row[5].as_bool().unwrap_or(false),
+                        })
+                    } else {
+                        None
+                    }
+                }).collect())
+                .unwrap_or_default();
+            if rows.is_empty() {
+                break;
+            }
+            let count = rows.len() as u32;
+            let jsonl = rows.iter()
+                .filter_map(|r| serde_json::to_string(r).ok())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let file_path = format!("snapshots/{}/relationships_{:04}.jsonl", snapshot_id, chunks.len());
+            self.supabase_storage_upload(&file_path, &jsonl).await?;
+            chunks.push(SnapshotChunkRef { file_path, record_count: count, record_type: "relationship".to_string() });
+            relationship_count += count;
+            if count < chunk_size {
+                break;
+            }
+            skip += chunk_size;
+        }
+
+        let manifest_path = format!("snapshots/{}/manifest.json", snapshot_id);
+        let manifest = SnapshotManifest {
+            snapshot_id,
+            created_at,
+            entity_count,
+            relationship_count,
+            chunks,
+            manifest_path: manifest_path.clone(),
+        };
+        let manifest_json = serde_json::to_string(&manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        self.supabase_storage_upload(&manifest_path, &manifest_json).await?;
+
+        Ok(manifest)
+    }
+
+    /// Re-reads a snapshot's manifest from storage and MERGEs every entity
+    /// and relationship chunk it lists back into Neo4j. Existing nodes/edges
+    /// are updated in place (MERGE), not replaced - this is meant for
+    /// recovering a lost or corrupted graph, not for reverting one that's
+    /// still up but diverged from the snapshot.
+    #[mutate]
+    async fn restore_from_snapshot(&mut self, snapshot_id: String) -> Result<RestoreSummary, String> {
+        self.maintenance_guard()?;
+        self.update_cache("restore_from_snapshot", "", "",
+            &format!("Restore graph snapshot {}", snapshot_id));
+
+        let manifest_path = format!("snapshots/{}/manifest.json", snapshot_id);
+        let manifest_json = self.supabase_storage_download(&manifest_path).await?;
+        let manifest: SnapshotManifest = serde_json::from_str(&manifest_json)
+            .map_err(|e| format!("Failed to parse manifest at {}: {}", manifest_path, e))?;
+
+        let mut entities_restored = 0u32;
+        let mut relationships_restored = 0u32;
+        let mut chunks_processed = 0u32;
+
+        for chunk in &manifest.chunks {
+            let jsonl = self.supabase_storage_download(&chunk.file_path).await?;
+            match chunk.record_type.as_str() {
+                "entity" => {
+                    for line in jsonl.lines().filter(|l| !l.trim().is_empty()) {
+                        let entity: Entity = serde_json::from_str(line)
+                            .map_err(|e| format!("Bad entity record in {}: {}", chunk.file_path, e))?;
+                        let cypher = format!(
+                            "MERGE (e:Entity {{entity_id: '{}'}}) SET e.entity_type = '{}', e.name = '{}', e.pan_number = '{}', e.registration_id = '{}'",
+                            entity.entity_id, entity.entity_type, entity.name, entity.pan_number, entity.registration_id
+                        );
+                        let response = self.execute_cypher(&cypher).await?;
+                        if !response.errors.is_empty() {
+                            return Err(response.errors[0].message.clone());
+                        }
+                        entities_restored += 1;
+                    }
+                }
+                "relationship" => {
+                    for line in jsonl.lines().filter(|l| !l.trim().is_empty()) {
+                        let rel: Relationship = serde_json::from_str(line)
+                            .map_err(|e| format!("Bad relationship record in {}: {}", chunk.file_path, e))?;
+                        let cypher = format!(
+                            "MATCH (a:Entity {{entity_id: '{}'}}), (b:Entity {{entity_id: '{}'}}) MERGE (a)-[r:{}]->(b) SET r.detail = '{}', r.strength = {}, r.verified = {}",
+                            rel.source_entity_id, rel.target_entity_id, rel.relationship_type, rel.relationship_detail, rel.strength, rel.verified
+                        );
+                        let response = self.execute_cypher(&cypher).await?;
+                        if !response.errors.is_empty() {
+                            return Err(response.errors[0].message.clone());
+                        }
+                        relationships_restored += 1;
+                    }
+                }
+                other => return Err(format!("Unknown chunk record_type '{}' in manifest {}", other, manifest_path)),
+            }
+            chunks_processed += 1;
+        }
+
+        Ok(RestoreSummary {
+            snapshot_id: manifest.snapshot_id,
+            entities_restored,
+            relationships_restored,
+            chunks_processed,
+        })
+    }
+
+    /// Record that a sensitive config field was rotated in the secret store. execute_cypher
+    /// re-reads self.secrets.config() on every call, so the new Neo4j credentials are already
+    /// live - this just gives operators an auditable confirmation that the rotation took effect.
+    #[mutate]
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String> {
+        self.maintenance_guard()?;
+        let known_fields = ["neo4j_user", "neo4j_password"];
+        if !known_fields.contains(&field_name.as_str()) {
+            return Err(format!("Unknown rotatable field '{}': expected one of {:?}", field_name, known_fields));
+        }
+
+        for entry in self.secret_versions.iter_mut() {
+            if entry.field_name == field_name {
+                entry.version += 1;
+                entry.rotated_at = rotated_at;
+                return Ok(entry.clone());
+            }
+        }
+
+        let entry = SecretVersionEntry {
+            field_name,
+            version: 1,
+            rotated_at,
+        };
+        self.secret_versions.push(entry.clone());
+        Ok(entry)
+    }
+
+    #[query]
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry> {
+        self.secret_versions.clone()
+    }
+
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -757,6 +1946,25 @@ impl EntityRelationship for EntityRelationshipContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_entity_by_pan",
+      "description": "Get entity details by exact PAN match in Neo4j\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "pan": {
+            "type": "string",
+            "description": "PAN (e.g., AAAPL1234C) - must be an exact, correctly formatted PAN\n"
+          }
+        },
+        "required": [
+          "pan"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -800,6 +2008,35 @@ impl EntityRelationship for EntityRelationshipContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "diff_entity_network",
+      "description": "Diff an entity's relationships between two timestamps: added, removed, and strength-changed\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity identifier - supports fuzzy matching\n"
+          },
+          "ts_a": {
+            "type": "integer",
+            "description": "Earlier timestamp\n"
+          },
+          "ts_b": {
+            "type": "integer",
+            "description": "Later timestamp\n"
+          }
+        },
+        "required": [
+          "entity_id",
+          "ts_a",
+          "ts_b"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -814,7 +2051,15 @@ impl EntityRelationship for EntityRelationshipContractState {
           },
           "max_hops": {
             "type": "integer",
-            "description": "Maximum hops to traverse (1-5)\n"
+            "description": "Maximum hops to traverse (1-4)\n"
+          },
+          "page": {
+            "type": "integer",
+            "description": "Zero-based page number (default: 0)\n"
+          },
+          "page_size": {
+            "type": "integer",
+            "description": "Results per page, capped at 200 (default: 50)\n"
           }
         },
         "required": [
@@ -896,6 +2141,30 @@ impl EntityRelationship for EntityRelationshipContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "explain_connection",
+      "description": "Shortest path between two entities, rendered as a sentence-form narrative suitable for an STR\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id_1": {
+            "type": "string",
+            "description": "First entity ID\n"
+          },
+          "entity_id_2": {
+            "type": "string",
+            "description": "Second entity ID\n"
+          }
+        },
+        "required": [
+          "entity_id_1",
+          "entity_id_2"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -914,6 +2183,217 @@ impl EntityRelationship for EntityRelationshipContractState {
         ]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_group_companies",
+      "description": "Get the corporate group (GROUP/SUBSIDIARY edges) for a company symbol or entity_id, for cross-company insider checks\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id_or_group": {
+            "type": "string",
+            "description": "Company symbol or entity_id - supports fuzzy matching\n"
+          }
+        },
+        "required": [
+          "entity_id_or_group"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "check_cross_company_insider",
+      "description": "List every company in the entity's corporate group where the entity or a family member holds insider status - insiders at a parent often trade the listed subsidiary\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity ID - supports fuzzy matching\n"
+          }
+        },
+        "required": [
+          "entity_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "sync_insider_relationship",
+      "description": "Create or revoke an INSIDER_OF edge for an entity/company pair - called by the designated-person register to keep Neo4j as the source of truth for who is an insider\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity ID - supports fuzzy matching\n"
+          },
+          "company_symbol": {
+            "type": "string",
+            "description": "Stock symbol - supports fuzzy matching\n"
+          },
+          "designation": {
+            "type": "string",
+            "description": "Designation of the person (e.g., CFO, Company Secretary)\n"
+          },
+          "effective_from": {
+            "type": "integer",
+            "description": "Timestamp from which the designation is effective\n"
+          },
+          "active": {
+            "type": "boolean",
+            "description": "true to create/update the INSIDER_OF edge, false to remove it\n"
+          }
+        },
+        "required": [
+          "entity_id",
+          "company_symbol",
+          "designation",
+          "effective_from",
+          "active"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "export_graph_snapshot",
+      "description": "Page through every entity and relationship in Neo4j, write them as chunked JSONL to Supabase storage, and record a manifest listing the chunks - the only backup path since Neo4j Aura's free tier has none built in\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "snapshot_id": {
+            "type": "string",
+            "description": "Identifier for this snapshot; also used as its storage path prefix\n"
+          },
+          "created_at": {
+            "type": "integer",
+            "description": "Timestamp to record on the manifest\n"
+          }
+        },
+        "required": [
+          "snapshot_id",
+          "created_at"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "restore_from_snapshot",
+      "description": "Re-read a snapshot's manifest from storage and MERGE every entity and relationship chunk it lists back into Neo4j\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "snapshot_id": {
+            "type": "string",
+            "description": "Identifier of a snapshot previously written by export_graph_snapshot\n"
+          }
+        },
+        "required": [
+          "snapshot_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "rotate_secret",
+      "description": "Record that a sensitive config field (neo4j_user, neo4j_password) was rotated in the secret store\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "field_name": {
+            "type": "string",
+            "description": "Name of the rotated config field\n"
+          },
+          "rotated_at": {
+            "type": "integer",
+            "description": "Timestamp of the rotation\n"
+          }
+        },
+        "required": [
+          "field_name",
+          "rotated_at"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_secret_versions",
+      "description": "Get rotation metadata (field name, version, timestamp) for sensitive config fields, values excluded\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_circuit_status",
+      "description": "Get the outbound rate-limiter/circuit-breaker status for a host\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "host": {
+            "type": "string",
+            "description": "Host to check, e.g. the configured Neo4j URI\n"
+          }
+        },
+        "required": [
+          "host"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_maintenance_mode",
+      "description": "Enable/disable maintenance mode; while enabled, mutating methods return an error instead of writing partial state\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "enabled": {
+            "type": "boolean",
+            "description": "Whether maintenance mode should be on\n"
+          },
+          "message": {
+            "type": "string",
+            "description": "Operator-facing reason shown in the maintenance error and status banner\n"
+          }
+        },
+        "required": [
+          "enabled",
+          "message"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_maintenance_status",
+      "description": "Get the current maintenance-mode banner (enabled flag and message)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
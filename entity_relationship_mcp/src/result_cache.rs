@@ -0,0 +1,52 @@
+
+use serde::{Deserialize, Serialize};
+use weil_macros::WeilType;
+
+/// One cached Neo4j query result, keyed by a per-method prefix + resolved
+/// params so entries naturally group by the identifier that would invalidate
+/// them (e.g. "company_insiders:INFY"). ticks_remaining counts down on
+/// every cache access rather than wall-clock time - this contract has no
+/// wall clock (see update_cache's timestamp counter), so TTL is expressed in
+/// cache-access ticks, same idea as OutboundGuard's cooldown_ticks_remaining.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CachedQuery {
+    pub key: String,
+    pub value: String,
+    pub ticks_remaining: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct ResultCache {
+    entries: Vec<CachedQuery>,
+}
+
+impl ResultCache {
+    /// Age every entry by one tick and drop anything that just expired
+    fn tick(&mut self) {
+        for entry in self.entries.iter_mut() {
+            if entry.ticks_remaining > 0 {
+                entry.ticks_remaining -= 1;
+            }
+        }
+        self.entries.retain(|e| e.ticks_remaining > 0);
+    }
+
+    pub fn get(&mut self, key: &str) -> Option<String> {
+        self.tick();
+        self.entries.iter().find(|e| e.key == key).map(|e| e.value.clone())
+    }
+
+    pub fn put(&mut self, key: String, value: String, ttl_ticks: u32) {
+        self.entries.retain(|e| e.key != key);
+        if ttl_ticks > 0 {
+            self.entries.push(CachedQuery { key, value, ticks_remaining: ttl_ticks });
+        }
+    }
+
+    /// Drop every cached entry whose key starts with `prefix` - called by the
+    /// write APIs (sync_insider_relationship and attach_document) so a stale read
+    /// can't outlive the write that invalidated it
+    pub fn invalidate(&mut self, prefix: &str) {
+        self.entries.retain(|e| !e.key.starts_with(prefix));
+    }
+}
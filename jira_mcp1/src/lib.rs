@@ -1,4 +1,7 @@
 
+mod http_fixtures;
+mod outbound_guard;
+
 use base64::{Engine as _, engine::general_purpose};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -6,6 +9,8 @@ use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
 use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
 
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
+
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
@@ -15,10 +20,24 @@ pub struct JiraConfig {
     pub jira_api_token: String,
     pub project_key: String,
     pub default_issue_type: String,
+    /// "live" (default): call Jira for real. "record": call it for real and
+    /// save the response as a fixture. "playback": skip the network and return
+    /// the previously recorded fixture, erroring if none exists - see
+    /// http_fixtures for the whole scheme
+    pub http_fixture_mode: String,
 }
 
 // ===== DATA STRUCTURES =====
 
+/// Rotation metadata for a sensitive config field - never the value itself,
+/// so operators can confirm a rotation took effect without exposing the secret
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SecretVersionEntry {
+    pub field_name: String,
+    pub version: u32,
+    pub rotated_at: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct JiraTicket {
     pub ticket_id: String,
@@ -42,6 +61,56 @@ pub struct TicketResult {
     pub error: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BatchTicketItem {
+    pub summary: String,
+    pub description: String,
+    pub priority: String,
+    pub issue_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BatchTicketResult {
+    pub results: Vec<TicketResult>,
+    pub created_count: u32,
+    pub failed_count: u32,
+}
+
+/// One mandatory or optional step in a case's investigation checklist
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ChecklistItem {
+    pub item_id: String,
+    pub description: String,
+    pub mandatory: bool,
+    pub completed: bool,
+    pub completed_by: String,
+    pub note: String,
+    pub completed_at: u64,
+}
+
+/// The investigation checklist attached to a case ticket, seeded from a
+/// per-case-type template when the checklist is started
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CaseChecklist {
+    pub ticket_key: String,
+    pub case_type: String,
+    pub items: Vec<ChecklistItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraBulkResponse {
+    issues: Vec<JiraIssueResponse>,
+    errors: Vec<JiraBulkError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraBulkError {
+    #[serde(rename = "failedElementNumber")]
+    failed_element_number: Option<u32>,
+    #[serde(rename = "elementErrors")]
+    element_errors: Option<serde_json::Value>,
+}
+
 // Jira API response structures
 #[derive(Debug, Deserialize)]
 struct JiraIssueResponse {
@@ -91,30 +160,211 @@ struct JiraUser {
     display_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct JiraSearchResponse {
+    issues: Vec<JiraSearchIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraSearchIssue {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraChangelogResponse {
+    changelog: Option<JiraChangelog>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraChangelog {
+    histories: Vec<JiraHistory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraHistory {
+    created: String,
+    author: Option<JiraUser>,
+    items: Vec<JiraHistoryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraHistoryItem {
+    field: String,
+    #[serde(rename = "fromString")]
+    from_string: Option<String>,
+    #[serde(rename = "toString")]
+    to_string: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraCommentsResponse {
+    comments: Vec<JiraComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraComment {
+    created: String,
+    author: Option<JiraUser>,
+    body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TimelineEvent {
+    pub timestamp: String,
+    pub day: String,
+    pub actor: String,
+    pub description: String,
+}
+
 // ===== TRAIT DEFINITION =====
 
 trait JiraIntegration {
     fn new() -> Result<Self, String> where Self: Sized;
-    async fn create_ticket(&self, summary: String, description: Option<String>, priority: Option<String>, issue_type: Option<String>) -> Result<TicketResult, String>;
-    async fn create_case_ticket(&self, case_id: String, subject_entity: String, case_summary: String, priority: Option<String>) -> Result<TicketResult, String>;
-    async fn close_ticket(&self, ticket_key: String, resolution: Option<String>) -> Result<TicketResult, String>;
-    async fn get_ticket(&self, ticket_key: String) -> Result<JiraTicket, String>;
-    async fn add_comment(&self, ticket_key: String, comment: String) -> Result<TicketResult, String>;
-    async fn update_ticket_status(&self, ticket_key: String, new_status: String) -> Result<TicketResult, String>;
+    // idempotency_key: if set and a prior call with the same key is still
+    // within its TTL, returns that call's result instead of filing again
+    async fn create_ticket(&mut self, summary: String, description: Option<String>, priority: Option<String>, issue_type: Option<String>, idempotency_key: Option<String>) -> Result<TicketResult, String>;
+    async fn create_case_ticket(&mut self, case_id: String, subject_entity: String, case_summary: String, priority: Option<String>) -> Result<TicketResult, String>;
+    async fn create_tickets_batch(&mut self, items_json: String) -> Result<BatchTicketResult, String>;
+    async fn close_ticket(&mut self, ticket_key: String, resolution: Option<String>) -> Result<TicketResult, String>;
+    async fn start_case_checklist(&mut self, ticket_key: String, case_type: String) -> Result<CaseChecklist, String>;
+    async fn complete_checklist_item(&mut self, ticket_key: String, item_id: String, actor: String, note: String) -> Result<CaseChecklist, String>;
+    fn get_case_checklist(&self, ticket_key: String) -> Result<CaseChecklist, String>;
+    async fn get_ticket(&mut self, ticket_key: String) -> Result<JiraTicket, String>;
+    async fn add_comment(&mut self, ticket_key: String, comment: String) -> Result<TicketResult, String>;
+    async fn update_ticket_status(&mut self, ticket_key: String, new_status: String) -> Result<TicketResult, String>;
+    async fn export_timeline(&mut self, case_id: String, format: String) -> Result<String, String>;
+    async fn get_case_events(&mut self, case_id: String) -> Result<Vec<TimelineEvent>, String>;
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String>;
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely carry out
+/// maintenance without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
+/// One cached create_ticket result, keyed by the caller-supplied
+/// idempotency_key, so an agent's retried call returns the original ticket
+/// instead of filing a duplicate. ticks_remaining counts down on every cache
+/// access rather than wall-clock time - this contract has no wall clock, same
+/// idea as OutboundGuard's cooldown_ticks_remaining.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CachedIdempotentResult {
+    pub key: String,
+    pub value: String,
+    pub ticks_remaining: u32,
+}
+
+const IDEMPOTENCY_TTL_TICKS: u32 = 50;
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct IdempotencyCache {
+    entries: Vec<CachedIdempotentResult>,
+}
+
+impl IdempotencyCache {
+    fn tick(&mut self) {
+        for entry in self.entries.iter_mut() {
+            if entry.ticks_remaining > 0 {
+                entry.ticks_remaining -= 1;
+            }
+        }
+        self.entries.retain(|e| e.ticks_remaining > 0);
+    }
+
+    /// Look up a prior result for `key`, if still within its TTL
+    fn get(&mut self, key: &str) -> Option<String> {
+        self.tick();
+        self.entries.iter().find(|e| e.key == key).map(|e| e.value.clone())
+    }
+
+    /// Remember `value` (a serialized result) under `key` for IDEMPOTENCY_TTL_TICKS accesses
+    fn put(&mut self, key: &str, value: String) {
+        self.entries.retain(|e| e.key != key);
+        self.entries.push(CachedIdempotentResult { key: key.to_string(), value, ticks_remaining: IDEMPOTENCY_TTL_TICKS });
+    }
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct JiraIntegrationContractState {
     secrets: Secrets<JiraConfig>,
+    outbound_guard: OutboundGuard,
+    secret_versions: Vec<SecretVersionEntry>,
+    maintenance: MaintenanceStatus,
+    checklists: Vec<CaseChecklist>,
+    http_fixtures: Vec<http_fixtures::HttpFixture>,
+    /// Keyed by the idempotency_key callers pass to create_ticket - see
+    /// CachedIdempotentResult's doc comment
+    idempotency_cache: IdempotencyCache,
 }
 
 // ===== HELPER METHODS =====
 
 impl JiraIntegrationContractState {
+    /// Reject mutating calls while maintenance mode is on, so in-flight
+    /// maintenance can't race a concurrent tool call into a half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Predefined mandatory/optional steps for a case type, seeded when a checklist is started
+    fn default_checklist_items(case_type: &str) -> Vec<ChecklistItem> {
+        let templates: &[(&str, bool)] = match case_type {
+            "INSIDER" => &[
+                ("Confirm insider/designated-person status", true),
+                ("Pull UPSI access records", true),
+                ("Pull trade history around the UPSI window", true),
+                ("Conduct interview and file note", true),
+                ("Legal review", true),
+            ],
+            "MANIPULATION" => &[
+                ("Confirm suspicious trading pattern", true),
+                ("Pull order book and trade history", true),
+                ("Identify connected/related accounts", true),
+                ("Legal review", true),
+            ],
+            "FRONT_RUNNING" => &[
+                ("Confirm order-timing sequence", true),
+                ("Pull trade history for front-runner and client orders", true),
+                ("Identify connected entities", true),
+                ("Legal review", true),
+            ],
+            _ => &[
+                ("Initial case review", true),
+                ("Legal review", true),
+            ],
+        };
+
+        templates
+            .iter()
+            .enumerate()
+            .map(|(i, (description, mandatory))| ChecklistItem {
+                item_id: format!("item-{}", i + 1),
+                description: description.to_string(),
+                mandatory: *mandatory,
+                completed: false,
+                completed_by: "".to_string(),
+                note: "".to_string(),
+                completed_at: 0,
+            })
+            .collect()
+    }
+
     fn get_headers(&self) -> HashMap<String, String> {
         let config = self.secrets.config();
         let credentials = format!("{}:{}", config.jira_email, config.jira_api_token);
@@ -127,13 +377,39 @@ impl JiraIntegrationContractState {
     }
     
     async fn make_request(
-        &self,
+        &mut self,
         method: HttpMethod,
         endpoint: &str,
         query_params: Vec<(String, String)>,
         body: Option<String>,
         expected_status_code: u16,
     ) -> Result<(u16, String), String> {
+        let host = self.secrets.config().jira_url.clone();
+        let mode = self.secrets.config().http_fixture_mode.clone();
+        let method_str = match method {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Patch => "PATCH",
+        };
+        let mut sorted_params = query_params.clone();
+        sorted_params.sort();
+        let params_str = sorted_params.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+        let key = http_fixtures::fixture_key(method_str, &format!("{}?{}", endpoint, params_str), body.as_deref().unwrap_or(""));
+
+        if mode == "playback" {
+            return match http_fixtures::find(&self.http_fixtures, &key) {
+                Some(f) if f.status as u16 == expected_status_code || (200..300).contains(&(f.status as u16)) => {
+                    Ok((f.status as u16, f.body.clone()))
+                }
+                Some(f) => Err(format!("HTTP {} (fixture): {}", f.status, f.body)),
+                None => Err(format!("No recorded HTTP fixture for {}", key)),
+            };
+        }
+
+        self.outbound_guard.check(&host)?;
+
         let url = format!(
             "{}/rest/api/3/{}",
             self.secrets.config().jira_url,
@@ -145,21 +421,116 @@ impl JiraIntegrationContractState {
         let mut request = HttpClient::request(&url, method)
             .headers(headers)
             .query(query_params);
-        
+
         if let Some(body_str) = body {
             request = request.body(body_str);
         }
 
-        let response = request.send().map_err(|err| err.to_string())?;
+        let response = match request.send() {
+            Ok(response) => response,
+            Err(err) => {
+                self.outbound_guard.record_result(&host, false);
+                if mode == "record" {
+                    http_fixtures::upsert(&mut self.http_fixtures, key, 599, err.to_string());
+                }
+                return Err(err.to_string());
+            }
+        };
         let status = response.status();
         let text = response.text();
 
+        if mode == "record" {
+            http_fixtures::upsert(&mut self.http_fixtures, key, status, text.clone());
+        }
+
         if status != expected_status_code && !(200..300).contains(&status) {
+            self.outbound_guard.record_result(&host, false);
             return Err(format!("HTTP {}: {}", status, text));
         }
 
+        self.outbound_guard.record_result(&host, true);
         Ok((status, text))
     }
+
+    /// Find the Jira ticket for a case and merge its status changes and comments into
+    /// a single timestamp-sorted event list. Shared by export_timeline and get_case_events.
+    async fn collect_case_events(&mut self, case_id: &str) -> Result<(String, Vec<TimelineEvent>), String> {
+        let jql = format!("summary ~ \"[CASE {}]\"", case_id);
+        let (_, search_text) = self.make_request(
+            HttpMethod::Get,
+            "search",
+            vec![("jql".to_string(), jql), ("maxResults".to_string(), "1".to_string())],
+            None,
+            200,
+        ).await?;
+
+        let search: JiraSearchResponse = serde_json::from_str(&search_text)
+            .map_err(|e| format!("Failed to parse Jira search response: {} - Body: {}", e, search_text))?;
+
+        let ticket_key = search.issues.into_iter().next()
+            .map(|i| i.key)
+            .ok_or_else(|| format!("No Jira ticket found for case {}", case_id))?;
+
+        let (_, issue_text) = self.make_request(
+            HttpMethod::Get,
+            &format!("issue/{}", ticket_key),
+            vec![("expand".to_string(), "changelog".to_string())],
+            None,
+            200,
+        ).await?;
+
+        let changelog: JiraChangelogResponse = serde_json::from_str(&issue_text)
+            .map_err(|e| format!("Failed to parse Jira changelog: {} - Body: {}", e, issue_text))?;
+
+        let (_, comments_text) = self.make_request(
+            HttpMethod::Get,
+            &format!("issue/{}/comment", ticket_key),
+            vec![],
+            None,
+            200,
+        ).await?;
+
+        let comments: JiraCommentsResponse = serde_json::from_str(&comments_text)
+            .map_err(|e| format!("Failed to parse Jira comments: {} - Body: {}", e, comments_text))?;
+
+        let mut events = Vec::new();
+
+        if let Some(cl) = changelog.changelog {
+            for history in cl.histories {
+                let actor = history.author.and_then(|a| a.display_name).unwrap_or_else(|| "Unknown".to_string());
+                let day = history.created.get(0..10).unwrap_or(&history.created).to_string();
+                for item in history.items {
+                    events.push(TimelineEvent {
+                        timestamp: history.created.clone(),
+                        day: day.clone(),
+                        actor: actor.clone(),
+                        description: format!(
+                            "Changed {} from '{}' to '{}'",
+                            item.field,
+                            item.from_string.unwrap_or_default(),
+                            item.to_string.unwrap_or_default()
+                        ),
+                    });
+                }
+            }
+        }
+
+        for comment in comments.comments {
+            let actor = comment.author.and_then(|a| a.display_name).unwrap_or_else(|| "Unknown".to_string());
+            let day = comment.created.get(0..10).unwrap_or(&comment.created).to_string();
+            let text = comment.body.as_ref().map(adf_to_text).unwrap_or_default();
+            events.push(TimelineEvent {
+                timestamp: comment.created.clone(),
+                day,
+                actor,
+                description: format!("Commented: {}", text),
+            });
+        }
+
+        events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        Ok((ticket_key, events))
+    }
 }
 
 // ===== CONTRACT IMPLEMENTATION =====
@@ -173,13 +544,46 @@ impl JiraIntegration for JiraIntegrationContractState {
     {
         Ok(JiraIntegrationContractState {
             secrets: Secrets::new(),
+            outbound_guard: OutboundGuard::default(),
+            secret_versions: Vec::new(),
+            maintenance: MaintenanceStatus::default(),
+            checklists: Vec::new(),
+            http_fixtures: Vec::new(),
+            idempotency_cache: IdempotencyCache::default(),
         })
     }
 
-    #[query]
+    #[mutate]
     async fn create_ticket(
-        &self, 
-        summary: String, 
+        &mut self,
+        summary: String,
+        description: Option<String>,
+        priority: Option<String>,
+        issue_type: Option<String>,
+        idempotency_key: Option<String>
+    ) -> Result<TicketResult, String> {
+        self.maintenance_guard()?;
+
+        if let Some(ref key) = idempotency_key {
+            if let Some(cached) = self.idempotency_cache.get(key) {
+                return serde_json::from_str(&cached).map_err(|e| format!("Failed to replay cached create_ticket result: {}", e));
+            }
+        }
+
+        let result = self.create_ticket_inner(summary, description, priority, issue_type).await;
+
+        if let (Some(ref key), Ok(ref value)) = (&idempotency_key, &result) {
+            if let Ok(serialized) = serde_json::to_string(value) {
+                self.idempotency_cache.put(key, serialized);
+            }
+        }
+
+        result
+    }
+
+    async fn create_ticket_inner(
+        &mut self,
+        summary: String,
         description: Option<String>,
         priority: Option<String>,
         issue_type: Option<String>
@@ -242,25 +646,115 @@ impl JiraIntegration for JiraIntegrationContractState {
         }
     }
 
-    #[query]
+    #[mutate]
     async fn create_case_ticket(
-        &self, 
-        case_id: String, 
+        &mut self,
+        case_id: String,
         subject_entity: String, 
         case_summary: String,
         priority: Option<String>
     ) -> Result<TicketResult, String> {
+        self.maintenance_guard()?;
         let summary = format!("[CASE {}] Investigation: {}", case_id, subject_entity);
         let description = format!(
             "Surveillance Case Investigation\n\n- Case ID: {}\n- Subject Entity: {}\n- Summary: {}\n\nThis ticket was auto-created from the Market Surveillance System.",
             case_id, subject_entity, case_summary
         );
         
-        self.create_ticket(summary, Some(description), priority, Some("Task".to_string())).await
+        self.create_ticket_inner(summary, Some(description), priority, Some("Task".to_string())).await
     }
 
-    #[query]
-    async fn close_ticket(&self, ticket_key: String, resolution: Option<String>) -> Result<TicketResult, String> {
+    #[mutate]
+    async fn create_tickets_batch(&mut self, items_json: String) -> Result<BatchTicketResult, String> {
+        self.maintenance_guard()?;
+        let config = self.secrets.config();
+        let items: Vec<BatchTicketItem> = serde_json::from_str(&items_json)
+            .map_err(|e| format!("Invalid items_json: {}", e))?;
+
+        if items.is_empty() {
+            return Err("items_json must contain at least one ticket".to_string());
+        }
+
+        let issue_updates: Vec<serde_json::Value> = items
+            .iter()
+            .map(|item| {
+                let itype = if item.issue_type.is_empty() { config.default_issue_type.clone() } else { item.issue_type.clone() };
+                let prio = if item.priority.is_empty() { "Medium".to_string() } else { item.priority.clone() };
+                serde_json::json!({
+                    "fields": {
+                        "project": { "key": config.project_key },
+                        "summary": item.summary,
+                        "description": {
+                            "type": "doc",
+                            "version": 1,
+                            "content": [{
+                                "type": "paragraph",
+                                "content": [{ "type": "text", "text": item.description }]
+                            }]
+                        },
+                        "issuetype": { "name": itype },
+                        "priority": { "name": prio }
+                    }
+                })
+            })
+            .collect();
+
+        let payload = serde_json::json!({ "issueUpdates": issue_updates });
+        let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+
+        let result = self.make_request(
+            HttpMethod::Post,
+            "issue/bulk",
+            vec![],
+            Some(body),
+            201,
+        ).await?;
+
+        let bulk: JiraBulkResponse = serde_json::from_str(&result.1)
+            .map_err(|e| format!("Failed to parse bulk response: {}. Response: {}", e, result.1))?;
+
+        let mut results: Vec<TicketResult> = bulk.issues.into_iter().map(|issue| TicketResult {
+            success: true,
+            ticket_key: issue.key.clone(),
+            ticket_url: format!("{}/browse/{}", config.jira_url, issue.key),
+            error: "".to_string(),
+        }).collect();
+
+        for err in &bulk.errors {
+            results.push(TicketResult {
+                success: false,
+                ticket_key: "".to_string(),
+                ticket_url: "".to_string(),
+                error: format!(
+                    "element {}: {}",
+                    err.failed_element_number.unwrap_or(0),
+                    err.element_errors.as_ref().map(|v| v.to_string()).unwrap_or_default()
+                ),
+            });
+        }
+
+        let created_count = results.iter().filter(|r| r.success).count() as u32;
+        let failed_count = results.len() as u32 - created_count;
+
+        Ok(BatchTicketResult { results, created_count, failed_count })
+    }
+
+    #[mutate]
+    async fn close_ticket(&mut self, ticket_key: String, resolution: Option<String>) -> Result<TicketResult, String> {
+        self.maintenance_guard()?;
+        if let Some(checklist) = self.checklists.iter().find(|c| c.ticket_key == ticket_key) {
+            let outstanding: Vec<&str> = checklist.items.iter()
+                .filter(|i| i.mandatory && !i.completed)
+                .map(|i| i.description.as_str())
+                .collect();
+            if !outstanding.is_empty() {
+                return Err(format!(
+                    "Cannot close {}: mandatory checklist items outstanding: {}",
+                    ticket_key,
+                    outstanding.join(", ")
+                ));
+            }
+        }
         let config = self.secrets.config();
         let _res = resolution.unwrap_or_else(|| "Done".to_string());
         
@@ -294,8 +788,54 @@ impl JiraIntegration for JiraIntegrationContractState {
         }
     }
 
+    #[mutate]
+    async fn start_case_checklist(&mut self, ticket_key: String, case_type: String) -> Result<CaseChecklist, String> {
+        self.maintenance_guard()?;
+        if self.checklists.iter().any(|c| c.ticket_key == ticket_key) {
+            return Err(format!("A checklist already exists for ticket {}", ticket_key));
+        }
+
+        let checklist = CaseChecklist {
+            ticket_key: ticket_key.clone(),
+            case_type: case_type.clone(),
+            items: Self::default_checklist_items(&case_type),
+        };
+        self.checklists.push(checklist.clone());
+        Ok(checklist)
+    }
+
+    #[mutate]
+    async fn complete_checklist_item(&mut self, ticket_key: String, item_id: String, actor: String, note: String) -> Result<CaseChecklist, String> {
+        self.maintenance_guard()?;
+        let timestamp = 1735689600u64;
+
+        let checklist = self.checklists.iter_mut()
+            .find(|c| c.ticket_key == ticket_key)
+            .ok_or_else(|| format!("No checklist found for ticket {}", ticket_key))?;
+
+        let item = checklist.items.iter_mut()
+            .find(|i| i.item_id == item_id)
+            .ok_or_else(|| format!("Unknown checklist item '{}' for ticket {}", item_id, ticket_key))?;
+
+        item.completed = true;
+        item.completed_by = actor;
+        item.note = note;
+        item.completed_at = timestamp;
+
+        Ok(checklist.clone())
+    }
+
     #[query]
-    async fn get_ticket(&self, ticket_key: String) -> Result<JiraTicket, String> {
+    fn get_case_checklist(&self, ticket_key: String) -> Result<CaseChecklist, String> {
+        self.checklists.iter()
+            .find(|c| c.ticket_key == ticket_key)
+            .cloned()
+            .ok_or_else(|| format!("No checklist found for ticket {}", ticket_key))
+    }
+
+    #[mutate]
+    async fn get_ticket(&mut self, ticket_key: String) -> Result<JiraTicket, String> {
+        self.maintenance_guard()?;
         let config = self.secrets.config();
         
         let result = self.make_request(
@@ -326,8 +866,9 @@ impl JiraIntegration for JiraIntegrationContractState {
         }
     }
 
-    #[query]
-    async fn add_comment(&self, ticket_key: String, comment: String) -> Result<TicketResult, String> {
+    #[mutate]
+    async fn add_comment(&mut self, ticket_key: String, comment: String) -> Result<TicketResult, String> {
+        self.maintenance_guard()?;
         let config = self.secrets.config();
         
         let payload = serde_json::json!({
@@ -367,8 +908,9 @@ impl JiraIntegration for JiraIntegrationContractState {
         }
     }
 
-    #[query]
-    async fn update_ticket_status(&self, ticket_key: String, new_status: String) -> Result<TicketResult, String> {
+    #[mutate]
+    async fn update_ticket_status(&mut self, ticket_key: String, new_status: String) -> Result<TicketResult, String> {
+        self.maintenance_guard()?;
         let config = self.secrets.config();
         
         let transition_id = match new_status.as_str() {
@@ -408,6 +950,78 @@ impl JiraIntegration for JiraIntegrationContractState {
         }
     }
 
+    /// Export a case's Jira history (status changes and comments) as a day/actor grouped
+    /// timeline, either as ADF (for posting back as a structured comment) or Markdown
+    /// (for inclusion in investigation reports)
+    #[mutate]
+    async fn export_timeline(&mut self, case_id: String, format: String) -> Result<String, String> {
+        self.maintenance_guard()?;
+        let (ticket_key, events) = self.collect_case_events(&case_id).await?;
+
+        match format.as_str() {
+            "adf" => Ok(render_timeline_adf(&ticket_key, &events)),
+            _ => Ok(render_timeline_markdown(&ticket_key, &events)),
+        }
+    }
+
+    /// Same underlying Jira changelog + comment history as export_timeline, but returned
+    /// as structured events so other MCPs can merge it into a cross-system activity view
+    #[mutate]
+    async fn get_case_events(&mut self, case_id: String) -> Result<Vec<TimelineEvent>, String> {
+        self.maintenance_guard()?;
+        let (_, events) = self.collect_case_events(&case_id).await?;
+        Ok(events)
+    }
+
+    /// Record that a sensitive config field was rotated in the secret store. get_headers()
+    /// re-reads self.secrets.config() on every call, so the new value is already live -
+    /// this just gives operators an auditable confirmation that the rotation took effect.
+    #[mutate]
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String> {
+        self.maintenance_guard()?;
+        let known_fields = ["jira_email", "jira_api_token"];
+        if !known_fields.contains(&field_name.as_str()) {
+            return Err(format!("Unknown rotatable field '{}': expected one of {:?}", field_name, known_fields));
+        }
+
+        for entry in self.secret_versions.iter_mut() {
+            if entry.field_name == field_name {
+                entry.version += 1;
+                entry.rotated_at = rotated_at;
+                return Ok(entry.clone());
+            }
+        }
+
+        let entry = SecretVersionEntry {
+            field_name,
+            version: 1,
+            rotated_at,
+        };
+        self.secret_versions.push(entry.clone());
+        Ok(entry)
+    }
+
+    #[query]
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry> {
+        self.secret_versions.clone()
+    }
+
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -434,6 +1048,10 @@ impl JiraIntegration for JiraIntegrationContractState {
           "issue_type": {
             "type": "string",
             "description": "Optional issue type: Task, Bug, Story\n"
+          },
+          "idempotency_key": {
+            "type": "string",
+            "description": "Optional caller-supplied key; a retried call with the same key replays the original result instead of filing a duplicate ticket\n"
           }
         },
         "required": [
@@ -475,6 +1093,25 @@ impl JiraIntegration for JiraIntegrationContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "create_tickets_batch",
+      "description": "Create several Jira tickets in a single call via the bulk create endpoint\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "items_json": {
+            "type": "string",
+            "description": "JSON array of {summary, description, priority, issue_type} objects\n"
+          }
+        },
+        "required": [
+          "items_json"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -498,6 +1135,83 @@ impl JiraIntegration for JiraIntegrationContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "start_case_checklist",
+      "description": "Seed a case ticket with the mandatory/optional investigation checklist for its case type. close_ticket refuses to close the ticket until mandatory items are complete\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "ticket_key": {
+            "type": "string",
+            "description": "Jira ticket key (e.g., WEIL-123)\n"
+          },
+          "case_type": {
+            "type": "string",
+            "description": "Case type: INSIDER, MANIPULATION, FRONT_RUNNING, or any other value for a generic checklist\n"
+          }
+        },
+        "required": [
+          "ticket_key",
+          "case_type"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "complete_checklist_item",
+      "description": "Mark a checklist item complete, recording who did it and any note\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "ticket_key": {
+            "type": "string",
+            "description": "Jira ticket key\n"
+          },
+          "item_id": {
+            "type": "string",
+            "description": "Checklist item ID (e.g., item-1)\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "Who completed the item\n"
+          },
+          "note": {
+            "type": "string",
+            "description": "Optional note about how the item was completed\n"
+          }
+        },
+        "required": [
+          "ticket_key",
+          "item_id",
+          "actor",
+          "note"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_case_checklist",
+      "description": "Get the current checklist and completion status for a case ticket\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "ticket_key": {
+            "type": "string",
+            "description": "Jira ticket key\n"
+          }
+        },
+        "required": [
+          "ticket_key"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -541,6 +1255,61 @@ impl JiraIntegration for JiraIntegrationContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "rotate_secret",
+      "description": "Record that a sensitive config field (jira_email, jira_api_token) was rotated in the secret store\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "field_name": {
+            "type": "string",
+            "description": "Name of the rotated config field\n"
+          },
+          "rotated_at": {
+            "type": "integer",
+            "description": "Timestamp of the rotation\n"
+          }
+        },
+        "required": [
+          "field_name",
+          "rotated_at"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_secret_versions",
+      "description": "Get rotation metadata (field name, version, timestamp) for sensitive config fields, values excluded\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_circuit_status",
+      "description": "Get the outbound rate-limiter/circuit-breaker status for a host\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "host": {
+            "type": "string",
+            "description": "Host to inspect (e.g., the configured jira_url)\n"
+          }
+        },
+        "required": [
+          "host"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -564,6 +1333,85 @@ impl JiraIntegration for JiraIntegrationContractState {
         ]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "export_timeline",
+      "description": "Export a case's Jira status changes and comments as a day/actor grouped timeline\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "case_id": {
+            "type": "string",
+            "description": "Case ID as used in create_case_ticket (e.g., CASE-001)\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "Output format: 'adf' for a structured comment body, 'markdown' for report inclusion\n"
+          }
+        },
+        "required": [
+          "case_id",
+          "format"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_case_events",
+      "description": "Same history as export_timeline, returned as structured events\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "case_id": {
+            "type": "string",
+            "description": "Case ID as used in create_case_ticket (e.g., CASE-001)\n"
+          }
+        },
+        "required": [
+          "case_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_maintenance_mode",
+      "description": "Enable/disable maintenance mode; while enabled, mutating methods return an error instead of writing partial state\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "enabled": {
+            "type": "boolean",
+            "description": "Whether maintenance mode should be on\n"
+          },
+          "message": {
+            "type": "string",
+            "description": "Banner message to surface to callers while maintenance mode is on\n"
+          }
+        },
+        "required": [
+          "enabled",
+          "message"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_maintenance_status",
+      "description": "Get the current maintenance-mode banner (enabled flag and message)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
@@ -575,3 +1423,84 @@ impl JiraIntegration for JiraIntegrationContractState {
 }"#.to_string()
     }
 }
+
+// ===== TIMELINE EXPORT HELPERS =====
+
+/// Flatten an ADF document into plain text by concatenating all "text" nodes
+fn adf_to_text(value: &serde_json::Value) -> String {
+    let mut text = String::new();
+    if let Some(t) = value.get("text").and_then(|v| v.as_str()) {
+        text.push_str(t);
+    }
+    if let Some(content) = value.get("content").and_then(|v| v.as_array()) {
+        for item in content {
+            let child = adf_to_text(item);
+            if !child.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&child);
+            }
+        }
+    }
+    text
+}
+
+fn render_timeline_markdown(ticket_key: &str, events: &[TimelineEvent]) -> String {
+    let mut out = format!("# Case Timeline ({})\n\n", ticket_key);
+    let mut current_day = String::new();
+
+    for event in events {
+        if event.day != current_day {
+            out.push_str(&format!("## {}\n\n", event.day));
+            current_day = event.day.clone();
+        }
+        out.push_str(&format!("- **{}**: {}\n", event.actor, event.description));
+    }
+
+    out
+}
+
+fn render_timeline_adf(ticket_key: &str, events: &[TimelineEvent]) -> String {
+    let mut content = vec![serde_json::json!({
+        "type": "heading",
+        "attrs": { "level": 1 },
+        "content": [{ "type": "text", "text": format!("Case Timeline ({})", ticket_key) }]
+    })];
+
+    let mut current_day = String::new();
+    let mut bullets: Vec<serde_json::Value> = Vec::new();
+
+    for event in events {
+        if event.day != current_day {
+            if !bullets.is_empty() {
+                content.push(serde_json::json!({ "type": "bulletList", "content": bullets }));
+                bullets = Vec::new();
+            }
+            content.push(serde_json::json!({
+                "type": "heading",
+                "attrs": { "level": 2 },
+                "content": [{ "type": "text", "text": event.day.clone() }]
+            }));
+            current_day = event.day.clone();
+        }
+        bullets.push(serde_json::json!({
+            "type": "listItem",
+            "content": [{
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": format!("{}: {}", event.actor, event.description) }]
+            }]
+        }));
+    }
+    if !bullets.is_empty() {
+        content.push(serde_json::json!({ "type": "bulletList", "content": bullets }));
+    }
+
+    let doc = serde_json::json!({
+        "type": "doc",
+        "version": 1,
+        "content": content
+    });
+
+    serde_json::to_string(&doc).unwrap_or_default()
+}
@@ -4,11 +4,15 @@ use weil_rs::runtime::Runtime;
 
 pub struct DashboardMcp {
     contract_id: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert/upsert_case's
+    /// caller_token
+    caller_token: String,
 }
 
 impl DashboardMcp {
-    pub fn new(contract_id: String) -> Self {
-        DashboardMcp { contract_id }
+    pub fn new(contract_id: String, caller_token: String) -> Self {
+        DashboardMcp { contract_id, caller_token }
     }
 }
 
@@ -75,7 +79,14 @@ pub struct RiskEntity {
 
 impl DashboardMcp {
     pub fn get_stats(&self) -> Result<SurveillanceStats> {
-        let serialized_args = None;
+        #[derive(Debug, Serialize)]
+        struct GetStatsArgs {
+            token: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetStatsArgs {
+            token: self.caller_token.clone(),
+        })?);
 
         let resp = Runtime::call_contract::<SurveillanceStats>(
             self.contract_id.clone(),
@@ -110,11 +121,13 @@ impl DashboardMcp {
     pub fn get_live_alerts(&self, severity_filter: String, limit: u32) -> Result<Vec<Alert>> {
         #[derive(Debug, Serialize)]
         struct GetLiveAlertsArgs {
+            token: String,
             severity_filter: String,
             limit: u32,
         }
 
         let serialized_args = Some(serde_json::to_string(&GetLiveAlertsArgs {
+            token: self.caller_token.clone(),
             severity_filter,
             limit,
         })?);
@@ -131,11 +144,13 @@ impl DashboardMcp {
     pub fn get_workflow_history(&self, workflow_type: String, limit: u32) -> Result<Vec<WorkflowExecution>> {
         #[derive(Debug, Serialize)]
         struct GetWorkflowHistoryArgs {
+            token: String,
             workflow_type: String,
             limit: u32,
         }
 
         let serialized_args = Some(serde_json::to_string(&GetWorkflowHistoryArgs {
+            token: self.caller_token.clone(),
             workflow_type,
             limit,
         })?);
@@ -152,11 +167,13 @@ impl DashboardMcp {
     pub fn get_high_risk_entities(&self, min_risk_score: u32, limit: u32) -> Result<Vec<RiskEntity>> {
         #[derive(Debug, Serialize)]
         struct GetHighRiskEntitiesArgs {
+            token: String,
             min_risk_score: u32,
             limit: u32,
         }
 
         let serialized_args = Some(serde_json::to_string(&GetHighRiskEntitiesArgs {
+            token: self.caller_token.clone(),
             min_risk_score,
             limit,
         })?);
@@ -173,10 +190,14 @@ impl DashboardMcp {
     pub fn get_case_details(&self, case_id: String) -> Result<CaseRecord> {
         #[derive(Debug, Serialize)]
         struct GetCaseDetailsArgs {
+            token: String,
             case_id: String,
         }
 
-        let serialized_args = Some(serde_json::to_string(&GetCaseDetailsArgs { case_id })?);
+        let serialized_args = Some(serde_json::to_string(&GetCaseDetailsArgs {
+            token: self.caller_token.clone(),
+            case_id,
+        })?);
 
         let resp = Runtime::call_contract::<CaseRecord>(
             self.contract_id.clone(),
@@ -190,10 +211,14 @@ impl DashboardMcp {
     pub fn push_alert(&self, alert: Alert) -> Result<String> {
         #[derive(Debug, Serialize)]
         struct PushAlertArgs {
+            caller_token: String,
             alert: Alert,
         }
 
-        let serialized_args = Some(serde_json::to_string(&PushAlertArgs { alert })?);
+        let serialized_args = Some(serde_json::to_string(&PushAlertArgs {
+            caller_token: self.caller_token.clone(),
+            alert,
+        })?);
 
         let resp = Runtime::call_contract::<String>(
             self.contract_id.clone(),
@@ -207,10 +232,14 @@ impl DashboardMcp {
     pub fn upsert_case(&self, case_record: CaseRecord) -> Result<String> {
         #[derive(Debug, Serialize)]
         struct UpsertCaseArgs {
+            caller_token: String,
             case_record: CaseRecord,
         }
 
-        let serialized_args = Some(serde_json::to_string(&UpsertCaseArgs { case_record })?);
+        let serialized_args = Some(serde_json::to_string(&UpsertCaseArgs {
+            caller_token: self.caller_token.clone(),
+            case_record,
+        })?);
 
         let resp = Runtime::call_contract::<String>(
             self.contract_id.clone(),
@@ -224,11 +253,13 @@ impl DashboardMcp {
     pub fn get_entity_alerts(&self, entity_id: String, limit: u32) -> Result<Vec<Alert>> {
         #[derive(Debug, Serialize)]
         struct GetEntityAlertsArgs {
+            token: String,
             entity_id: String,
             limit: u32,
         }
 
         let serialized_args = Some(serde_json::to_string(&GetEntityAlertsArgs {
+            token: self.caller_token.clone(),
             entity_id,
             limit,
         })?);
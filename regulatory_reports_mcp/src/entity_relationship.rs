@@ -47,6 +47,14 @@ pub struct EntityConnection {
     pub relationship_types: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectedEntitiesPage {
+    pub connections: Vec<EntityConnection>,
+    pub page: u32,
+    pub page_size: u32,
+    pub has_more: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InsiderStatus {
     pub entity_id: String,
@@ -117,19 +125,23 @@ impl EntityRelationshipMcp {
     }
 
     /// Get connected entities within N hops using Neo4j graph traversal
-    pub fn get_connected_entities(&self, entity_id: String, max_hops: u32) -> Result<Vec<EntityConnection>> {
+    pub fn get_connected_entities(&self, entity_id: String, max_hops: u32, page: Option<u32>, page_size: Option<u32>) -> Result<ConnectedEntitiesPage> {
         #[derive(Debug, Serialize)]
         struct GetConnectedEntitiesArgs {
             entity_id: String,
             max_hops: u32,
+            page: Option<u32>,
+            page_size: Option<u32>,
         }
 
         let serialized_args = Some(serde_json::to_string(&GetConnectedEntitiesArgs {
             entity_id,
             max_hops,
+            page,
+            page_size,
         })?);
 
-        let resp = Runtime::call_contract::<Vec<EntityConnection>>(
+        let resp = Runtime::call_contract::<ConnectedEntitiesPage>(
             self.contract_id.clone(),
             "get_connected_entities".to_string(),
             serialized_args,
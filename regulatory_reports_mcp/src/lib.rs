@@ -2,41 +2,145 @@
 mod anomaly_detection;
 mod dashboard;
 mod entity_relationship;
+mod fuzzy_match;
 mod jira;
 mod risk_scoring;
+mod slack_notifier;
+mod storage;
+mod trade_data;
+mod upsi_database;
 
 use anomaly_detection::AnomalyDetectionMcp;
-use dashboard::DashboardMcp;
+use dashboard::{Alert, DashboardMcp};
 use entity_relationship::EntityRelationshipMcp;
 use jira::JiraMcp;
 use risk_scoring::RiskScoringMcp;
+use slack_notifier::SlackMcp;
+use storage::{ReportStorage, S3CompatibleStorage, SupabaseStorage};
+use trade_data::TradeDataMcp;
+use upsi_database::UpsiDatabaseMcp;
 
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
 use weil_rs::config::Secrets;
-use weil_rs::http::{HttpClient, HttpMethod};
+
+/// Formats an epoch-milliseconds UTC timestamp as an IST (UTC+5:30) string,
+/// e.g. "2025-01-18 21:30:00 IST" - duplicated in trade_data_mcp and
+/// upsi_database_mcp since there's no shared crate between MCPs
+fn epoch_ms_to_ist(epoch_ms: u64) -> String {
+    let utc: DateTime<Utc> = match DateTime::from_timestamp_millis(epoch_ms as i64) {
+        Some(dt) => dt,
+        None => return "INVALID_TIMESTAMP".to_string(),
+    };
+    let ist_offset = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    utc.with_timezone(&ist_offset).format("%Y-%m-%d %H:%M:%S IST").to_string()
+}
 
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
 pub struct RegulatoryReportsConfig {
     pub dashboard_contract_id: String,
+    pub slack_contract_id: String,
     pub jira_contract_id: String,
     pub risk_scoring_contract_id: String,
     pub anomaly_detection_contract_id: String,
     pub entity_relationship_contract_id: String,
+    pub trade_data_contract_id: String,
+    pub upsi_database_contract_id: String,
     pub supabase_url: String,
     pub supabase_service_key: String,
     pub supabase_bucket: String,
+    /// Which ReportStorage backend to use: "supabase" (default) or "s3". Any
+    /// other value falls back to supabase.
+    pub storage_backend: String,
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_access_key: String,
+    pub s3_secret_key: String,
     pub sebi_api_endpoint: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert/upsert_case's
+    /// caller_token
+    pub dashboard_caller_token: String,
+    /// Intraday high/low vs. avg-price spread (percent) that moves a security
+    /// into SHORT_TERM ESM, consulted by evaluate_esm_transitions
+    pub esm_short_term_band_pct: u32,
+    /// Spread (percent) that escalates a security already in SHORT_TERM up to
+    /// LONG_TERM ESM
+    pub esm_long_term_band_pct: u32,
+    /// P/E threshold intended to feed evaluate_esm_transitions alongside the
+    /// price variation bands. Accepted here but not evaluated: this platform
+    /// has no fundamentals/earnings feed anywhere (trade_data_mcp only proxies
+    /// Alpha Vantage quote/volume data), so there is no P/E figure to compare
+    /// it against.
+    pub esm_pe_threshold: u32,
 }
 
 // ===== DATA STRUCTURES =====
 
+/// One buffered call to push_history, held locally until flush_history_buffer
+/// sends the batch on to the dashboard in a single push_history_batch call
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub timestamp: u64,
+    pub source_mcp: String,
+    pub method_name: String,
+    pub params: String,
+    pub result_summary: String,
+    pub status: String,
+    pub entity_id: String,
+    pub symbol: String,
+}
+
+/// Rotation metadata for a sensitive config field - never the value itself,
+/// so operators can confirm a rotation took effect without exposing the secret
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SecretVersionEntry {
+    pub field_name: String,
+    pub version: u32,
+    pub rotated_at: u64,
+}
+
+/// Verified fields gathered from anomaly detection, UPSI access logs, the entity
+/// graph, and trade data - kept alongside the generated narrative so an auditor
+/// can check every sentence of grounds_of_suspicion against a concrete source field
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SuspicionFacts {
+    pub entity_id: String,
+    pub entity_name: String,
+    pub company_symbol: String,
+    pub anomaly_count: u32,
+    pub top_anomaly_type: String,
+    pub top_anomaly_confidence: u32,
+    pub upsi_access_count: u32,
+    pub connected_entity_count: u32,
+    pub trade_volume: u64,
+    pub trade_avg_price: String,
+}
+
+/// One field's provenance in a generated report: which contract/method it was
+/// fetched from, what was fetched, when, and whether a fallback constant had to
+/// stand in for a real answer. Today fallback values (e.g. a hardcoded risk
+/// score when scan_entity_anomalies errors) are indistinguishable from real
+/// data once they're in the report body - this is what lets an auditor tell
+/// them apart.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct LineageEntry {
+    pub field: String,
+    pub source_contract: String,
+    pub method: String,
+    pub params: String,
+    pub fetch_timestamp: u64,
+    pub fallback_used: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct STRReport {
     pub str_id: String,
+    pub case_id: String,
     pub report_date: String,
     pub suspicious_entity_id: String,
     pub suspicious_entity_name: String,
@@ -45,9 +149,58 @@ pub struct STRReport {
     pub total_value: String,
     pub suspicion_reason: String,
     pub investigation_summary: String,
+    pub grounds_of_suspicion: String,
+    pub facts: SuspicionFacts,
+    /// Provenance for every fetched field in `facts` plus entity_name/company_symbol
+    /// - one entry per cross-contract read, recording whether it hit a fallback
+    pub data_lineage: Vec<LineageEntry>,
     pub recommendation: String,
     pub risk_score: u32,
     pub generated_at: u64,
+    /// generated_at formatted as IST via epoch_ms_to_ist, since generated_at is
+    /// epoch milliseconds UTC
+    pub generated_at_ist: String,
+    pub submitted: bool,
+    pub submitted_at: u64,
+    /// While true, purge_expired_reports will never remove this STR regardless of age
+    pub legal_hold: bool,
+    /// str_id of the original STR this is a supplementary filing for, set by
+    /// create_supplementary_str; empty for an original STR
+    pub supplementary_of: String,
+}
+
+/// One pending (unsubmitted) STR's standing in get_str_backlog_report, with
+/// its ageing bucket and how many days remain before it breaches
+/// str_filing_deadline_days
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct STRBacklogEntry {
+    pub str_id: String,
+    pub case_id: String,
+    pub suspicious_entity_id: String,
+    pub suspicious_entity_name: String,
+    pub company_symbol: String,
+    pub age_days: u64,
+    /// "0-7", "8-15", or ">15"
+    pub ageing_bucket: String,
+    /// assigned_to from the case's CaseRecord, or "UNASSIGNED" if the case
+    /// lookup fails or the case has no assignee
+    pub responsible_analyst: String,
+    /// Negative once the STR has passed str_filing_deadline_days
+    pub days_until_deadline: i64,
+    pub deadline_breached: bool,
+}
+
+/// Result of get_str_backlog_report: every pending STR bucketed by age, plus
+/// how many deadline alerts were actually pushed to the dashboard this call
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct STRBacklogReport {
+    pub entries: Vec<STRBacklogEntry>,
+    pub bucket_0_7_count: u32,
+    pub bucket_8_15_count: u32,
+    pub bucket_over_15_count: u32,
+    pub breached_count: u32,
+    pub alerts_sent: u32,
+    pub generated_at: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -64,6 +217,23 @@ pub struct MarketSurveillanceReport {
     pub summary: String,
 }
 
+/// End-of-day rollup across the dashboard, STR pipeline, and ESM stage
+/// moves for a single calendar date - the one artifact the compliance head
+/// reads every evening, per generate_daily_compliance_summary's doc comment
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DailyComplianceSummary {
+    pub date: String,
+    pub total_alerts: u32,
+    pub critical_alerts: u32,
+    pub open_cases: u32,
+    pub new_cases: u32,
+    pub closed_cases: u32,
+    pub strs_generated: u32,
+    pub strs_submitted: u32,
+    pub esm_stage_moves: u32,
+    pub summary: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct ComplianceScorecard {
     pub entity_id: String,
@@ -79,6 +249,76 @@ pub struct ComplianceScorecard {
     pub last_updated: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ChartPoint {
+    pub timestamp: u64,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct AlertMarker {
+    pub timestamp: u64,
+    pub label: String,
+    pub severity: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradeAnomalyReport {
+    pub report_id: String,
+    pub symbol: String,
+    pub report_period: String,
+    pub generated_at: u64,
+    pub total_volume: u64,
+    pub avg_price: String,
+    pub concentration_ratio: String,
+    pub volume_anomaly_detected: bool,
+    pub volume_ratio: String,
+    pub anomaly_score: u32,
+    pub price_series: Vec<ChartPoint>,
+    pub volume_series: Vec<ChartPoint>,
+    pub alert_markers: Vec<AlertMarker>,
+}
+
+/// One trade line in a designated-person's quarterly reconciliation. This
+/// platform has no trading pre-clearance/permission-to-trade system, so
+/// preclearance_status is always "NOT_TRACKED" rather than a real approval
+/// state - documented here rather than silently omitted, same as
+/// generate_entity_compliance_pack's pending_preclearances.json.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DpTradeLine {
+    pub trade_id: String,
+    pub trade_type: String,
+    pub quantity: u64,
+    pub price: String,
+    pub timestamp: u64,
+    pub during_closed_window: bool,
+    pub preclearance_status: String,
+    pub flagged: bool,
+}
+
+/// One designated person's trades for the quarter, plus how many of those
+/// trades landed during a closed trading window
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DpReconciliationEntry {
+    pub dp_id: String,
+    pub entity_id: String,
+    pub designation: String,
+    pub trades: Vec<DpTradeLine>,
+    pub flagged_trade_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DpTradingReport {
+    pub report_id: String,
+    pub company_symbol: String,
+    pub quarter: String,
+    pub generated_at: u64,
+    pub dp_count: u32,
+    pub total_trades: u32,
+    pub flagged_trades: u32,
+    pub entries: Vec<DpReconciliationEntry>,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct ReportResult {
     pub report_id: String,
@@ -89,6 +329,123 @@ pub struct ReportResult {
     pub risk_score: u32,
     pub success: bool,
     pub error: String,
+    pub duplicate_of: String,
+}
+
+/// A security's current stage under the Enhanced Surveillance Measure, as of
+/// the last evaluate_esm_transitions run - what generate_esm_report reads from
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct EsmSecurityStatus {
+    pub symbol: String,
+    /// "NONE", "SHORT_TERM", or "LONG_TERM"
+    pub stage: String,
+    pub since: u64,
+}
+
+/// One stage change evaluate_esm_transitions made, with the price-variation
+/// figures that justified it. A security already at its computed target stage
+/// produces no move.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct EsmStageMove {
+    pub move_id: String,
+    pub symbol: String,
+    pub from_stage: String,
+    pub to_stage: String,
+    pub variation_pct: u32,
+    pub reason: String,
+    pub evaluated_at: u64,
+}
+
+/// Outcome of a purge_expired_reports run - lets an operator confirm nothing
+/// under legal hold or within the statutory retention window was touched
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReportPurgeSummary {
+    pub purged_count: u32,
+    pub held_count: u32,
+    pub retained_count: u32,
+}
+
+/// One bucket object gc_storage found with no matching entry in the report
+/// registry it reconciled against, old enough to clear the retention window
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct OrphanedObject {
+    pub file_path: String,
+    pub updated_at: String,
+    pub size_bytes: u64,
+    pub deleted: bool,
+}
+
+/// Outcome of a gc_storage run. purge_expired_reports only ever removes the
+/// in-memory pending_strs entry for an expired STR - the uploaded object under
+/// str/ is left in the bucket forever, and a superseded/regenerated report's
+/// old object is never cleaned up either. gc_storage only reconciles the str/
+/// prefix, since pending_strs is the only report type this contract keeps an
+/// in-memory registry for; every other report type (surveillance/,
+/// compliance/, risk/, packs/, daily/, gsm/, esm/, investigation/, trade_anomaly/,
+/// dp_trading/) is generated and uploaded without ever being tracked in
+/// contract state, so there is nothing to reconcile bucket listings against
+/// for those prefixes - skipped_prefixes names them so this isn't silently
+/// read as full bucket coverage.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct GcStorageSummary {
+    pub dry_run: bool,
+    pub scanned_count: u32,
+    pub orphaned: Vec<OrphanedObject>,
+    pub reclaimed_bytes: u64,
+    pub skipped_prefixes: Vec<String>,
+}
+
+/// A dual-control approval in flight: the first call to a gated operation records
+/// this and is rejected; a second call for the same (operation, target_id) by a
+/// different caller, within the approval window, consumes it and is allowed
+/// through - so one compromised agent session can't act alone
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PendingApproval {
+    pub approval_id: String,
+    pub operation: String,
+    pub target_id: String,
+    pub requested_by: String,
+    pub requested_at: u64,
+}
+
+/// Per (report_type, financial_year) numbering counter, so numbering restarts at
+/// 1 every financial year instead of a single counter that never resets across
+/// redeploys
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReportNumberCounter {
+    pub report_type: String,
+    pub financial_year: String,
+    pub next_number: u32,
+}
+
+/// One access to a generated report's contents or download URL - STR downloads
+/// need their own trail just like UPSI record access does
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReportAccessLog {
+    pub access_id: String,
+    pub report_id: String,
+    pub accessor: String,
+    pub purpose: String,
+    pub access_timestamp: u64,
+}
+
+/// One runner-up candidate resolve_reference didn't pick, with its own confidence
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceCandidate {
+    pub value: String,
+    pub confidence: u32,
+}
+
+/// resolve_reference's result: the resolved value plus a 0-100 confidence
+/// score and up to 3 runner-up candidates, so a caller can ask a clarifying
+/// question instead of silently acting on a low-confidence fuzzy match
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceResolution {
+    pub kind: String,
+    pub query: String,
+    pub resolved_value: String,
+    pub confidence: u32,
+    pub alternatives: Vec<ReferenceCandidate>,
 }
 
 // ===== CONTEXT CACHE STRUCTURES =====
@@ -118,20 +475,128 @@ pub struct QueryContext {
 trait RegulatoryReports {
     fn new() -> Result<Self, String> where Self: Sized;
     async fn get_context(&mut self) -> QueryContext;
-    async fn generate_str(&mut self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String) -> Result<ReportResult, String>;
+    /// kind: "entity", "company", "case", or "report" - see ReferenceResolution's doc comment
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String>;
+    // idempotency_key: if set and a prior call with the same key is still
+    // within its TTL, returns that call's result instead of generating again -
+    // on top of the existing force_new dedup, which is keyed by case+entity
+    // rather than a caller-supplied token
+    async fn generate_str(&mut self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String, force_new: bool, idempotency_key: Option<String>) -> Result<ReportResult, String>;
     async fn generate_surveillance_report(&mut self, from_date: String, to_date: String, report_type: String) -> Result<ReportResult, String>;
     async fn generate_compliance_scorecard(&mut self, entity_id: String, period: String) -> Result<ReportResult, String>;
+    // Pulls dashboard stats, the critical-alert count, new/closed cases, STRs
+    // generated/submitted, and ESM stage moves for a single date into one
+    // report, then pushes the Slack daily digest with its download link. Alert
+    // dispositions, GSM changes and window closures are not included.
+    async fn generate_daily_compliance_summary(&mut self, date: String) -> Result<ReportResult, String>;
     async fn generate_entity_risk_report(&mut self, entity_id: String) -> Result<ReportResult, String>;
+    async fn generate_entity_compliance_pack(&mut self, entity_id: String, period: String) -> Result<ReportResult, String>;
     async fn generate_gsm_report(&mut self, report_date: String) -> Result<ReportResult, String>;
     async fn generate_esm_report(&mut self, report_date: String) -> Result<ReportResult, String>;
+    /// Applies the price-variation-band rules to each symbol and records any
+    /// stage move; run this ahead of generate_esm_report so the report reflects
+    /// current state instead of its own snapshot
+    async fn evaluate_esm_transitions(&mut self, symbols: String) -> Result<Vec<EsmStageMove>, String>;
+    /// Every stage move evaluate_esm_transitions has recorded for a symbol, oldest
+    /// first - the source get_symbol_timeline (dashboard_webserver) merges in
+    fn get_esm_stage_history(&self, symbol: String) -> Vec<EsmStageMove>;
     async fn get_pending_strs(&mut self, limit: u32) -> Result<Vec<STRReport>, String>;
-    async fn submit_str(&mut self, str_id: String) -> Result<ReportResult, String>;
+    /// Every unsubmitted STR bucketed by age (0-7, 8-15, >15 days) with the
+    /// responsible analyst, pushing a dashboard alert for any STR within
+    /// str_filing_deadline_alert_window_days of breaching the statutory
+    /// filing deadline
+    async fn get_str_backlog_report(&mut self) -> Result<STRBacklogReport, String>;
+    async fn get_reports_for_case(&mut self, case_id: String) -> Result<Vec<STRReport>, String>;
+    async fn submit_str(&mut self, str_id: String, requested_by: String) -> Result<ReportResult, String>;
+    // Files a new STR referencing original_str_id via supplementary_of, so the
+    // filing chain is visible on both reports and get_case_activity picks up
+    // the new filing under the same case automatically
+    async fn create_supplementary_str(&mut self, original_str_id: String, additional_findings: String) -> Result<ReportResult, String>;
+    async fn set_legal_hold(&mut self, report_id: String, enabled: bool) -> Result<STRReport, String>;
+    async fn purge_expired_reports(&mut self, requested_by: String) -> Result<ReportPurgeSummary, String>;
+    // Lists the str/ prefix in the configured storage backend, reconciles it
+    // against pending_strs, and reports (or, outside dry_run and past dual
+    // control, deletes) objects with no matching STR older than str_retention_days
+    // - see GcStorageSummary's doc comment for why only the str/ prefix is covered
+    async fn gc_storage(&mut self, dry_run: bool, requested_by: String) -> Result<GcStorageSummary, String>;
+    fn list_pending_approvals(&self) -> Vec<PendingApproval>;
     async fn generate_investigation_report(&mut self, case_id: String, include_evidence: bool) -> Result<ReportResult, String>;
     async fn get_report_url(&mut self, report_id: String) -> Result<ReportResult, String>;
+    async fn record_report_access(&mut self, report_id: String, accessor: String, purpose: String) -> Result<ReportAccessLog, String>;
+    async fn get_report_access_log(&mut self, report_id: String) -> Result<Vec<ReportAccessLog>, String>;
+    async fn get_next_report_number_preview(&self, report_type: String) -> Result<String, String>;
+    async fn generate_trade_anomaly_report(&mut self, symbol: String, from_date: String, to_date: String) -> Result<ReportResult, String>;
+    async fn generate_dp_trading_report(&mut self, company_symbol: String, quarter: String) -> Result<ReportResult, String>;
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String>;
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry>;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
+    /// Injects the current wall-clock reading; every report timestamp in this
+    /// contract is derived from this rather than from a real clock - see
+    /// ClockState's doc comment
+    fn set_clock(&mut self, timestamp: u64, date: String) -> ClockState;
+    fn get_clock(&self) -> ClockState;
+    /// Drains the buffer push_history has been accumulating and sends it to the
+    /// dashboard as a single push_history_batch call, returning how many
+    /// entries were actually flushed (0 if the dashboard call failed - they
+    /// stay queued for the next flush)
+    async fn flush_history(&mut self) -> Result<u32, String>;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely carry out
+/// maintenance without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
+/// This contract has no access to a real wall clock, so get_current_timestamp
+/// and get_current_date read from here instead - defaults to a fixed date at
+/// construction time until an operator calls set_clock with the real time
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ClockState {
+    pub timestamp: u64,
+    pub date: String,
+}
+
+/// One cached mutating-call result, keyed by the caller-supplied
+/// idempotency_key, so an agent's retried call returns the original result
+/// instead of generating a duplicate report. Expires by clock.timestamp now
+/// that this contract has an injected wall clock, rather than a tick counter.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CachedIdempotentResult {
+    pub key: String,
+    pub value: String,
+    pub expires_at: u64,
+}
+
+/// Generous window since an agent's retry storm can be spread over minutes,
+/// not just the current call
+const IDEMPOTENCY_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct IdempotencyCache {
+    entries: Vec<CachedIdempotentResult>,
+}
+
+impl IdempotencyCache {
+    /// Look up a prior result for `key`, if still within its TTL as of `now`
+    fn get(&mut self, key: &str, now: u64) -> Option<String> {
+        self.entries.retain(|e| e.expires_at > now);
+        self.entries.iter().find(|e| e.key == key).map(|e| e.value.clone())
+    }
+
+    /// Remember `value` (a serialized result) under `key` until now + IDEMPOTENCY_TTL_MS
+    fn put(&mut self, key: &str, value: String, now: u64) {
+        self.entries.retain(|e| e.key != key);
+        self.entries.push(CachedIdempotentResult { key: key.to_string(), value, expires_at: now + IDEMPOTENCY_TTL_MS });
+    }
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
@@ -140,74 +605,235 @@ pub struct RegulatoryReportsContractState {
     query_cache: QueryContext,
     pending_strs: Vec<STRReport>,
     report_counter: u32,
+    report_access_log: Vec<ReportAccessLog>,
+    access_log_counter: u32,
+    report_number_counters: Vec<ReportNumberCounter>,
+    secret_versions: Vec<SecretVersionEntry>,
+    maintenance: MaintenanceStatus,
+    /// Statutory minimum age (in days) a submitted STR must reach before
+    /// purge_expired_reports will consider removing it
+    str_retention_days: u64,
+    /// Days from generated_at an STR has before it breaches the statutory
+    /// filing deadline, consulted by get_str_backlog_report
+    str_filing_deadline_days: u64,
+    /// How close (in days) to str_filing_deadline_days an STR must be before
+    /// get_str_backlog_report pushes a dashboard alert for it
+    str_filing_deadline_alert_window_days: u64,
+    pending_approvals: Vec<PendingApproval>,
+    approval_counter: u32,
+    esm_security_status: Vec<EsmSecurityStatus>,
+    esm_stage_history: Vec<EsmStageMove>,
+    esm_move_counter: u32,
+    /// Entries queued by push_history, awaiting flush_history_buffer - see
+    /// push_history's doc comment
+    history_buffer: Vec<HistoryEntry>,
+    /// Injected wall-clock reading - see set_clock's doc comment for why this
+    /// contract can't just read a real clock itself
+    clock: ClockState,
+    /// Keyed by the idempotency_key callers pass to generate_str and similar -
+    /// see CachedIdempotentResult's doc comment
+    idempotency_cache: IdempotencyCache,
 }
 impl RegulatoryReportsContractState {
-    // ===== SUPABASE STORAGE METHODS =====
+    /// Reject mutating calls while maintenance mode is on, so in-flight
+    /// maintenance can't race a concurrent tool call into a half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
 
-    fn upload_to_supabase(&self, file_path: &str, content: &str) -> Result<String, String> {
-        let config = self.secrets.config();
-        
-        let url = format!(
-            "{}/storage/v1/object/{}/{}",
-            config.supabase_url, config.supabase_bucket, file_path
-        );
-        
-        let mut headers = HashMap::new();
-        headers.insert("apikey".to_string(), config.supabase_service_key.clone());
-        headers.insert("Authorization".to_string(), format!("Bearer {}", config.supabase_service_key));
-        headers.insert("Content-Type".to_string(), "application/json".to_string());
-        headers.insert("x-upsert".to_string(), "true".to_string());
-        
-        match HttpClient::request(&url, HttpMethod::Post)
-            .headers(headers)
-            .body(content.to_string())
-            .send() 
+    /// Dual-control gate for destructive/high-risk operations. The first call
+    /// records a pending approval and is rejected; a second call for the same
+    /// (operation, target_id) from a different caller, within the window, consumes
+    /// the pending approval and lets the operation proceed. Expired pending
+    /// approvals are swept on every call.
+    fn dual_control_check(&mut self, operation: &str, target_id: &str, caller: &str) -> Result<(), String> {
+        const APPROVAL_WINDOW_MS: u64 = 60 * 60 * 1000;
+        let now = self.get_current_timestamp();
+        self.pending_approvals.retain(|a| now.saturating_sub(a.requested_at) < APPROVAL_WINDOW_MS);
+
+        if let Some(pos) = self.pending_approvals.iter()
+            .position(|a| a.operation == operation && a.target_id == target_id)
         {
-            Ok(response) => {
-                let resp_text = response.text();
-                let debug_resp = if resp_text.len() > 80 {
-                    format!("{}...", &resp_text[..80])
-                } else {
-                    resp_text.clone()
-                };
-                
-                if resp_text.contains("\"error\"") || resp_text.contains("\"statusCode\"") {
-                    Ok(format!("ERR|{}|{}", debug_resp.replace("\"", "'"), file_path))
-                } else if resp_text.is_empty() {
-                    Ok(format!("EMPTY|{}", file_path))
-                } else {
-                    Ok(format!("OK|{}|{}", debug_resp.replace("\"", "'"), file_path))
-                }
-            },
-            Err(e) => {
-                Ok(format!("FAIL|{:?}|{}", e, file_path))
+            let pending = self.pending_approvals[pos].clone();
+            if pending.requested_by != caller {
+                self.pending_approvals.remove(pos);
+                return Ok(());
             }
+            return Err(format!(
+                "{} on {} is already pending approval from {}; a different caller must confirm within {} minutes",
+                operation, target_id, pending.requested_by, APPROVAL_WINDOW_MS / 60_000
+            ));
+        }
+
+        self.approval_counter += 1;
+        self.pending_approvals.push(PendingApproval {
+            approval_id: format!("APR-{:04}", self.approval_counter),
+            operation: operation.to_string(),
+            target_id: target_id.to_string(),
+            requested_by: caller.to_string(),
+            requested_at: now,
+        });
+
+        Err(format!(
+            "{} on {} requires a second approval from a caller other than {} within {} minutes; call again as that caller to execute",
+            operation, target_id, caller, APPROVAL_WINDOW_MS / 60_000
+        ))
+    }
+
+    // ===== REPORT STORAGE =====
+
+    /// Builds the configured ReportStorage backend fresh for this call - there's
+    /// no per-request state to keep between calls, so nothing is cached on self.
+    fn build_storage(&self) -> Box<dyn ReportStorage> {
+        let config = self.secrets.config();
+        match config.storage_backend.as_str() {
+            "s3" => Box::new(S3CompatibleStorage {
+                endpoint: config.s3_endpoint.clone(),
+                bucket: config.s3_bucket.clone(),
+                access_key: config.s3_access_key.clone(),
+                secret_key: config.s3_secret_key.clone(),
+            }),
+            _ => Box::new(SupabaseStorage {
+                url: config.supabase_url.clone(),
+                service_key: config.supabase_service_key.clone(),
+                bucket: config.supabase_bucket.clone(),
+            }),
         }
     }
 
+    fn upload_report(&self, file_path: &str, content: &str) -> Result<String, String> {
+        self.build_storage().upload(file_path, content)
+    }
+
     fn get_public_url(&self, file_path: &str) -> String {
-        let config = self.secrets.config();
-        format!(
-            "{}/storage/v1/object/public/{}/{}",
-            config.supabase_url, config.supabase_bucket, file_path
-        )
+        self.build_storage().get_public_url(file_path)
     }
 
     #[allow(dead_code)]
-    fn get_signed_url(&self, file_path: &str, _expires_in: u64) -> Result<String, String> {
-        Ok(self.get_public_url(file_path))
+    fn get_signed_url(&self, file_path: &str, expires_in: u64) -> Result<String, String> {
+        self.build_storage().get_signed_url(file_path, expires_in)
     }
 
     fn get_current_timestamp(&self) -> u64 {
-        1737225600000
+        self.clock.timestamp
     }
     fn get_current_date(&self) -> String {
-        "2026-01-13".to_string()
+        self.clock.date.clone()
+    }
+
+    /// Indian financial year (April-March) derived from get_current_date, e.g.
+    /// "2025-26" for any date from 2025-04-01 through 2026-03-31
+    fn current_financial_year(&self) -> String {
+        let date = self.get_current_date();
+        let parts: Vec<&str> = date.split('-').collect();
+        let (year, month) = match (
+            parts.first().and_then(|y| y.parse::<u32>().ok()),
+            parts.get(1).and_then(|m| m.parse::<u32>().ok()),
+        ) {
+            (Some(y), Some(m)) => (y, m),
+            _ => (2026, 1),
+        };
+        let fy_start = if month >= 4 { year } else { year.saturating_sub(1) };
+        format!("{:04}-{:02}", fy_start, (fy_start + 1) % 100)
+    }
+
+    /// Next number for (report_type, financial_year) without consuming it
+    fn peek_report_number(&self, report_type: &str, financial_year: &str) -> u32 {
+        self.report_number_counters.iter()
+            .find(|c| c.report_type == report_type && c.financial_year == financial_year)
+            .map(|c| c.next_number)
+            .unwrap_or(1)
+    }
+
+    /// Consumes and returns the next number for (report_type, financial_year),
+    /// upserting the counter so numbering restarts at 1 on financial-year rollover
+    fn next_report_number(&mut self, report_type: &str, financial_year: &str) -> u32 {
+        if let Some(counter) = self.report_number_counters.iter_mut()
+            .find(|c| c.report_type == report_type && c.financial_year == financial_year)
+        {
+            let number = counter.next_number;
+            counter.next_number += 1;
+            return number;
+        }
+        self.report_number_counters.push(ReportNumberCounter {
+            report_type: report_type.to_string(),
+            financial_year: financial_year.to_string(),
+            next_number: 2,
+        });
+        1
     }
 
     fn generate_report_id(&mut self, prefix: &str) -> String {
-        self.report_counter += 1;
-        format!("{}-2026-{:04}", prefix, self.report_counter)
+        let fy = self.current_financial_year();
+        let number = self.next_report_number(prefix, &fy);
+        format!("{}-{}-{:04}", prefix, fy, number)
+    }
+
+    /// Records one cross-contract fetch's provenance for a report's data_lineage
+    /// section - see LineageEntry's own doc comment for why this matters
+    fn record_lineage(lineage: &mut Vec<LineageEntry>, field: &str, source_contract: &str, method: &str, params: &str, fetch_timestamp: u64, fallback_used: bool) {
+        lineage.push(LineageEntry {
+            field: field.to_string(),
+            source_contract: source_contract.to_string(),
+            method: method.to_string(),
+            params: params.to_string(),
+            fetch_timestamp,
+            fallback_used,
+        });
+    }
+
+    // ===== NARRATIVE GENERATION =====
+
+    /// Build the "grounds of suspicion" paragraph by slot-filling a fixed sentence
+    /// template with verified fields from `facts`, so the filing text is traceable
+    /// to concrete data rather than free-form prose. Sentences whose underlying
+    /// fact is empty/zero are omitted rather than filled with a placeholder.
+    fn build_grounds_of_suspicion(&self, facts: &SuspicionFacts, suspicious_activity_type: &str) -> String {
+        let mut sentences = Vec::new();
+
+        sentences.push(format!(
+            "{} ({}) is flagged for suspected {}.",
+            facts.entity_name, facts.entity_id, suspicious_activity_type
+        ));
+
+        if facts.anomaly_count > 0 {
+            sentences.push(format!(
+                "Automated surveillance recorded {} anomal{} on {}, the most significant being {} at {}% confidence.",
+                facts.anomaly_count,
+                if facts.anomaly_count == 1 { "y" } else { "ies" },
+                facts.company_symbol,
+                facts.top_anomaly_type,
+                facts.top_anomaly_confidence
+            ));
+        }
+
+        if facts.upsi_access_count > 0 {
+            sentences.push(format!(
+                "The entity accessed unpublished price sensitive information {} time(s) in the period under review.",
+                facts.upsi_access_count
+            ));
+        }
+
+        if facts.connected_entity_count > 0 {
+            sentences.push(format!(
+                "Graph analysis identified {} connected entit{} that may be relevant to this activity.",
+                facts.connected_entity_count,
+                if facts.connected_entity_count == 1 { "y" } else { "ies" }
+            ));
+        }
+
+        if facts.trade_volume > 0 {
+            sentences.push(format!(
+                "Trading activity in {} totalled {} units at an average price of {} over the review period.",
+                facts.company_symbol, facts.trade_volume, facts.trade_avg_price
+            ));
+        }
+
+        sentences.join(" ")
     }
 
     // ===== CACHE METHODS =====
@@ -251,87 +877,110 @@ impl RegulatoryReportsContractState {
         if partial.is_empty() {
             return self.query_cache.last_entity_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_entity_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_entity_id.clone();
-        }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.entity_id.is_empty() && query.entity_id.to_lowercase().contains(&partial_lower) {
-                return query.entity_id.clone();
-            }
-        }
-        
-        partial.to_string()
+
+        let candidates = std::iter::once(self.query_cache.last_entity_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
     }
 
     fn resolve_case(&self, partial: &str) -> String {
         if partial.is_empty() {
             return self.query_cache.last_case_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_case_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_case_id.clone();
-        }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.case_id.is_empty() && query.case_id.to_lowercase().contains(&partial_lower) {
-                return query.case_id.clone();
-            }
+
+        let candidates = std::iter::once(self.query_cache.last_case_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.case_id.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
+    }
+
+    fn resolve_company(&self, partial: &str) -> String {
+        if partial.is_empty() {
+            return self.query_cache.last_company_symbol.clone();
         }
-        
-        partial.to_string()
+
+        let candidates = std::iter::once(self.query_cache.last_company_symbol.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
     }
 
     fn resolve_report(&self, partial: &str) -> String {
         if partial.is_empty() {
             return self.query_cache.last_report_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_report_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_report_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_report_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.report_id.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
+    }
+
+    /// How many buffered entries trigger an automatic flush_history_buffer
+    const HISTORY_BATCH_SIZE: usize = 20;
+    /// Hard cap on the buffer so a prolonged dashboard outage can't grow state
+    /// without bound; once past this, oldest entries are dropped to make room
+    /// rather than blocking or erroring the calling method
+    const HISTORY_BUFFER_MAX: usize = 200;
+
+    /// Queues a history entry locally instead of calling the dashboard
+    /// synchronously on every method - that used to double the latency of every
+    /// tool call. flush_history_buffer drains the buffer in one batched call
+    /// once it reaches HISTORY_BATCH_SIZE, or on an explicit flush_history call.
+    fn push_history(&mut self, method_name: &str, params: &str, result_summary: &str, status: &str, entity_id: &str, symbol: &str) {
+        self.report_counter += 1;
+        self.history_buffer.push(HistoryEntry {
+            id: format!("HIST-reports-{}-{}", method_name, self.report_counter),
+            timestamp: 0u64,
+            source_mcp: "regulatory_reports".to_string(),
+            method_name: method_name.to_string(),
+            params: params.to_string(),
+            result_summary: result_summary.to_string(),
+            status: status.to_string(),
+            entity_id: entity_id.to_string(),
+            symbol: symbol.to_string(),
+        });
+
+        if self.history_buffer.len() > Self::HISTORY_BUFFER_MAX {
+            let overflow = self.history_buffer.len() - Self::HISTORY_BUFFER_MAX;
+            self.history_buffer.drain(0..overflow);
         }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.report_id.is_empty() && query.report_id.to_lowercase().contains(&partial_lower) {
-                return query.report_id.clone();
-            }
+
+        if self.history_buffer.len() >= Self::HISTORY_BATCH_SIZE {
+            self.flush_history_buffer();
         }
-        
-        partial.to_string()
     }
 
-    fn push_history(&self, method_name: &str, params: &str, result_summary: &str, status: &str, entity_id: &str, symbol: &str) {
+    /// Sends every buffered entry to the dashboard in one push_history_batch
+    /// call. Loss-safe: entries are only cleared from the buffer once the call
+    /// actually succeeds, so a down or misconfigured dashboard leaves them
+    /// queued for the next flush instead of silently dropping them.
+    fn flush_history_buffer(&mut self) {
         let config = self.secrets.config();
-        if config.dashboard_contract_id.is_empty() {
+        if config.dashboard_contract_id.is_empty() || self.history_buffer.is_empty() {
             return;
         }
 
-        let entry = serde_json::json!({
-            "id": format!("HIST-reports-{}-{}", method_name, self.report_counter),
-            "timestamp": 0u64,
-            "source_mcp": "regulatory_reports",
-            "method_name": method_name,
-            "params": params,
-            "result_summary": result_summary,
-            "status": status,
-            "entity_id": entity_id,
-            "symbol": symbol
-        });
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "entries": self.history_buffer }).to_string();
 
-        let args = serde_json::json!({ "entry": entry }).to_string();
-        
-        let _ = weil_rs::runtime::Runtime::call_contract::<String>(
+        let sent = weil_rs::runtime::Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
-            "push_history".to_string(),
+            "push_history_batch".to_string(),
             Some(args),
         );
+
+        if sent.is_ok() {
+            self.history_buffer.clear();
+        }
     }
 }
 
@@ -382,6 +1031,25 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             },
             pending_strs: Vec::new(),
             report_counter: 10,
+            report_access_log: Vec::new(),
+            access_log_counter: 0,
+            report_number_counters: Vec::new(),
+            secret_versions: Vec::new(),
+            maintenance: MaintenanceStatus::default(),
+            str_retention_days: 2555, // 7 years - standard SEBI/PMLA record retention period
+            str_filing_deadline_days: 7, // PMLA STRs must be filed within 7 working days of the suspicion being established
+            str_filing_deadline_alert_window_days: 2,
+            pending_approvals: Vec::new(),
+            approval_counter: 0,
+            esm_security_status: Vec::new(),
+            esm_stage_history: Vec::new(),
+            esm_move_counter: 0,
+            history_buffer: Vec::new(),
+            clock: ClockState {
+                timestamp: 1737225600000,
+                date: "2026-01-13".to_string(),
+            },
+            idempotency_cache: IdempotencyCache::default(),
         })
     }
 
@@ -390,66 +1058,211 @@ impl RegulatoryReports for RegulatoryReportsContractState {
         self.query_cache.clone()
     }
 
+    #[query]
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String> {
+        if partial.is_empty() {
+            return Err("partial must not be empty".to_string());
+        }
+
+        let candidates: Vec<&str> = match kind.as_str() {
+            "entity" => std::iter::once(self.query_cache.last_entity_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()))
+                .collect(),
+            "company" => std::iter::once(self.query_cache.last_company_symbol.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()))
+                .collect(),
+            "case" => std::iter::once(self.query_cache.last_case_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.case_id.as_str()))
+                .collect(),
+            "report" => std::iter::once(self.query_cache.last_report_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.report_id.as_str()))
+                .collect(),
+            other => return Err(format!("Unknown reference kind '{}' - expected entity, company, case, or report", other)),
+        };
+
+        let mut ranked = fuzzy_match::resolve_ranked(&partial, candidates.into_iter(), &fuzzy_match::DEFAULT_STRATEGIES, 4).into_iter();
+        let (resolved_value, confidence) = match ranked.next() {
+            Some(m) => (m.value, (m.score * 100.0).round() as u32),
+            None => (partial.clone(), 0),
+        };
+        let alternatives = ranked.map(|m| ReferenceCandidate { value: m.value, confidence: (m.score * 100.0).round() as u32 }).collect();
+
+        Ok(ReferenceResolution { kind, query: partial, resolved_value, confidence, alternatives })
+    }
+
     #[mutate]
-    async fn generate_str(&mut self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String) -> Result<ReportResult, String> {
+    async fn generate_str(&mut self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String, force_new: bool, idempotency_key: Option<String>) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+
+        if let Some(ref key) = idempotency_key {
+            let now = self.get_current_timestamp();
+            if let Some(cached) = self.idempotency_cache.get(key, now) {
+                return serde_json::from_str(&cached).map_err(|e| format!("Failed to replay cached generate_str result: {}", e));
+            }
+        }
+
+        let result = self.generate_str_inner(case_id, entity_id, suspicious_activity_type, suspicion_reason, force_new).await;
+
+        if let (Some(ref key), Ok(ref value)) = (&idempotency_key, &result) {
+            if let Ok(serialized) = serde_json::to_string(value) {
+                let now = self.get_current_timestamp();
+                self.idempotency_cache.put(key, serialized, now);
+            }
+        }
+
+        result
+    }
+
+    async fn generate_str_inner(&mut self, case_id: String, entity_id: String, suspicious_activity_type: String, suspicion_reason: String, force_new: bool) -> Result<ReportResult, String> {
         let resolved_case = self.resolve_case(&case_id);
         let resolved_entity = self.resolve_entity(&entity_id);
-        
+
+        if !force_new {
+            if let Some(existing) = self.pending_strs.iter()
+                .find(|s| s.case_id == resolved_case && s.suspicious_entity_id == resolved_entity)
+            {
+                let file_path = format!("str/{}.json", existing.str_id);
+                return Ok(ReportResult {
+                    report_id: existing.str_id.clone(),
+                    report_type: "STR".to_string(),
+                    storage_path: file_path.clone(),
+                    download_url: self.get_public_url(&file_path),
+                    expires_at: 0,
+                    risk_score: existing.risk_score,
+                    success: true,
+                    error: "".to_string(),
+                    duplicate_of: existing.str_id.clone(),
+                });
+            }
+        }
+
         let str_id = self.generate_report_id("STR");
         let report_date = self.get_current_date();
         let timestamp = self.get_current_timestamp();
         let config = self.secrets.config();
         
+        let mut data_lineage: Vec<LineageEntry> = Vec::new();
+
         let entity_name = {
             let entity_mcp = EntityRelationshipMcp::new(config.entity_relationship_contract_id.clone());
-            match entity_mcp.get_entity(resolved_entity.clone()) {
+            let result = entity_mcp.get_entity(resolved_entity.clone());
+            Self::record_lineage(&mut data_lineage, "entity_name", &config.entity_relationship_contract_id, "get_entity",
+                &format!("entity_id={}", resolved_entity), timestamp, result.is_err());
+            match result {
                 Ok(entity) => entity.name,
                 Err(_) => format!("Entity {}", resolved_entity),
             }
         };
-        
-        let (investigation_summary, risk_score) = {
+
+        let company_symbol = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            let result = dashboard_mcp.get_case_details(resolved_case.clone());
+            Self::record_lineage(&mut data_lineage, "company_symbol", &config.dashboard_contract_id, "get_case_details",
+                &format!("case_id={}", resolved_case), timestamp, result.is_err());
+            match result {
+                Ok(case_record) => case_record.symbol,
+                Err(_) => String::new(),
+            }
+        };
+
+        let (investigation_summary, anomaly_count, top_anomaly_type, top_anomaly_confidence, risk_score) = {
             let anomaly_mcp = AnomalyDetectionMcp::new(config.anomaly_detection_contract_id.clone());
-            match anomaly_mcp.scan_entity_anomalies(resolved_entity.clone()) {
+            let result = anomaly_mcp.scan_entity_anomalies(resolved_entity.clone());
+            Self::record_lineage(&mut data_lineage, "anomaly_count/top_anomaly_type/top_anomaly_confidence/risk_score", &config.anomaly_detection_contract_id,
+                "scan_entity_anomalies", &format!("entity_id={}", resolved_entity), timestamp, result.is_err());
+            match result {
                 Ok(anomalies) => {
                     if anomalies.is_empty() {
-                        ("No anomalies detected for this entity.".to_string(), 50u32)
+                        ("No anomalies detected for this entity.".to_string(), 0u32, String::new(), 0u32, 50u32)
                     } else {
                         let summary = anomalies.iter()
                             .map(|a| format!("{}: {}", a.anomaly_type, a.details))
                             .collect::<Vec<_>>()
                             .join("; ");
-                        let max_score = anomalies.iter().map(|a| a.confidence_score).max().unwrap_or(50);
-                        (summary, max_score)
+                        let top = anomalies.iter().max_by_key(|a| a.confidence_score).unwrap();
+                        (summary, anomalies.len() as u32, top.anomaly_type.clone(), top.confidence_score, top.confidence_score)
                     }
                 },
                 Err(_) => (
                     "Detailed investigation reveals suspicious trading patterns before corporate announcements.".to_string(),
-                    85u32
+                    0u32, String::new(), 0u32, 85u32
                 ),
             }
         };
-        
-        let str_report = STRReport {
-            str_id: str_id.clone(),
-            report_date: report_date.clone(),
-            suspicious_entity_id: resolved_entity.clone(),
-            suspicious_entity_name: entity_name,
-            suspicious_activity_type: suspicious_activity_type.clone(),
-            transaction_details: format!("Case {} investigation details", resolved_case),
-            total_value: "₹50,00,000".to_string(),
+
+        let upsi_access_count = {
+            let upsi_mcp = UpsiDatabaseMcp::new(config.upsi_database_contract_id.clone());
+            let result = upsi_mcp.get_access_by_person(resolved_entity.clone(), 90);
+            Self::record_lineage(&mut data_lineage, "upsi_access_count", &config.upsi_database_contract_id, "get_access_by_person",
+                &format!("accessor_entity_id={}, days_back=90", resolved_entity), timestamp, result.is_err());
+            result.map(|logs| logs.len() as u32).unwrap_or(0)
+        };
+
+        let connected_entity_count = {
+            let entity_mcp = EntityRelationshipMcp::new(config.entity_relationship_contract_id.clone());
+            let result = entity_mcp.get_connected_entities(resolved_entity.clone(), 2, None, None);
+            Self::record_lineage(&mut data_lineage, "connected_entity_count", &config.entity_relationship_contract_id, "get_connected_entities",
+                &format!("entity_id={}, max_hops=2", resolved_entity), timestamp, result.is_err());
+            result.map(|page| page.connections.len() as u32).unwrap_or(0)
+        };
+
+        let (trade_volume, trade_avg_price) = if company_symbol.is_empty() {
+            (0u64, String::new())
+        } else {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            let result = trade_data_mcp.analyze_volume(company_symbol.clone());
+            Self::record_lineage(&mut data_lineage, "trade_volume/trade_avg_price", &config.trade_data_contract_id, "analyze_volume",
+                &format!("symbol={}", company_symbol), timestamp, result.is_err());
+            match result {
+                Ok(analysis) => (analysis.total_volume, analysis.avg_price),
+                Err(_) => (0u64, String::new()),
+            }
+        };
+
+        let facts = SuspicionFacts {
+            entity_id: resolved_entity.clone(),
+            entity_name: entity_name.clone(),
+            company_symbol: company_symbol.clone(),
+            anomaly_count,
+            top_anomaly_type,
+            top_anomaly_confidence,
+            upsi_access_count,
+            connected_entity_count,
+            trade_volume,
+            trade_avg_price,
+        };
+
+        let grounds_of_suspicion = self.build_grounds_of_suspicion(&facts, &suspicious_activity_type);
+
+        let str_report = STRReport {
+            str_id: str_id.clone(),
+            case_id: resolved_case.clone(),
+            report_date: report_date.clone(),
+            suspicious_entity_id: resolved_entity.clone(),
+            suspicious_entity_name: entity_name,
+            suspicious_activity_type: suspicious_activity_type.clone(),
+            transaction_details: format!("Case {} investigation details", resolved_case),
+            total_value: "₹50,00,000".to_string(),
             suspicion_reason: suspicion_reason.clone(),
             investigation_summary,
+            grounds_of_suspicion,
+            facts,
+            data_lineage,
             recommendation: if risk_score >= 70 { "ESCALATE TO SEBI".to_string() } else { "MONITOR".to_string() },
             risk_score,
             generated_at: timestamp,
+            generated_at_ist: epoch_ms_to_ist(timestamp),
+            submitted: false,
+            submitted_at: 0,
+            legal_hold: false,
+            supplementary_of: "".to_string(),
         };
-        
+
         let content = serde_json::to_string_pretty(&str_report)
             .map_err(|e| format!("Failed to serialize STR: {}", e))?;
         
         let file_path = format!("str/{}.json", str_id);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -476,17 +1289,19 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn generate_surveillance_report(&mut self, from_date: String, to_date: String, report_type: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let report_id = self.generate_report_id("SURV");
         let timestamp = self.get_current_timestamp();
         let config = self.secrets.config();
         
         let (total_alerts, investigations_opened, investigations_closed, open_cases) = {
-            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone());
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
             match dashboard_mcp.get_stats() {
                 Ok(stats) => (
                     stats.total_alerts_today,
@@ -499,7 +1314,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
         };
         
         let (critical_alerts, manipulation_cases, insider_cases) = {
-            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone());
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
             match dashboard_mcp.get_live_alerts("CRITICAL".to_string(), 100) {
                 Ok(alerts) => {
                     let critical = alerts.len() as u32;
@@ -529,7 +1344,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             .map_err(|e| format!("Failed to serialize report: {}", e))?;
         
         let file_path = format!("surveillance/{}_{}.json", report_type.to_lowercase(), from_date);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -545,11 +1360,13 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 0,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn generate_compliance_scorecard(&mut self, entity_id: String, period: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
         let report_id = self.generate_report_id("COMP");
         let timestamp = self.get_current_timestamp();
@@ -572,7 +1389,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             .map_err(|e| format!("Failed to serialize scorecard: {}", e))?;
         
         let file_path = format!("compliance/{}_{}.json", resolved_entity, period);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -588,11 +1405,104 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 45,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
+        })
+    }
+
+    /// The one artifact the compliance head reads every evening - pulls
+    /// dashboard stats, the critical-alert count, case counts, STR filing
+    /// activity, and ESM stage moves for a single date into one report, then
+    /// triggers the Slack daily digest with its download link. Alert
+    /// dispositions and GSM changes are not included. Window
+    /// closures aren't included: upsi_database_mcp only exposes per-symbol
+    /// trading-window lookups, not a platform-wide list for a given date.
+    #[mutate]
+    async fn generate_daily_compliance_summary(&mut self, date: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let report_id = self.generate_report_id("DAILY");
+        let timestamp = self.get_current_timestamp();
+        let config = self.secrets.config();
+
+        let (total_alerts, critical_alerts, open_cases) = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            match dashboard_mcp.get_stats() {
+                Ok(stats) => (stats.total_alerts_today, 0, stats.open_cases),
+                Err(_) => (0, 0, 0),
+            }
+        };
+
+        let critical_alerts = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            match dashboard_mcp.get_live_alerts("CRITICAL".to_string(), 200) {
+                Ok(alerts) => alerts.len() as u32,
+                Err(_) => critical_alerts,
+            }
+        };
+
+        let (new_cases, closed_cases) = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            let opened = dashboard_mcp.get_cases_by_status("OPEN".to_string(), 500).map(|c| c.len() as u32).unwrap_or(0);
+            let closed = dashboard_mcp.get_cases_by_status("CLOSED".to_string(), 500).map(|c| c.len() as u32).unwrap_or(0);
+            (opened, closed)
+        };
+
+        let strs_generated = self.pending_strs.iter().filter(|s| s.report_date == date).count() as u32;
+        let strs_submitted = self.pending_strs.iter().filter(|s| s.report_date == date && s.submitted).count() as u32;
+
+        let esm_stage_moves = self.esm_stage_history.iter()
+            .filter(|m| epoch_ms_to_ist(m.evaluated_at).starts_with(&date))
+            .count() as u32;
+
+        let summary_line = format!(
+            "{}: {} alerts ({} critical), {} open cases ({} new, {} closed), {} STRs generated ({} submitted), {} ESM stage moves.",
+            date, total_alerts, critical_alerts, open_cases, new_cases, closed_cases, strs_generated, strs_submitted, esm_stage_moves,
+        );
+
+        let summary = DailyComplianceSummary {
+            date: date.clone(),
+            total_alerts,
+            critical_alerts,
+            open_cases,
+            new_cases,
+            closed_cases,
+            strs_generated,
+            strs_submitted,
+            esm_stage_moves,
+            summary: summary_line,
+        };
+
+        let content = serde_json::to_string_pretty(&summary)
+            .map_err(|e| format!("Failed to serialize daily compliance summary: {}", e))?;
+
+        let file_path = format!("daily/{}.json", date);
+        let _ = self.upload_report(&file_path, &content)?;
+
+        let download_url = self.get_public_url(&file_path);
+
+        if !config.slack_contract_id.is_empty() {
+            let slack_mcp = SlackMcp::new(config.slack_contract_id.clone());
+            let _ = slack_mcp.send_daily_summary(date.clone(), total_alerts, critical_alerts, open_cases, new_cases);
+        }
+
+        self.update_cache("generate_daily_compliance_summary", "", "", "", &report_id,
+            &format!("Generated daily compliance summary for {}", date));
+
+        Ok(ReportResult {
+            report_id,
+            report_type: "DAILY_COMPLIANCE_SUMMARY".to_string(),
+            storage_path: file_path,
+            download_url,
+            expires_at: timestamp + 3600000,
+            risk_score: 0,
+            success: true,
+            error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn generate_entity_risk_report(&mut self, entity_id: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let resolved_entity = self.resolve_entity(&entity_id);
         let report_id = self.generate_report_id("RISK");
         let timestamp = self.get_current_timestamp();
@@ -608,14 +1518,14 @@ impl RegulatoryReports for RegulatoryReportsContractState {
         
         let connected_entities = {
             let entity_mcp = EntityRelationshipMcp::new(config.entity_relationship_contract_id.clone());
-            match entity_mcp.get_connected_entities(resolved_entity.clone(), 2) {
-                Ok(connections) => connections.len() as u32,
+            match entity_mcp.get_connected_entities(resolved_entity.clone(), 2, None, None) {
+                Ok(page) => page.connections.len() as u32,
                 Err(_) => 2,
             }
         };
         
         let recent_alerts = {
-            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone());
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
             match dashboard_mcp.get_entity_alerts(resolved_entity.clone(), 10) {
                 Ok(alerts) => alerts.len() as u32,
                 Err(_) => 5,
@@ -661,7 +1571,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             .map_err(|e| format!("Failed to serialize risk report: {}", e))?;
         
         let file_path = format!("risk/{}_{}.json", resolved_entity, timestamp);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -677,11 +1587,126 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: overall_risk_score,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
+        })
+    }
+
+    /// One call producing the scorecard, risk report, open cases, and pending
+    /// pre-clearances for a periodic designated-person review, stored under
+    /// packs/{entity}/{period}/ with a manifest ReportResult pointing at all four.
+    /// This platform has no trading pre-clearance/permission-to-trade system, so
+    /// pending_preclearances.json is always an empty list - documented in the file
+    /// itself rather than silently omitted.
+    #[mutate]
+    async fn generate_entity_compliance_pack(&mut self, entity_id: String, period: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity(&entity_id);
+        let report_id = self.generate_report_id("PACK");
+        let timestamp = self.get_current_timestamp();
+        let config = self.secrets.config();
+        let folder = format!("packs/{}/{}", resolved_entity, period);
+
+        let scorecard = ComplianceScorecard {
+            entity_id: resolved_entity.clone(),
+            entity_name: format!("Entity {}", resolved_entity),
+            reporting_period: period.clone(),
+            overall_score: 78,
+            kyc_compliance: 85,
+            aml_compliance: 72,
+            surveillance_compliance: 80,
+            reporting_compliance: 75,
+            violations_count: 3,
+            risk_score: 45,
+            last_updated: timestamp,
+        };
+        let scorecard_path = format!("{}/scorecard.json", folder);
+        let scorecard_content = serde_json::to_string_pretty(&scorecard)
+            .map_err(|e| format!("Failed to serialize scorecard: {}", e))?;
+        let _ = self.upload_report(&scorecard_path, &scorecard_content)?;
+
+        let risk_profile = {
+            let risk_mcp = RiskScoringMcp::new(config.risk_scoring_contract_id.clone());
+            match risk_mcp.calculate_entity_risk(resolved_entity.clone(), 30) {
+                Ok(profile) => Some(profile),
+                Err(_) => None,
+            }
+        };
+        let (overall_risk_score, insider_risk, manipulation_risk, aml_risk) = match risk_profile {
+            Some(ref profile) => (profile.overall_score, profile.insider_risk, profile.manipulation_risk, profile.aml_risk),
+            None => (72, 65, 80, 55),
+        };
+        let risk_report = serde_json::json!({
+            "report_id": format!("{}-RISK", report_id),
+            "entity_id": resolved_entity,
+            "generated_at": timestamp,
+            "overall_risk_score": overall_risk_score,
+            "risk_factors": {
+                "insider_risk": insider_risk,
+                "manipulation_risk": manipulation_risk,
+                "aml_risk": aml_risk,
+            },
+        });
+        let risk_report_path = format!("{}/risk_report.json", folder);
+        let risk_report_content = serde_json::to_string_pretty(&risk_report)
+            .map_err(|e| format!("Failed to serialize risk report: {}", e))?;
+        let _ = self.upload_report(&risk_report_path, &risk_report_content)?;
+
+        let open_cases = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            match dashboard_mcp.get_cases_by_status("OPEN".to_string(), 200) {
+                Ok(cases) => cases.into_iter().filter(|c| c.subject_entity == resolved_entity).collect::<Vec<_>>(),
+                Err(_) => Vec::new(),
+            }
+        };
+        let open_cases_path = format!("{}/open_cases.json", folder);
+        let open_cases_content = serde_json::to_string_pretty(&open_cases)
+            .map_err(|e| format!("Failed to serialize open cases: {}", e))?;
+        let _ = self.upload_report(&open_cases_path, &open_cases_content)?;
+
+        let preclearances_content = serde_json::to_string_pretty(&serde_json::json!({
+            "pending_preclearances": Vec::<String>::new(),
+            "note": "This platform has no trading pre-clearance/permission-to-trade system; this list is always empty",
+        })).map_err(|e| format!("Failed to serialize pending pre-clearances: {}", e))?;
+        let preclearances_path = format!("{}/pending_preclearances.json", folder);
+        let _ = self.upload_report(&preclearances_path, &preclearances_content)?;
+
+        let manifest = serde_json::json!({
+            "report_id": report_id,
+            "entity_id": resolved_entity,
+            "period": period,
+            "generated_at": timestamp,
+            "scorecard": self.get_public_url(&scorecard_path),
+            "risk_report": self.get_public_url(&risk_report_path),
+            "open_cases": self.get_public_url(&open_cases_path),
+            "pending_preclearances": self.get_public_url(&preclearances_path),
+            "open_case_count": open_cases.len(),
+        });
+        let manifest_path = format!("{}/manifest.json", folder);
+        let manifest_content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        let _ = self.upload_report(&manifest_path, &manifest_content)?;
+
+        let download_url = self.get_public_url(&manifest_path);
+
+        self.update_cache("generate_entity_compliance_pack", &resolved_entity, "", "", &report_id,
+            &format!("Generated compliance pack for {} ({})", resolved_entity, period));
+
+        Ok(ReportResult {
+            report_id,
+            report_type: "COMPLIANCE_PACK".to_string(),
+            storage_path: manifest_path,
+            download_url,
+            expires_at: timestamp + 3600000,
+            risk_score: overall_risk_score,
+            success: true,
+            error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn generate_gsm_report(&mut self, report_date: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let report_id = self.generate_report_id("GSM");
         let timestamp = self.get_current_timestamp();
         
@@ -702,7 +1727,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             .map_err(|e| format!("Failed to serialize GSM report: {}", e))?;
         
         let file_path = format!("gsm/{}.json", report_date);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -718,31 +1743,42 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 0,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn generate_esm_report(&mut self, report_date: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let report_id = self.generate_report_id("ESM");
         let timestamp = self.get_current_timestamp();
         
+        let securities_under_esm: Vec<_> = self.esm_security_status.iter()
+            .filter(|s| s.stage != "NONE")
+            .map(|s| serde_json::json!({
+                "symbol": s.symbol,
+                "category": if s.stage == "LONG_TERM" { "Long Term" } else { "Short Term" },
+                "monitoring_since": s.since,
+            }))
+            .collect();
+        let high_risk_count = self.esm_security_status.iter()
+            .filter(|s| s.stage == "LONG_TERM")
+            .count() as u32;
+
         let report = serde_json::json!({
             "report_id": report_id,
             "report_type": "ESM",
             "report_date": report_date,
-            "securities_under_esm": [
-                {"symbol": "DEF", "category": "Long Term", "monitoring_since": "2025-06-01"},
-                {"symbol": "GHI", "category": "Short Term", "monitoring_since": "2025-11-01"}
-            ],
-            "total_esm_securities": 2,
-            "high_risk_count": 1
+            "total_esm_securities": securities_under_esm.len() as u32,
+            "high_risk_count": high_risk_count,
+            "securities_under_esm": securities_under_esm,
         });
         
         let content = serde_json::to_string_pretty(&report)
             .map_err(|e| format!("Failed to serialize ESM report: {}", e))?;
         
         let file_path = format!("esm/{}.json", report_date);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -758,25 +1794,239 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 0,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
+    #[mutate]
+    async fn evaluate_esm_transitions(&mut self, symbols: String) -> Result<Vec<EsmStageMove>, String> {
+        self.maintenance_guard()?;
+        let config = self.secrets.config();
+        let short_band = config.esm_short_term_band_pct;
+        let long_band = config.esm_long_term_band_pct;
+        let trade_data_contract_id = config.trade_data_contract_id.clone();
+        let now = self.get_current_timestamp();
+
+        let symbol_list: Vec<String> = symbols.split(',')
+            .map(|s| self.resolve_company(s.trim()))
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let mut moves = Vec::new();
+        for symbol in symbol_list {
+            let variation_pct = {
+                let trade_data_mcp = TradeDataMcp::new(trade_data_contract_id.clone());
+                match trade_data_mcp.analyze_volume(symbol.clone()) {
+                    Ok(analysis) => {
+                        let high: f64 = analysis.high_price.parse().unwrap_or(0.0);
+                        let low: f64 = analysis.low_price.parse().unwrap_or(0.0);
+                        let avg: f64 = analysis.avg_price.parse().unwrap_or(0.0);
+                        if avg > 0.0 { (((high - low) / avg) * 100.0).round() as u32 } else { 0 }
+                    }
+                    Err(_) => 0,
+                }
+            };
+
+            let current_stage = self.esm_security_status.iter()
+                .find(|s| s.symbol == symbol)
+                .map(|s| s.stage.clone())
+                .unwrap_or_else(|| "NONE".to_string());
+
+            let target_stage = if long_band > 0 && variation_pct >= long_band {
+                "LONG_TERM"
+            } else if short_band > 0 && variation_pct >= short_band {
+                "SHORT_TERM"
+            } else {
+                "NONE"
+            }.to_string();
+
+            if target_stage == current_stage {
+                continue;
+            }
+
+            self.esm_move_counter += 1;
+            let stage_move = EsmStageMove {
+                move_id: format!("ESMMV-{:04}", self.esm_move_counter),
+                symbol: symbol.clone(),
+                from_stage: current_stage,
+                to_stage: target_stage.clone(),
+                variation_pct,
+                reason: format!(
+                    "day-range spread {}% of avg price vs. short-term band {}% / long-term band {}%",
+                    variation_pct, short_band, long_band
+                ),
+                evaluated_at: now,
+            };
+            self.esm_stage_history.push(stage_move.clone());
+
+            match self.esm_security_status.iter_mut().find(|s| s.symbol == symbol) {
+                Some(status) => {
+                    status.stage = target_stage;
+                    status.since = now;
+                }
+                None => self.esm_security_status.push(EsmSecurityStatus {
+                    symbol,
+                    stage: target_stage,
+                    since: now,
+                }),
+            }
+
+            moves.push(stage_move);
+        }
+
+        self.update_cache("evaluate_esm_transitions", "", "", "", "",
+            &format!("Evaluated ESM transitions, {} stage move(s)", moves.len()));
+
+        Ok(moves)
+    }
+
+    #[query]
+    fn get_esm_stage_history(&self, symbol: String) -> Vec<EsmStageMove> {
+        let resolved = self.resolve_company(&symbol);
+        self.esm_stage_history.iter()
+            .filter(|m| m.symbol == resolved)
+            .cloned()
+            .collect()
+    }
+
     #[mutate]
     async fn get_pending_strs(&mut self, limit: u32) -> Result<Vec<STRReport>, String> {
-        let count = (limit as usize).min(self.pending_strs.len());
-        Ok(self.pending_strs.iter().take(count).cloned().collect())
+        self.maintenance_guard()?;
+        let count = limit as usize;
+        Ok(self.pending_strs.iter().filter(|s| !s.submitted).take(count).cloned().collect())
+    }
+
+    #[mutate]
+    async fn get_str_backlog_report(&mut self) -> Result<STRBacklogReport, String> {
+        self.maintenance_guard()?;
+        let now = self.get_current_timestamp();
+        let config = self.secrets.config();
+        let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+
+        let mut entries = Vec::new();
+        let mut bucket_0_7_count = 0u32;
+        let mut bucket_8_15_count = 0u32;
+        let mut bucket_over_15_count = 0u32;
+        let mut breached_count = 0u32;
+        let mut alerts_sent = 0u32;
+
+        for str_report in self.pending_strs.iter().filter(|s| !s.submitted) {
+            let age_days = now.saturating_sub(str_report.generated_at) / 86_400_000;
+            let ageing_bucket = if age_days <= 7 {
+                bucket_0_7_count += 1;
+                "0-7"
+            } else if age_days <= 15 {
+                bucket_8_15_count += 1;
+                "8-15"
+            } else {
+                bucket_over_15_count += 1;
+                ">15"
+            };
+
+            let responsible_analyst = dashboard_mcp.get_case_details(str_report.case_id.clone())
+                .map(|c| if c.assigned_to.is_empty() { "UNASSIGNED".to_string() } else { c.assigned_to })
+                .unwrap_or_else(|_| "UNASSIGNED".to_string());
+
+            let days_until_deadline = self.str_filing_deadline_days as i64 - age_days as i64;
+            let deadline_breached = days_until_deadline < 0;
+            if deadline_breached {
+                breached_count += 1;
+            }
+
+            if deadline_breached || days_until_deadline as u64 <= self.str_filing_deadline_alert_window_days {
+                let severity = if deadline_breached { "CRITICAL" } else { "HIGH" };
+                let description = if deadline_breached {
+                    format!(
+                        "STR {} for case {} is {} day(s) past the {}-day statutory filing deadline and still unsubmitted",
+                        str_report.str_id, str_report.case_id, -days_until_deadline, self.str_filing_deadline_days
+                    )
+                } else {
+                    format!(
+                        "STR {} for case {} is due for filing in {} day(s) (deadline: {} days from generation) and still unsubmitted",
+                        str_report.str_id, str_report.case_id, days_until_deadline, self.str_filing_deadline_days
+                    )
+                };
+
+                let alert = Alert {
+                    id: format!("STR-DEADLINE-{}", str_report.str_id),
+                    alert_type: "STR_FILING_DEADLINE".to_string(),
+                    severity: severity.to_string(),
+                    risk_score: str_report.risk_score,
+                    entity_id: str_report.suspicious_entity_id.clone(),
+                    symbol: str_report.facts.company_symbol.clone(),
+                    description,
+                    workflow_id: "".to_string(),
+                    timestamp: now,
+                };
+                if dashboard_mcp.push_alert(alert).is_ok() {
+                    alerts_sent += 1;
+                }
+            }
+
+            entries.push(STRBacklogEntry {
+                str_id: str_report.str_id.clone(),
+                case_id: str_report.case_id.clone(),
+                suspicious_entity_id: str_report.suspicious_entity_id.clone(),
+                suspicious_entity_name: str_report.suspicious_entity_name.clone(),
+                company_symbol: str_report.facts.company_symbol.clone(),
+                age_days,
+                ageing_bucket: ageing_bucket.to_string(),
+                responsible_analyst,
+                days_until_deadline,
+                deadline_breached,
+            });
+        }
+
+        self.update_cache("get_str_backlog_report", "", "", "", "",
+            &format!("STR backlog: {} pending, {} past deadline", entries.len(), breached_count));
+
+        self.push_history(
+            "get_str_backlog_report",
+            "",
+            &format!("pending={}, breached={}, alerts_sent={}", entries.len(), breached_count, alerts_sent),
+            "SUCCESS",
+            "",
+            "",
+        );
+
+        Ok(STRBacklogReport {
+            entries,
+            bucket_0_7_count,
+            bucket_8_15_count,
+            bucket_over_15_count,
+            breached_count,
+            alerts_sent,
+            generated_at: now,
+        })
+    }
+
+    /// All STRs (pending or already filed) tied to a case, for building a per-case
+    /// activity view against the report registry
+    #[mutate]
+    async fn get_reports_for_case(&mut self, case_id: String) -> Result<Vec<STRReport>, String> {
+        self.maintenance_guard()?;
+        let resolved_case = self.resolve_case(&case_id);
+        Ok(self.pending_strs.iter().filter(|s| s.case_id == resolved_case).cloned().collect())
     }
 
     #[mutate]
-    async fn submit_str(&mut self, str_id: String) -> Result<ReportResult, String> {
+    async fn submit_str(&mut self, str_id: String, requested_by: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let resolved_str = self.resolve_report(&str_id);
+        self.dual_control_check("submit_str", &resolved_str, &requested_by)?;
         let timestamp = self.get_current_timestamp();
-        
-        self.pending_strs.retain(|s| s.str_id != resolved_str);
-        
-        self.update_cache("submit_str", "", "", "", &resolved_str, 
+
+        match self.pending_strs.iter_mut().find(|s| s.str_id == resolved_str) {
+            Some(report) => {
+                report.submitted = true;
+                report.submitted_at = timestamp;
+            }
+            None => return Err(format!("No STR found with ID {}", resolved_str)),
+        }
+
+        self.update_cache("submit_str", "", "", "", &resolved_str,
             &format!("Submitted STR {} to SEBI", resolved_str));
-        
+
         Ok(ReportResult {
             report_id: resolved_str.clone(),
             report_type: "STR_SUBMITTED".to_string(),
@@ -786,65 +2036,282 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 0,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
+    /// Clones the original STR's facts forward into a new, unsubmitted STR that
+    /// records additional_findings and points back at the original via
+    /// supplementary_of, then stores it in the same pending_strs registry as the
+    /// original - so the filing chain is a plain sequence of linked STRReport
+    /// entries rather than a separate structure. No new call to the dashboard is
+    /// needed to "update the case timeline": get_case_activity already pulls
+    /// every STR tied to a case via get_reports_for_case, so the supplementary
+    /// filing shows up there as soon as it exists.
     #[mutate]
-    async fn generate_investigation_report(&mut self, case_id: String, include_evidence: bool) -> Result<ReportResult, String> {
-        let resolved_case = self.resolve_case(&case_id);
-        let report_id = self.generate_report_id("INV");
+    async fn create_supplementary_str(&mut self, original_str_id: String, additional_findings: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let resolved_original = self.resolve_report(&original_str_id);
+
+        let original = self.pending_strs.iter()
+            .find(|s| s.str_id == resolved_original)
+            .cloned()
+            .ok_or_else(|| format!("No STR found with ID {}", resolved_original))?;
+
+        if !original.submitted {
+            return Err(format!(
+                "STR {} has not been submitted yet; supplementary filings only apply to already-submitted STRs",
+                resolved_original
+            ));
+        }
+
+        let str_id = self.generate_report_id("STR");
+        let report_date = self.get_current_date();
         let timestamp = self.get_current_timestamp();
-        let config = self.secrets.config();
-        
-        let (case_status, subject_entity, risk_score) = {
-            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone());
-            match dashboard_mcp.get_case_details(resolved_case.clone()) {
-                Ok(case_record) => (
-                    case_record.status,
-                    case_record.subject_entity,
-                    case_record.risk_score,
-                ),
-                Err(_) => ("IN_PROGRESS".to_string(), "UNKNOWN".to_string(), 85), // Fallback
-            }
-        };
-        
-        let findings = {
-            let anomaly_mcp = AnomalyDetectionMcp::new(config.anomaly_detection_contract_id.clone());
-            match anomaly_mcp.scan_entity_anomalies(subject_entity.clone()) {
-                Ok(anomalies) => {
-                    if anomalies.is_empty() {
-                        vec![
-                            "No automated anomalies detected".to_string(),
-                            "Manual investigation in progress".to_string(),
-                        ]
-                    } else {
-                        anomalies.iter()
-                            .take(5)
-                            .map(|a| format!("{}: {} (confidence: {}%)", a.anomaly_type, a.details, a.confidence_score))
-                            .collect()
-                    }
-                },
-                Err(_) => vec![
-                    "Unusual trading pattern detected 2 days before announcement".to_string(),
-                    "Connected entities identified through graph analysis".to_string(),
-                    "UPSI access confirmed before trading".to_string(),
-                ], 
-            }
-        };
-        
-        let risk_assessment = if risk_score >= 80 {
-            "HIGH"
-        } else if risk_score >= 50 {
-            "MEDIUM"
-        } else {
-            "LOW"
+
+        let supplementary = STRReport {
+            str_id: str_id.clone(),
+            case_id: original.case_id.clone(),
+            report_date,
+            suspicious_entity_id: original.suspicious_entity_id.clone(),
+            suspicious_entity_name: original.suspicious_entity_name.clone(),
+            suspicious_activity_type: original.suspicious_activity_type.clone(),
+            transaction_details: original.transaction_details.clone(),
+            total_value: original.total_value.clone(),
+            suspicion_reason: format!("Supplementary to {}: {}", resolved_original, additional_findings),
+            investigation_summary: format!("{}\n\nSupplementary findings: {}", original.investigation_summary, additional_findings),
+            grounds_of_suspicion: original.grounds_of_suspicion.clone(),
+            facts: original.facts.clone(),
+            data_lineage: original.data_lineage.clone(),
+            recommendation: original.recommendation.clone(),
+            risk_score: original.risk_score,
+            generated_at: timestamp,
+            generated_at_ist: epoch_ms_to_ist(timestamp),
+            submitted: false,
+            submitted_at: 0,
+            legal_hold: false,
+            supplementary_of: resolved_original.clone(),
         };
-        
-        let recommended_action = if risk_score >= 70 {
-            "PROCEED_TO_ENFORCEMENT"
-        } else if risk_score >= 50 {
-            "CONTINUE_INVESTIGATION"
-        } else {
+
+        let content = serde_json::to_string_pretty(&supplementary)
+            .map_err(|e| format!("Failed to serialize STR: {}", e))?;
+
+        let file_path = format!("str/{}.json", str_id);
+        let _ = self.upload_report(&file_path, &content)?;
+        let download_url = self.get_public_url(&file_path);
+
+        self.pending_strs.push(supplementary);
+
+        self.update_cache("create_supplementary_str", &original.suspicious_entity_id, "", &original.case_id, &str_id,
+            &format!("Filed supplementary STR {} referencing {}", str_id, resolved_original));
+
+        self.push_history(
+            "create_supplementary_str",
+            &format!("original={}", resolved_original),
+            &format!("report_id={}", str_id),
+            "SUCCESS",
+            &original.suspicious_entity_id,
+            "",
+        );
+
+        Ok(ReportResult {
+            report_id: str_id,
+            report_type: "STR_SUPPLEMENTARY".to_string(),
+            storage_path: file_path,
+            download_url,
+            expires_at: timestamp + 3600000,
+            risk_score: original.risk_score,
+            success: true,
+            error: "".to_string(),
+            duplicate_of: "".to_string(),
+        })
+    }
+
+    #[mutate]
+    async fn set_legal_hold(&mut self, report_id: String, enabled: bool) -> Result<STRReport, String> {
+        self.maintenance_guard()?;
+        let resolved_str = self.resolve_report(&report_id);
+
+        let report = self.pending_strs.iter_mut()
+            .find(|s| s.str_id == resolved_str)
+            .ok_or_else(|| format!("No STR found with ID {}", resolved_str))?;
+        report.legal_hold = enabled;
+        let updated = report.clone();
+
+        self.update_cache("set_legal_hold", "", "", "", &resolved_str,
+            &format!("{} legal hold on STR {}", if enabled { "Placed" } else { "Released" }, resolved_str));
+
+        Ok(updated)
+    }
+
+    #[mutate]
+    async fn purge_expired_reports(&mut self, requested_by: String) -> Result<ReportPurgeSummary, String> {
+        self.maintenance_guard()?;
+        self.dual_control_check("purge_expired_reports", "ALL", &requested_by)?;
+        let now = self.get_current_timestamp();
+        let retention_ms = self.str_retention_days.saturating_mul(86_400_000);
+
+        let mut purged_count = 0u32;
+        let mut held_count = 0u32;
+        let mut retained_count = 0u32;
+
+        self.pending_strs.retain(|s| {
+            if !s.submitted {
+                retained_count += 1;
+                return true;
+            }
+            if s.legal_hold {
+                held_count += 1;
+                return true;
+            }
+            let age = now.saturating_sub(s.submitted_at);
+            if age < retention_ms {
+                retained_count += 1;
+                return true;
+            }
+            purged_count += 1;
+            false
+        });
+
+        self.update_cache("purge_expired_reports", "", "", "", "",
+            &format!("Purged {} STR(s) past the {}-day retention period", purged_count, self.str_retention_days));
+
+        Ok(ReportPurgeSummary {
+            purged_count,
+            held_count,
+            retained_count,
+        })
+    }
+
+    #[mutate]
+    async fn gc_storage(&mut self, dry_run: bool, requested_by: String) -> Result<GcStorageSummary, String> {
+        self.maintenance_guard()?;
+        if !dry_run {
+            self.dual_control_check("gc_storage", "str/", &requested_by)?;
+        }
+
+        let now = self.get_current_timestamp();
+        let retention_seconds = self.str_retention_days.saturating_mul(86400);
+        let storage = self.build_storage();
+
+        let objects = storage.list("str/")?;
+        let scanned_count = objects.len() as u32;
+
+        let mut orphaned = Vec::new();
+        let mut reclaimed_bytes = 0u64;
+        let mut to_delete = Vec::new();
+
+        for object in &objects {
+            let str_id = object.name
+                .trim_start_matches("str/")
+                .trim_end_matches(".json");
+            if self.pending_strs.iter().any(|s| s.str_id == str_id) {
+                continue;
+            }
+
+            let updated_at_ms = DateTime::parse_from_rfc3339(&object.updated_at)
+                .map(|dt| dt.timestamp_millis().max(0) as u64)
+                .unwrap_or(0);
+            let age_seconds = now.saturating_sub(updated_at_ms) / 1000;
+            if age_seconds < retention_seconds {
+                continue;
+            }
+
+            if !dry_run {
+                to_delete.push(object.name.clone());
+            }
+            reclaimed_bytes += object.size_bytes;
+            orphaned.push(OrphanedObject {
+                file_path: object.name.clone(),
+                updated_at: object.updated_at.clone(),
+                size_bytes: object.size_bytes,
+                deleted: !dry_run,
+            });
+        }
+
+        if !to_delete.is_empty() {
+            storage.delete_batch(&to_delete)?;
+        }
+
+        self.update_cache("gc_storage", "", "", "", "",
+            &format!("{} {} orphaned str/ object(s) past the {}-day retention period",
+                if dry_run { "Found" } else { "Reclaimed" }, orphaned.len(), self.str_retention_days));
+
+        Ok(GcStorageSummary {
+            dry_run,
+            scanned_count,
+            orphaned,
+            reclaimed_bytes,
+            skipped_prefixes: vec![
+                "surveillance/".to_string(), "compliance/".to_string(), "risk/".to_string(),
+                "packs/".to_string(), "daily/".to_string(), "gsm/".to_string(), "esm/".to_string(),
+                "investigation/".to_string(), "trade_anomaly/".to_string(), "dp_trading/".to_string(),
+            ],
+        })
+    }
+
+    #[query]
+    fn list_pending_approvals(&self) -> Vec<PendingApproval> {
+        self.pending_approvals.clone()
+    }
+
+    #[mutate]
+    async fn generate_investigation_report(&mut self, case_id: String, include_evidence: bool) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let resolved_case = self.resolve_case(&case_id);
+        let report_id = self.generate_report_id("INV");
+        let timestamp = self.get_current_timestamp();
+        let config = self.secrets.config();
+        
+        let (case_status, subject_entity, risk_score) = {
+            let dashboard_mcp = DashboardMcp::new(config.dashboard_contract_id.clone(), config.dashboard_caller_token.clone());
+            match dashboard_mcp.get_case_details(resolved_case.clone()) {
+                Ok(case_record) => (
+                    case_record.status,
+                    case_record.subject_entity,
+                    case_record.risk_score,
+                ),
+                Err(_) => ("IN_PROGRESS".to_string(), "UNKNOWN".to_string(), 85), // Fallback
+            }
+        };
+        
+        let findings = {
+            let anomaly_mcp = AnomalyDetectionMcp::new(config.anomaly_detection_contract_id.clone());
+            match anomaly_mcp.scan_entity_anomalies(subject_entity.clone()) {
+                Ok(anomalies) => {
+                    if anomalies.is_empty() {
+                        vec![
+                            "No automated anomalies detected".to_string(),
+                            "Manual investigation in progress".to_string(),
+                        ]
+                    } else {
+                        anomalies.iter()
+                            .take(5)
+                            .map(|a| format!("{}: {} (confidence: {}%)", a.anomaly_type, a.details, a.confidence_score))
+                            .collect()
+                    }
+                },
+                Err(_) => vec![
+                    "Unusual trading pattern detected 2 days before announcement".to_string(),
+                    "Connected entities identified through graph analysis".to_string(),
+                    "UPSI access confirmed before trading".to_string(),
+                ], 
+            }
+        };
+        
+        let risk_assessment = if risk_score >= 80 {
+            "HIGH"
+        } else if risk_score >= 50 {
+            "MEDIUM"
+        } else {
+            "LOW"
+        };
+        
+        let recommended_action = if risk_score >= 70 {
+            "PROCEED_TO_ENFORCEMENT"
+        } else if risk_score >= 50 {
+            "CONTINUE_INVESTIGATION"
+        } else {
             "CLOSE_CASE"
         };
         
@@ -886,7 +2353,7 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             .map_err(|e| format!("Failed to serialize investigation report: {}", e))?;
         
         let file_path = format!("investigation/{}.json", resolved_case);
-        let _ = self.upload_to_supabase(&file_path, &content)?;
+        let _ = self.upload_report(&file_path, &content)?;
         
         let download_url = self.get_public_url(&file_path);
         
@@ -902,11 +2369,13 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
     #[mutate]
     async fn get_report_url(&mut self, report_id: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
         let resolved_report = self.resolve_report(&report_id);
         let timestamp = self.get_current_timestamp();
         
@@ -930,10 +2399,12 @@ impl RegulatoryReports for RegulatoryReportsContractState {
         
         let download_url = self.get_signed_url(&file_path, 3600)
             .unwrap_or_else(|_| self.get_public_url(&file_path));
-        
-        self.update_cache("get_report_url", "", "", "", &resolved_report, 
+
+        let _ = self.record_report_access(resolved_report.clone(), "SYSTEM".to_string(), "URL_RETRIEVAL".to_string()).await;
+
+        self.update_cache("get_report_url", "", "", "", &resolved_report,
             &format!("Retrieved URL for {}", resolved_report));
-        
+
         Ok(ReportResult {
             report_id: resolved_report,
             report_type: report_type.to_string(),
@@ -943,9 +2414,324 @@ impl RegulatoryReports for RegulatoryReportsContractState {
             risk_score: 0,
             success: true,
             error: "".to_string(),
+            duplicate_of: "".to_string(),
+        })
+    }
+
+    /// Records that report_id was accessed by accessor for purpose; get_report_url
+    /// calls this automatically with a SYSTEM/URL_RETRIEVAL entry, and callers with
+    /// their own accessor identity (e.g. the dashboard) should call this directly
+    #[mutate]
+    async fn record_report_access(&mut self, report_id: String, accessor: String, purpose: String) -> Result<ReportAccessLog, String> {
+        self.maintenance_guard()?;
+        let resolved_report = self.resolve_report(&report_id);
+        let timestamp = self.get_current_timestamp();
+        self.access_log_counter += 1;
+
+        let entry = ReportAccessLog {
+            access_id: format!("RAL-{:04}", self.access_log_counter),
+            report_id: resolved_report.clone(),
+            accessor: accessor.clone(),
+            purpose: purpose.clone(),
+            access_timestamp: timestamp,
+        };
+        self.report_access_log.push(entry.clone());
+
+        self.update_cache("record_report_access", "", "", "", &resolved_report,
+            &format!("Recorded access to {} by {} ({})", resolved_report, accessor, purpose));
+
+        Ok(entry)
+    }
+
+    #[mutate]
+    async fn get_report_access_log(&mut self, report_id: String) -> Result<Vec<ReportAccessLog>, String> {
+        self.maintenance_guard()?;
+        let resolved_report = self.resolve_report(&report_id);
+        Ok(self.report_access_log.iter().filter(|a| a.report_id == resolved_report).cloned().collect())
+    }
+
+    /// Previews the report_id generate_report_id(report_type) would produce next,
+    /// without consuming the counter
+    #[query]
+    async fn get_next_report_number_preview(&self, report_type: String) -> Result<String, String> {
+        let fy = self.current_financial_year();
+        let number = self.peek_report_number(&report_type, &fy);
+        Ok(format!("{}-{}-{:04}", report_type, fy, number))
+    }
+
+    #[mutate]
+    async fn generate_trade_anomaly_report(&mut self, symbol: String, from_date: String, to_date: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_company(&symbol);
+        let report_id = self.generate_report_id("ANOM");
+        let timestamp = self.get_current_timestamp();
+        let config = self.secrets.config();
+
+        let (total_volume, avg_price, concentration_ratio) = {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            match trade_data_mcp.analyze_volume(resolved_symbol.clone()) {
+                Ok(analysis) => (analysis.total_volume, analysis.avg_price, analysis.concentration_ratio),
+                Err(_) => (0, "0".to_string(), "0".to_string()),
+            }
+        };
+
+        let (volume_anomaly_detected, volume_ratio, volume_anomaly_score) = {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            match trade_data_mcp.detect_volume_anomaly(resolved_symbol.clone()) {
+                Ok(anomaly) => (anomaly.is_anomaly, anomaly.volume_ratio, anomaly.anomaly_score),
+                Err(_) => (false, "1.0".to_string(), 0),
+            }
+        };
+
+        let price_series = {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            match trade_data_mcp.get_trades_by_symbol(resolved_symbol.clone(), 50) {
+                Ok(trades) => trades.iter()
+                    .map(|t| ChartPoint { timestamp: t.timestamp, value: t.price.clone() })
+                    .collect::<Vec<_>>(),
+                Err(_) => Vec::new(),
+            }
+        };
+
+        let volume_series = {
+            let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+            match trade_data_mcp.get_trades_by_symbol(resolved_symbol.clone(), 50) {
+                Ok(trades) => trades.iter()
+                    .map(|t| ChartPoint { timestamp: t.timestamp, value: t.quantity.to_string() })
+                    .collect::<Vec<_>>(),
+                Err(_) => Vec::new(),
+            }
+        };
+
+        let alert_markers = {
+            let anomaly_mcp = AnomalyDetectionMcp::new(config.anomaly_detection_contract_id.clone());
+            let mut markers = Vec::new();
+            if let Ok(pump_dump) = anomaly_mcp.detect_pump_dump(resolved_symbol.clone(), 60) {
+                if pump_dump.is_pump_dump {
+                    markers.push(AlertMarker {
+                        timestamp,
+                        label: "PUMP_DUMP".to_string(),
+                        severity: "HIGH".to_string(),
+                    });
+                }
+            }
+            if let Ok(anomaly_result) = anomaly_mcp.analyze_volume_anomaly(resolved_symbol.clone(), "1d".to_string()) {
+                markers.push(AlertMarker {
+                    timestamp: anomaly_result.timestamp,
+                    label: anomaly_result.anomaly_type,
+                    severity: if anomaly_result.confidence_score >= 70 { "HIGH".to_string() } else { "MEDIUM".to_string() },
+                });
+            }
+            markers
+        };
+
+        let report = TradeAnomalyReport {
+            report_id: report_id.clone(),
+            symbol: resolved_symbol.clone(),
+            report_period: format!("{} to {}", from_date, to_date),
+            generated_at: timestamp,
+            total_volume,
+            avg_price,
+            concentration_ratio,
+            volume_anomaly_detected,
+            volume_ratio,
+            anomaly_score: volume_anomaly_score,
+            price_series,
+            volume_series,
+            alert_markers,
+        };
+
+        let content = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize trade anomaly report: {}", e))?;
+
+        let file_path = format!("trade_anomaly/{}_{}.json", resolved_symbol, from_date);
+        let _ = self.upload_report(&file_path, &content)?;
+
+        let download_url = self.get_public_url(&file_path);
+
+        self.update_cache("generate_trade_anomaly_report", "", &resolved_symbol, "", &report_id,
+            &format!("Generated trade anomaly report for {}", resolved_symbol));
+
+        Ok(ReportResult {
+            report_id,
+            report_type: "TRADE_ANOMALY".to_string(),
+            storage_path: file_path,
+            download_url,
+            expires_at: timestamp + 3600000,
+            risk_score: volume_anomaly_score,
+            success: true,
+            error: "".to_string(),
+            duplicate_of: "".to_string(),
+        })
+    }
+
+    /// Standard quarterly compliance deliverable: every designated person for
+    /// company_symbol from the DP register, their trades in the quarter (looked
+    /// up by treating each DP's entity_id as the trade_data account identifier,
+    /// same fuzzy-resolution convention trade_data_mcp's own
+    /// resolve_account_or_pan uses), each trade's window-period status, and
+    /// pre-clearance status. quarter is informational only - fetch_trades in
+    /// trade_data_mcp has no historical range query, so this reports against
+    /// whatever trades that contract currently returns for the account, not a
+    /// true quarter-bounded slice.
+    #[mutate]
+    async fn generate_dp_trading_report(&mut self, company_symbol: String, quarter: String) -> Result<ReportResult, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_company(&company_symbol);
+        let report_id = self.generate_report_id("DPTRADE");
+        let timestamp = self.get_current_timestamp();
+        let config = self.secrets.config();
+
+        let designated_persons = {
+            let upsi_mcp = UpsiDatabaseMcp::new(config.upsi_database_contract_id.clone());
+            upsi_mcp.list_designated_persons(resolved_symbol.clone()).unwrap_or_default()
+        };
+
+        let mut entries = Vec::new();
+        let mut total_trades = 0u32;
+        let mut flagged_trades = 0u32;
+
+        for dp in &designated_persons {
+            let trades = {
+                let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+                trade_data_mcp.get_trades_by_account(dp.entity_id.clone(), 100).unwrap_or_default()
+            };
+
+            let mut trade_lines = Vec::new();
+            let mut flagged_count = 0u32;
+
+            for trade in trades {
+                let during_closed_window = {
+                    let upsi_mcp = UpsiDatabaseMcp::new(config.upsi_database_contract_id.clone());
+                    upsi_mcp.check_window_violation(dp.entity_id.clone(), resolved_symbol.clone(), trade.timestamp)
+                        .unwrap_or(false)
+                };
+                let flagged = during_closed_window;
+                if flagged {
+                    flagged_count += 1;
+                }
+
+                trade_lines.push(DpTradeLine {
+                    trade_id: trade.trade_id,
+                    trade_type: trade.trade_type,
+                    quantity: trade.quantity,
+                    price: trade.price,
+                    timestamp: trade.timestamp,
+                    during_closed_window,
+                    preclearance_status: "NOT_TRACKED".to_string(),
+                    flagged,
+                });
+            }
+
+            total_trades += trade_lines.len() as u32;
+            flagged_trades += flagged_count;
+
+            entries.push(DpReconciliationEntry {
+                dp_id: dp.dp_id.clone(),
+                entity_id: dp.entity_id.clone(),
+                designation: dp.designation.clone(),
+                trades: trade_lines,
+                flagged_trade_count: flagged_count,
+            });
+        }
+
+        let report = DpTradingReport {
+            report_id: report_id.clone(),
+            company_symbol: resolved_symbol.clone(),
+            quarter: quarter.clone(),
+            generated_at: timestamp,
+            dp_count: designated_persons.len() as u32,
+            total_trades,
+            flagged_trades,
+            entries,
+        };
+
+        let content = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize DP trading report: {}", e))?;
+
+        let file_path = format!("dp_trading/{}_{}.json", resolved_symbol, quarter);
+        let _ = self.upload_report(&file_path, &content)?;
+
+        let download_url = self.get_public_url(&file_path);
+
+        self.update_cache("generate_dp_trading_report", "", &resolved_symbol, "", &report_id,
+            &format!("Generated DP trading reconciliation report for {} ({})", resolved_symbol, quarter));
+
+        Ok(ReportResult {
+            report_id,
+            report_type: "DP_TRADING_RECONCILIATION".to_string(),
+            storage_path: file_path,
+            download_url,
+            expires_at: timestamp + 3600000,
+            risk_score: 0,
+            success: true,
+            error: "".to_string(),
+            duplicate_of: "".to_string(),
         })
     }
 
+    /// Record that a sensitive config field was rotated in the secret store. Supabase
+    /// requests re-read self.secrets.config() on every call, so the new service key is
+    /// already live - this just gives operators an auditable confirmation of the rotation.
+    #[mutate]
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String> {
+        self.maintenance_guard()?;
+        let known_fields = ["supabase_service_key"];
+        if !known_fields.contains(&field_name.as_str()) {
+            return Err(format!("Unknown rotatable field '{}': expected one of {:?}", field_name, known_fields));
+        }
+
+        for entry in self.secret_versions.iter_mut() {
+            if entry.field_name == field_name {
+                entry.version += 1;
+                entry.rotated_at = rotated_at;
+                return Ok(entry.clone());
+            }
+        }
+
+        let entry = SecretVersionEntry {
+            field_name,
+            version: 1,
+            rotated_at,
+        };
+        self.secret_versions.push(entry.clone());
+        Ok(entry)
+    }
+
+    #[query]
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry> {
+        self.secret_versions.clone()
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
+    #[mutate]
+    fn set_clock(&mut self, timestamp: u64, date: String) -> ClockState {
+        self.clock = ClockState { timestamp, date };
+        self.clock.clone()
+    }
+
+    #[query]
+    fn get_clock(&self) -> ClockState {
+        self.clock.clone()
+    }
+
+    #[mutate]
+    async fn flush_history(&mut self) -> Result<u32, String> {
+        let before = self.history_buffer.len();
+        self.flush_history_buffer();
+        Ok((before - self.history_buffer.len()) as u32)
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -968,9 +2754,11 @@ impl RegulatoryReports for RegulatoryReportsContractState {
           "case_id": {"type": "string", "description": "Case ID - supports fuzzy matching"},
           "entity_id": {"type": "string", "description": "Entity ID - supports fuzzy matching"},
           "suspicious_activity_type": {"type": "string", "description": "INSIDER_TRADING, MANIPULATION, FRONT_RUNNING"},
-          "suspicion_reason": {"type": "string", "description": "Detailed reason for suspicion"}
+          "suspicion_reason": {"type": "string", "description": "Detailed reason for suspicion"},
+          "force_new": {"type": "boolean", "description": "Generate a new STR even if a pending one already exists for this case/entity (default: false)"},
+          "idempotency_key": {"type": "string", "description": "Optional caller-supplied key; a retried call with the same key replays the original result instead of generating a duplicate report"}
         },
-        "required": ["case_id", "entity_id", "suspicious_activity_type", "suspicion_reason"]
+        "required": ["case_id", "entity_id", "suspicious_activity_type", "suspicion_reason", "force_new"]
       }
     }
   },
@@ -1019,6 +2807,21 @@ impl RegulatoryReports for RegulatoryReportsContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_entity_compliance_pack",
+      "description": "Generate the scorecard, risk report, open cases, and pending pre-clearances for an entity's periodic review in one call\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {"type": "string", "description": "Entity ID - supports fuzzy matching"},
+          "period": {"type": "string", "description": "Reporting period label (e.g. 2026-Q1)"}
+        },
+        "required": ["entity_id", "period"]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -1061,17 +2864,99 @@ impl RegulatoryReports for RegulatoryReportsContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_str_backlog_report",
+      "description": "Get pending STRs bucketed by ageing (0-7, 8-15, >15 days) with the responsible analyst, alerting the dashboard for STRs near or past the statutory filing deadline\n",
+      "parameters": {
+        "type": "object",
+        "properties": {}
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_reports_for_case",
+      "description": "Get all STRs (pending or filed) tied to a case\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "case_id": {"type": "string", "description": "Case ID - supports fuzzy matching"}
+        },
+        "required": ["case_id"]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
       "name": "submit_str",
-      "description": "Submit STR to regulatory authority (SEBI)\n",
+      "description": "Submit STR to regulatory authority (SEBI). Dual-control: the first call from a caller records a pending approval and is rejected; a second call from a different caller within 60 minutes executes it\n",
       "parameters": {
         "type": "object",
         "properties": {
-          "str_id": {"type": "string", "description": "STR ID - supports fuzzy matching"}
+          "str_id": {"type": "string", "description": "STR ID - supports fuzzy matching"},
+          "requested_by": {"type": "string", "description": "Identity of the caller making this call"}
         },
-        "required": ["str_id"]
+        "required": ["str_id", "requested_by"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "create_supplementary_str",
+      "description": "File a supplementary STR against an already-submitted original, carrying its facts forward and recording additional findings; the chain shows up automatically in get_case_activity and get_reports_for_case\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "original_str_id": {"type": "string", "description": "Original STR ID - supports fuzzy matching"},
+          "additional_findings": {"type": "string", "description": "New evidence or findings that surfaced after the original filing"}
+        },
+        "required": ["original_str_id", "additional_findings"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_legal_hold",
+      "description": "Place or release a legal hold on an STR, preventing purge_expired_reports from removing it while enabled\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "report_id": {"type": "string", "description": "STR ID - supports fuzzy matching"},
+          "enabled": {"type": "boolean", "description": "true to place the hold, false to release it"}
+        },
+        "required": ["report_id", "enabled"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "purge_expired_reports",
+      "description": "Remove submitted STRs past the statutory retention period, skipping anything under legal hold or still within the window. Dual-control: the first call from a caller records a pending approval and is rejected; a second call from a different caller within 60 minutes executes it\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "requested_by": {"type": "string", "description": "Identity of the caller making this call"}
+        },
+        "required": ["requested_by"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_pending_approvals",
+      "description": "List dual-control approvals currently awaiting a second, different caller\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
       }
     }
   },
@@ -1103,6 +2988,108 @@ impl RegulatoryReports for RegulatoryReportsContractState {
         "required": ["report_id"]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "record_report_access",
+      "description": "Record that a report's contents or download URL were accessed, for the report's own access trail\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "report_id": {"type": "string", "description": "Report ID - supports fuzzy matching"},
+          "accessor": {"type": "string", "description": "Name or entity ID of whoever accessed the report"},
+          "purpose": {"type": "string", "description": "Why the report was accessed"}
+        },
+        "required": ["report_id", "accessor", "purpose"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_report_access_log",
+      "description": "Get the access trail for a report\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "report_id": {"type": "string", "description": "Report ID - supports fuzzy matching"}
+        },
+        "required": ["report_id"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_next_report_number_preview",
+      "description": "Preview the report_id the next report of this type would get, without consuming the counter\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "report_type": {"type": "string", "description": "Report prefix, e.g. STR, SURV, COMP, RISK, GSM, ESM, INV, ANOM"}
+        },
+        "required": ["report_type"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_trade_anomaly_report",
+      "description": "Generate a trade anomaly report combining trade volume analysis and anomaly detection results, with ready-to-plot price/volume series and alert markers for the dashboard\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "symbol": {"type": "string", "description": "Stock symbol - supports fuzzy matching"},
+          "from_date": {"type": "string", "description": "Start date (YYYY-MM-DD)"},
+          "to_date": {"type": "string", "description": "End date (YYYY-MM-DD)"}
+        },
+        "required": ["symbol", "from_date", "to_date"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_dp_trading_report",
+      "description": "Quarterly designated-person trade reconciliation: every DP for the company, their trades in the quarter, window-period flags, and pre-clearance status\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "company_symbol": {"type": "string", "description": "Company symbol - supports fuzzy matching"},
+          "quarter": {"type": "string", "description": "Reporting quarter label, e.g. Q1-2026 - informational only"}
+        },
+        "required": ["company_symbol", "quarter"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "rotate_secret",
+      "description": "Record that a sensitive config field (supabase_service_key) was rotated in the secret store\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "field_name": {"type": "string", "description": "Name of the rotated config field"},
+          "rotated_at": {"type": "integer", "description": "Timestamp of the rotation"}
+        },
+        "required": ["field_name", "rotated_at"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_secret_versions",
+      "description": "Get rotation metadata (field name, version, timestamp) for sensitive config fields, values excluded\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
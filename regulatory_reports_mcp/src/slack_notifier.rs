@@ -0,0 +1,52 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct SlackMcp {
+    contract_id: String,
+}
+
+impl SlackMcp {
+    pub fn new(contract_id: String) -> Self {
+        SlackMcp { contract_id }
+    }
+}
+
+// ===== Data Types for Slack =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationResult {
+    pub success: bool,
+    pub message_id: String,
+    pub timestamp: u64,
+    pub error: String,
+}
+
+impl SlackMcp {
+    pub fn send_daily_summary(&self, date: String, total_alerts: u32, critical_alerts: u32, open_cases: u32, new_cases: u32) -> Result<NotificationResult> {
+        #[derive(Debug, Serialize)]
+        struct SendDailySummaryArgs {
+            date: String,
+            total_alerts: u32,
+            critical_alerts: u32,
+            open_cases: u32,
+            new_cases: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&SendDailySummaryArgs {
+            date,
+            total_alerts,
+            critical_alerts,
+            open_cases,
+            new_cases,
+        })?);
+
+        let resp = Runtime::call_contract::<NotificationResult>(
+            self.contract_id.clone(),
+            "send_daily_summary".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct UpsiDatabaseMcp {
+    contract_id: String,
+}
+
+impl UpsiDatabaseMcp {
+    pub fn new(contract_id: String) -> Self {
+        UpsiDatabaseMcp { contract_id }
+    }
+}
+
+// ===== Response Types =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UPSIAccessLog {
+    pub access_id: String,
+    pub upsi_id: String,
+    pub accessor_entity_id: String,
+    pub accessor_name: String,
+    pub accessor_designation: String,
+    pub access_timestamp: u64,
+    pub access_reason: String,
+    pub access_mode: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DesignatedPerson {
+    pub dp_id: String,
+    pub entity_id: String,
+    pub company_symbol: String,
+    pub designation: String,
+    pub effective_from: u64,
+    pub active: bool,
+}
+
+impl UpsiDatabaseMcp {
+    pub fn get_access_by_person(&self, accessor_entity_id: String, days_back: u32) -> Result<Vec<UPSIAccessLog>> {
+        #[derive(Debug, Serialize)]
+        struct GetAccessByPersonArgs {
+            accessor_entity_id: String,
+            days_back: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetAccessByPersonArgs { accessor_entity_id, days_back })?);
+
+        let resp = Runtime::call_contract::<Vec<UPSIAccessLog>>(
+            self.contract_id.clone(),
+            "get_access_by_person".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn list_designated_persons(&self, company_symbol: String) -> Result<Vec<DesignatedPerson>> {
+        #[derive(Debug, Serialize)]
+        struct ListDesignatedPersonsArgs {
+            company_symbol: String,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&ListDesignatedPersonsArgs { company_symbol })?);
+
+        let resp = Runtime::call_contract::<Vec<DesignatedPerson>>(
+            self.contract_id.clone(),
+            "list_designated_persons".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn check_window_violation(&self, entity_id: String, company_symbol: String, trade_timestamp: u64) -> Result<bool> {
+        #[derive(Debug, Serialize)]
+        struct CheckWindowViolationArgs {
+            entity_id: String,
+            company_symbol: String,
+            trade_timestamp: u64,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&CheckWindowViolationArgs {
+            entity_id,
+            company_symbol,
+            trade_timestamp,
+        })?);
+
+        let resp = Runtime::call_contract::<bool>(
+            self.contract_id.clone(),
+            "check_window_violation".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}
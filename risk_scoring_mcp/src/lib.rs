@@ -6,7 +6,7 @@
 //! - 81-100: CRITICAL
 
 use serde::{Deserialize, Serialize};
-use weil_macros::{constructor, query, smart_contract, WeilType};
+use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
 use weil_rs::config::Secrets;
 use weil_rs::runtime::Runtime;
 
@@ -17,6 +17,11 @@ pub struct RiskScoringConfig {
     pub dashboard_contract_id: String,
     pub high_risk_threshold: String,
     pub critical_risk_threshold: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert's caller_token
+    pub dashboard_caller_token: String,
+    /// Graph source for propagate_risk's get_connected_entities call
+    pub entity_relationship_contract_id: String,
 }
 
 // ===== DATA STRUCTURES =====
@@ -57,6 +62,40 @@ pub struct PatternRiskResult {
     pub risk_score: u32,
 }
 
+/// A stored, reproducible breakdown of one score computation - factor values,
+/// weights, whatever raw source IDs fed into it, and a human-readable trace of how
+/// the number was built - so a score quoted in an STR can be pulled back up later
+/// by explanation_id instead of re-derived from memory.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ScoreExplanation {
+    pub explanation_id: String,
+    pub entity_id: String,
+    pub computed_at: u64,
+    pub overall_score: u32,
+    pub risk_level: String,
+    pub factors: Vec<RiskFactor>,
+    /// Alert/trade/UPSI-access IDs the score was computed from. Entity-level scoring
+    /// (calculate_entity_risk) doesn't resolve per-entity alert/trade/UPSI records
+    /// itself - callers pass trade- or event-level attributes directly into
+    /// calculate_trade_risk/evaluate_insider_risk instead - so this is empty until
+    /// that lookup exists; left as a real field rather than omitted so the shape is
+    /// ready once it does.
+    pub source_references: Vec<String>,
+    pub computation_trace: String,
+}
+
+/// One neighbor propagate_risk visited while walking the entity_relationship
+/// graph out from a seed entity - its distance from the seed, the score it
+/// inherited, and whether that score was high enough to register an alert
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PropagatedRisk {
+    pub entity_id: String,
+    pub hops: u32,
+    pub propagated_score: u32,
+    pub risk_level: String,
+    pub registered: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct Alert {
     pub id: String,
@@ -70,6 +109,49 @@ pub struct Alert {
     pub timestamp: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct AlertsSummary {
+    pub total: u32,
+    pub critical: u32,
+    pub high: u32,
+    pub medium: u32,
+    pub low: u32,
+}
+
+/// The canonical per-entity aggregation that both the UI's profile page and
+/// reports pull from, so the two don't drift into computing their own answers
+/// for "how risky is this entity right now" independently.
+///
+/// Several fields can't be backed by real data yet and are documented rather
+/// than silently faked - see profile_gaps for the exact reasons, filled in at
+/// call time:
+/// - trend: calculate_entity_risk returns the same fixed placeholder score for
+///   every entity (see explain_score's doc comment), so there is no real score history
+///   to compare against
+/// - open_case_count: get_cases_by_status/get_case on dashboard_webserver both
+///   require an ANALYST-authorized session token, which this contract has no
+///   way to hold
+/// - insider_designation_count: upsi_database_mcp only exposes
+///   list_designated_persons(company_symbol), with no reverse index by
+///   entity_id, so a lookup would need a symbol this endpoint isn't given
+/// - watchlist_member: no entity watchlist/membership registry exists
+///   anywhere in this codebase
+/// - last_review_date: no review-tracking concept exists anywhere in this
+///   codebase
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SurveillanceProfile {
+    pub entity_id: String,
+    pub risk_score: u32,
+    pub risk_level: String,
+    pub trend: String,
+    pub active_alerts: AlertsSummary,
+    pub open_case_count: u32,
+    pub insider_designation_count: u32,
+    pub watchlist_member: bool,
+    pub last_review_date: u64,
+    pub profile_gaps: Vec<String>,
+}
+
 // ===== TRAIT DEFINITION =====
 
 trait RiskScoring {
@@ -80,15 +162,42 @@ trait RiskScoring {
     async fn evaluate_insider_risk(&self, symbol: String, account_id: String, announcement_timestamp: u64, lookback_days: u32) -> Result<RiskScore, String>;
     async fn get_risk_factors(&self, target_id: String, target_type: String) -> Result<Vec<RiskFactor>, String>;
     async fn get_symbol_risk(&self, symbol: String, as_of_timestamp: u64) -> Result<RiskScore, String>;
+    async fn explain_score(&mut self, entity_id: String) -> Result<ScoreExplanation, String>;
+    async fn get_score_explanation(&self, explanation_id: String) -> Result<ScoreExplanation, String>;
+    /// Spreads a fraction of seed_entity_id's score to its entity_relationship
+    /// graph neighbors, decaying by decay_factor per hop out to max_hops, and
+    /// registers an alert with the dashboard for every neighbor whose
+    /// propagated score still clears the high-risk threshold
+    async fn propagate_risk(&self, seed_entity_id: String, decay_factor: String, max_hops: u32) -> Result<Vec<PropagatedRisk>, String>;
+    // Aggregates risk score/trend, an active-alerts summary, and (where the data
+    // actually exists) case/designation/watchlist/review fields into the one
+    // payload both the UI profile page and reports should read instead of each
+    // computing their own view - see SurveillanceProfile's doc comment for which
+    // fields are still gaps
+    async fn get_surveillance_profile(&self, entity_id: String) -> Result<SurveillanceProfile, String>;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely carry out
+/// maintenance without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct RiskScoringContractState {
     secrets: Secrets<RiskScoringConfig>,
+    maintenance: MaintenanceStatus,
+    score_history: Vec<ScoreExplanation>,
+    score_explanation_seq: u32,
 }
 
 // ===== HELPER METHODS =====
@@ -139,7 +248,7 @@ impl RiskScoringContractState {
                     timestamp: 0,
                 };
                 
-                let args = serde_json::to_string(&alert).unwrap();
+                let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
                 let _ = Runtime::call_contract::<String>(
                     config.dashboard_contract_id.clone(),
                     "push_alert".to_string(),
@@ -149,6 +258,48 @@ impl RiskScoringContractState {
         }
         Ok(())
     }
+
+    /// Best-effort active-alerts count for get_surveillance_profile: fetches the
+    /// dashboard's live-alerts view and filters to this entity_id client-side,
+    /// since get_live_alerts has no entity filter of its own. Never fails the
+    /// whole profile - an unconfigured or unreachable dashboard just leaves the
+    /// summary at all zeros with a gap noted.
+    async fn fetch_entity_alerts_summary(&self, entity_id: &str, profile_gaps: &mut Vec<String>) -> AlertsSummary {
+        let config = self.secrets.config();
+        if config.dashboard_contract_id.is_empty() {
+            profile_gaps.push("active_alerts is all zeros: dashboard_contract_id is not configured".to_string());
+            return AlertsSummary::default();
+        }
+
+        let args = serde_json::json!({
+            "token": config.dashboard_caller_token,
+            "severity_filter": null,
+            "limit": null,
+            "include_test": false,
+            "tenant_filter": null,
+        }).to_string();
+
+        match Runtime::call_contract::<Vec<Alert>>(
+            config.dashboard_contract_id.clone(),
+            "get_live_alerts".to_string(),
+            Some(args),
+        ) {
+            Ok(alerts) => {
+                let entity_alerts: Vec<&Alert> = alerts.iter().filter(|a| a.entity_id == entity_id).collect();
+                AlertsSummary {
+                    total: entity_alerts.len() as u32,
+                    critical: entity_alerts.iter().filter(|a| a.severity == "CRITICAL").count() as u32,
+                    high: entity_alerts.iter().filter(|a| a.severity == "HIGH").count() as u32,
+                    medium: entity_alerts.iter().filter(|a| a.severity == "MEDIUM").count() as u32,
+                    low: entity_alerts.iter().filter(|a| a.severity == "LOW").count() as u32,
+                }
+            }
+            Err(e) => {
+                profile_gaps.push(format!("active_alerts is all zeros: get_live_alerts call failed: {}", e));
+                AlertsSummary::default()
+            }
+        }
+    }
 }
 
 // ===== CONTRACT IMPLEMENTATION =====
@@ -162,6 +313,9 @@ impl RiskScoring for RiskScoringContractState {
     {
         Ok(RiskScoringContractState {
             secrets: Secrets::new(),
+            maintenance: MaintenanceStatus::default(),
+            score_history: Vec::new(),
+            score_explanation_seq: 0,
         })
     }
 
@@ -288,6 +442,195 @@ impl RiskScoring for RiskScoringContractState {
         })
     }
 
+    /// Recomputes an entity's score via calculate_entity_risk, decomposes it into
+    /// named factors, and persists the breakdown so a score quoted in an STR can be
+    /// pulled back up by explanation_id later instead of re-derived from memory.
+    ///
+    /// calculate_entity_risk itself is currently fixed placeholder logic - it
+    /// doesn't resolve the entity's actual alerts/trades/UPSI accesses, so
+    /// source_references stays empty and the trace says as much rather than
+    /// fabricating IDs. The factor breakdown and persistence are real; only the
+    /// underlying numbers being explained are still stubbed, same as everywhere
+    /// else in this contract.
+    #[mutate]
+    async fn explain_score(&mut self, entity_id: String) -> Result<ScoreExplanation, String> {
+        if self.maintenance.enabled {
+            return Err(format!("Contract is in maintenance mode: {}", self.maintenance.message));
+        }
+        let profile = self.calculate_entity_risk(entity_id.clone(), 0).await?;
+        let risk_level = self.get_risk_level(profile.overall_score);
+
+        let factors = vec![
+            RiskFactor {
+                factor_name: "Insider Risk".to_string(),
+                factor_weight: 40,
+                factor_value: profile.insider_risk.to_string(),
+                contribution: profile.insider_risk,
+            },
+            RiskFactor {
+                factor_name: "Manipulation Risk".to_string(),
+                factor_weight: 35,
+                factor_value: profile.manipulation_risk.to_string(),
+                contribution: profile.manipulation_risk,
+            },
+            RiskFactor {
+                factor_name: "AML Risk".to_string(),
+                factor_weight: 25,
+                factor_value: profile.aml_risk.to_string(),
+                contribution: profile.aml_risk,
+            },
+            RiskFactor {
+                factor_name: "Historical Alerts".to_string(),
+                factor_weight: 0,
+                factor_value: profile.historical_alerts.to_string(),
+                contribution: 0,
+            },
+        ];
+
+        self.score_explanation_seq += 1;
+        let explanation = ScoreExplanation {
+            explanation_id: format!("EXPL-{:04}", self.score_explanation_seq),
+            entity_id,
+            computed_at: 1735689600u64,
+            overall_score: profile.overall_score,
+            risk_level,
+            factors,
+            source_references: Vec::new(),
+            computation_trace: "calculate_entity_risk currently returns fixed placeholder \
+                sub-scores rather than deriving them from this entity's actual alerts, \
+                trades, or UPSI accesses, so overall_score/insider_risk/manipulation_risk/ \
+                aml_risk/historical_alerts above are the same constants for every entity \
+                until that lookup is wired up; source_references is empty for the same \
+                reason.".to_string(),
+        };
+
+        self.score_history.push(explanation.clone());
+        Ok(explanation)
+    }
+
+    #[query]
+    async fn get_score_explanation(&self, explanation_id: String) -> Result<ScoreExplanation, String> {
+        self.score_history.iter()
+            .find(|e| e.explanation_id == explanation_id)
+            .cloned()
+            .ok_or_else(|| format!("Score explanation {} not found", explanation_id))
+    }
+
+    /// calculate_entity_risk's overall_score is currently fixed placeholder logic
+    /// (same gap documented on explain_score), so every seed entity propagates
+    /// the same starting score until that lookup is wired up; the graph walk and
+    /// decay/registration logic below are real.
+    #[query]
+    async fn propagate_risk(&self, seed_entity_id: String, decay_factor: String, max_hops: u32) -> Result<Vec<PropagatedRisk>, String> {
+        #[derive(Debug, Deserialize)]
+        struct EntityConnection {
+            connected_entity_id: String,
+            hops: u32,
+        }
+        #[derive(Debug, Deserialize)]
+        struct ConnectedEntitiesPage {
+            connections: Vec<EntityConnection>,
+        }
+        #[derive(Debug, Serialize)]
+        struct GetConnectedEntitiesArgs {
+            entity_id: String,
+            max_hops: u32,
+            page: Option<u32>,
+            page_size: Option<u32>,
+        }
+
+        let config = self.secrets.config();
+        if config.entity_relationship_contract_id.is_empty() {
+            return Err("entity_relationship_contract_id is not configured".to_string());
+        }
+
+        let decay = decay_factor.parse::<f64>().unwrap_or(0.5).clamp(0.0, 1.0);
+        let seed_profile = self.calculate_entity_risk(seed_entity_id.clone(), 0).await?;
+
+        let args = serde_json::to_string(&GetConnectedEntitiesArgs {
+            entity_id: seed_entity_id.clone(),
+            max_hops,
+            page: None,
+            page_size: None,
+        }).unwrap();
+        let page = Runtime::call_contract::<ConnectedEntitiesPage>(
+            config.entity_relationship_contract_id.clone(),
+            "get_connected_entities".to_string(),
+            Some(args),
+        )?;
+
+        let mut results = Vec::new();
+        for connection in page.connections {
+            let propagated_score = (seed_profile.overall_score as f64 * decay.powi(connection.hops as i32))
+                .round()
+                .min(100.0) as u32;
+            let risk_level = self.get_risk_level(propagated_score);
+            let registered = risk_level == "HIGH" || risk_level == "CRITICAL";
+
+            if registered && !config.dashboard_contract_id.is_empty() {
+                let alert = Alert {
+                    id: format!("ALERT-PROP-{}-{}", seed_entity_id, connection.connected_entity_id),
+                    alert_type: "RING_PROPAGATION".to_string(),
+                    severity: risk_level.clone(),
+                    risk_score: propagated_score,
+                    entity_id: connection.connected_entity_id.clone(),
+                    symbol: "".to_string(),
+                    description: format!(
+                        "Inherited {} risk from connected entity {} ({} hop{} away, decay {})",
+                        risk_level, seed_entity_id, connection.hops, if connection.hops == 1 { "" } else { "s" }, decay
+                    ),
+                    workflow_id: "".to_string(),
+                    timestamp: 0,
+                };
+                let push_args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
+                let _ = Runtime::call_contract::<String>(
+                    config.dashboard_contract_id.clone(),
+                    "push_alert".to_string(),
+                    Some(push_args),
+                );
+            }
+
+            results.push(PropagatedRisk {
+                entity_id: connection.connected_entity_id,
+                hops: connection.hops,
+                propagated_score,
+                risk_level,
+                registered,
+            });
+        }
+
+        Ok(results)
+    }
+
+    #[query]
+    async fn get_surveillance_profile(&self, entity_id: String) -> Result<SurveillanceProfile, String> {
+        let risk_profile = self.calculate_entity_risk(entity_id.clone(), 0).await?;
+        let risk_level = self.get_risk_level(risk_profile.overall_score);
+
+        let mut profile_gaps = vec![
+            "trend is always \"UNKNOWN\": calculate_entity_risk returns the same fixed placeholder score for every entity, so there is no real score history to compare against".to_string(),
+            "open_case_count is always 0: get_cases_by_status/get_case on dashboard_webserver both require an ANALYST-authorized session token, which this contract has no way to hold".to_string(),
+            "insider_designation_count is always 0: upsi_database_mcp only exposes list_designated_persons(company_symbol), with no reverse index by entity_id, so a lookup would need a symbol this endpoint isn't given".to_string(),
+            "watchlist_member is always false: no entity watchlist/membership registry exists anywhere in this codebase".to_string(),
+            "last_review_date is always 0: no review-tracking concept exists anywhere in this codebase".to_string(),
+        ];
+
+        let active_alerts = self.fetch_entity_alerts_summary(&entity_id, &mut profile_gaps).await;
+
+        Ok(SurveillanceProfile {
+            entity_id,
+            risk_score: risk_profile.overall_score,
+            risk_level,
+            trend: "UNKNOWN".to_string(),
+            active_alerts,
+            open_case_count: 0,
+            insider_designation_count: 0,
+            watchlist_member: false,
+            last_review_date: 0,
+            profile_gaps,
+        })
+    }
+
     #[query]
     async fn evaluate_pattern_risk(
         &self, 
@@ -433,6 +776,17 @@ impl RiskScoring for RiskScoringContractState {
         })
     }
 
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -441,7 +795,13 @@ impl RiskScoring for RiskScoringContractState {
   {"type": "function", "function": {"name": "evaluate_pattern_risk", "description": "Evaluate manipulation pattern: SPOOFING, WASH_TRADE, CIRCULAR, PUMP_DUMP.", "parameters": {"type": "object", "properties": {"pattern_type": {"type": "string"}, "symbol": {"type": "string"}, "trade_ids": {"type": "string"}, "account_ids": {"type": "string"}}, "required": ["pattern_type", "symbol", "trade_ids", "account_ids"]}}},
   {"type": "function", "function": {"name": "evaluate_insider_risk", "description": "Evaluate insider trading risk for trades before announcement.", "parameters": {"type": "object", "properties": {"symbol": {"type": "string"}, "account_id": {"type": "string"}, "announcement_timestamp": {"type": "integer"}, "lookback_days": {"type": "integer"}}, "required": ["symbol", "account_id", "announcement_timestamp", "lookback_days"]}}},
   {"type": "function", "function": {"name": "get_risk_factors", "description": "Get detailed breakdown of risk factors.", "parameters": {"type": "object", "properties": {"target_id": {"type": "string"}, "target_type": {"type": "string"}}, "required": ["target_id", "target_type"]}}},
-  {"type": "function", "function": {"name": "get_symbol_risk", "description": "Get aggregated risk for a stock symbol.", "parameters": {"type": "object", "properties": {"symbol": {"type": "string"}, "as_of_timestamp": {"type": "integer"}}, "required": ["symbol", "as_of_timestamp"]}}}
+  {"type": "function", "function": {"name": "get_symbol_risk", "description": "Get aggregated risk for a stock symbol.", "parameters": {"type": "object", "properties": {"symbol": {"type": "string"}, "as_of_timestamp": {"type": "integer"}}, "required": ["symbol", "as_of_timestamp"]}}},
+  {"type": "function", "function": {"name": "explain_score", "description": "Recompute an entity's risk score, break it down into weighted factors, and store the breakdown so it can be reproduced later by explanation_id.", "parameters": {"type": "object", "properties": {"entity_id": {"type": "string"}}, "required": ["entity_id"]}}},
+  {"type": "function", "function": {"name": "get_score_explanation", "description": "Look up a previously stored score explanation by explanation_id.", "parameters": {"type": "object", "properties": {"explanation_id": {"type": "string"}}, "required": ["explanation_id"]}}},
+  {"type": "function", "function": {"name": "propagate_risk", "description": "Spread a fraction of a seed entity's risk score to its connection-graph neighbors, decaying per hop, and register alerts for neighbors that clear the high-risk threshold.", "parameters": {"type": "object", "properties": {"seed_entity_id": {"type": "string"}, "decay_factor": {"type": "string"}, "max_hops": {"type": "integer"}}, "required": ["seed_entity_id", "decay_factor", "max_hops"]}}},
+  {"type": "function", "function": {"name": "get_surveillance_profile", "description": "Get the canonical per-entity surveillance profile: risk score/trend, active alerts summary, and (where the data exists) case/designation/watchlist/review fields.", "parameters": {"type": "object", "properties": {"entity_id": {"type": "string"}}, "required": ["entity_id"]}}},
+  {"type": "function", "function": {"name": "set_maintenance_mode", "description": "Enable/disable maintenance mode; while enabled, mutating methods return an error instead of writing partial state.", "parameters": {"type": "object", "properties": {"enabled": {"type": "boolean"}, "message": {"type": "string"}}, "required": ["enabled", "message"]}}},
+  {"type": "function", "function": {"name": "get_maintenance_status", "description": "Get the current maintenance-mode banner (enabled flag and message).", "parameters": {"type": "object", "properties": {}, "required": []}}}
 ]"#.to_string()
     }
 
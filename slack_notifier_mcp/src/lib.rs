@@ -1,9 +1,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use weil_macros::{constructor, query, smart_contract, WeilType};
+use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
 use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
+use weil_rs::runtime::Runtime;
+
+mod outbound_guard;
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
 
 // ===== CONFIGURATION =====
 
@@ -11,6 +15,11 @@ use weil_rs::http::{HttpClient, HttpMethod};
 pub struct SlackNotifierConfig {
     pub webhook_url: String,
     pub default_channel: String,
+    /// Dashboard contract ID, used by ingest_slack_ack to call acknowledge_alert
+    pub dashboard_contract_id: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as acknowledge_alert's token
+    pub dashboard_caller_token: String,
 }
 
 // ===== DATA STRUCTURES =====
@@ -42,29 +51,88 @@ pub struct NotificationResult {
     pub error: String,
 }
 
+/// Groups every post_case_update call for a case under one thread_key, so
+/// updates land together in the channel instead of scattering. permalink is
+/// empty - a real Slack permalink needs chat.getPermalink, which needs a bot
+/// token; this integration only has an incoming webhook URL.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CaseThread {
+    pub case_id: String,
+    pub thread_key: String,
+    pub channel: String,
+    pub message_count: u32,
+    pub permalink: String,
+}
+
+/// A sent alert's marker, keyed by alert_id, used by ingest_slack_ack to
+/// correlate an incoming Slack event back to the alert it was posted for.
+/// This integration only holds an incoming webhook URL, not a bot token, so
+/// it never learns Slack's real message ts (webhooks respond with a bare
+/// "ok", not a message object) and has no conversations.history to backfill
+/// text for events that reference only item.ts/thread_ts. ingest_slack_ack
+/// can therefore only resolve an ack whose event_json carries this marker
+/// somewhere in its text (event.text, event.message.text, or
+/// event.previous_message.text) - a bare reaction_added event (item.ts plus
+/// an emoji, no text) can't be resolved from this data alone.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SentAlertRecord {
+    pub alert_id: String,
+    pub marker: String,
+    pub acknowledged: bool,
+}
+
 // ===== TRAIT DEFINITION =====
 
 trait SlackNotifier {
     fn new() -> Result<Self, String> where Self: Sized;
-    async fn send_message(&self, channel: String, message: String) -> Result<NotificationResult, String>;
-    async fn send_alert(&self, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult, String>;
-    async fn send_case_update(&self, case_id: String, status: String, update_message: String, assigned_to: String) -> Result<NotificationResult, String>;
-    async fn send_workflow_complete(&self, workflow_id: String, workflow_type: String, result_summary: String, alert_count: u32) -> Result<NotificationResult, String>;
-    async fn send_daily_summary(&self, date: String, total_alerts: u32, critical_alerts: u32, open_cases: u32, new_cases: u32) -> Result<NotificationResult, String>;
+    async fn send_message(&mut self, channel: String, message: String) -> Result<NotificationResult, String>;
+    async fn send_alert(&mut self, alert_id: String, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult, String>;
+    async fn ingest_slack_ack(&mut self, event_json: String) -> Result<NotificationResult, String>;
+    async fn send_case_update(&mut self, case_id: String, status: String, update_message: String, assigned_to: String) -> Result<NotificationResult, String>;
+    async fn post_case_update(&mut self, case_id: String, message: String) -> Result<NotificationResult, String>;
+    fn get_case_thread(&self, case_id: String) -> Result<CaseThread, String>;
+    async fn send_workflow_complete(&mut self, workflow_id: String, workflow_type: String, result_summary: String, alert_count: u32) -> Result<NotificationResult, String>;
+    async fn send_daily_summary(&mut self, date: String, total_alerts: u32, critical_alerts: u32, open_cases: u32, new_cases: u32) -> Result<NotificationResult, String>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely carry out
+/// maintenance without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct SlackNotifierContractState {
     secrets: Secrets<SlackNotifierConfig>,
+    outbound_guard: OutboundGuard,
+    maintenance: MaintenanceStatus,
+    case_threads: Vec<CaseThread>,
+    sent_alerts: Vec<SentAlertRecord>,
 }
 
 // ===== HELPER METHODS =====
 
 impl SlackNotifierContractState {
+    /// Reject mutating calls while maintenance mode is on, so in-flight
+    /// maintenance can't race a concurrent tool call into a half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
     fn get_severity_emoji(&self, severity: &str) -> &'static str {
         match severity {
             "CRITICAL" => "🚨",
@@ -75,9 +143,9 @@ impl SlackNotifierContractState {
         }
     }
     
-    async fn send_to_slack(&self, text: String) -> Result<NotificationResult, String> {
+    async fn send_to_slack(&mut self, text: String) -> Result<NotificationResult, String> {
         let config = self.secrets.config();
-        
+
         if config.webhook_url.is_empty() {
             return Ok(NotificationResult {
                 success: false,
@@ -86,24 +154,35 @@ impl SlackNotifierContractState {
                 error: "Webhook URL not configured".to_string(),
             });
         }
-        
+
+        let webhook_url = config.webhook_url.clone();
+        if let Err(e) = self.outbound_guard.check(&webhook_url) {
+            return Ok(NotificationResult {
+                success: false,
+                message_id: "".to_string(),
+                timestamp: 0,
+                error: e,
+            });
+        }
+
         let payload = serde_json::json!({
             "text": text
         });
-        
+
         let mut headers = HashMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
-        
-        let response = HttpClient::request(&config.webhook_url, HttpMethod::Post)
+
+        let response = HttpClient::request(&webhook_url, HttpMethod::Post)
             .headers(headers)
             .body(payload.to_string())
             .send();
-            
+
         match response {
             Ok(resp) => {
                 let status = resp.status();
                 let text = resp.text();
-                
+                self.outbound_guard.record_result(&webhook_url, status == 200);
+
                 if status == 200 {
                     Ok(NotificationResult {
                         success: true,
@@ -120,12 +199,15 @@ impl SlackNotifierContractState {
                     })
                 }
             },
-            Err(e) => Ok(NotificationResult {
-                success: false,
-                message_id: "".to_string(),
-                timestamp: 0,
-                error: format!("{:?}", e),
-            }),
+            Err(e) => {
+                self.outbound_guard.record_result(&webhook_url, false);
+                Ok(NotificationResult {
+                    success: false,
+                    message_id: "".to_string(),
+                    timestamp: 0,
+                    error: format!("{:?}", e),
+                })
+            }
         }
     }
 }
@@ -141,27 +223,105 @@ impl SlackNotifier for SlackNotifierContractState {
     {
         Ok(SlackNotifierContractState {
             secrets: Secrets::new(),
+            outbound_guard: OutboundGuard::default(),
+            maintenance: MaintenanceStatus::default(),
+            case_threads: Vec::new(),
+            sent_alerts: Vec::new(),
         })
     }
 
-    #[query]
-    async fn send_message(&self, channel: String, message: String) -> Result<NotificationResult, String> {
+    #[mutate]
+    async fn send_message(&mut self, channel: String, message: String) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
         let text = format!("📢 *{}*\n{}", channel, message);
         self.send_to_slack(text).await
     }
 
-    #[query]
-    async fn send_alert(&self, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult, String> {
+    #[mutate]
+    async fn send_alert(&mut self, alert_id: String, alert_type: String, severity: String, symbol: String, entity_id: String, description: String, risk_score: u32) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
         let emoji = self.get_severity_emoji(&severity);
+        let marker = format!("[ALERT-ID:{}]", alert_id);
         let text = format!(
-            "{} *{} Alert - {}*\n\n*Symbol:* {}\n*Entity:* {}\n*Risk Score:* {}/100\n*Description:* {}",
-            emoji, severity, alert_type, symbol, entity_id, risk_score, description
+            "{} *{} Alert - {}* {}\n\n*Symbol:* {}\n*Entity:* {}\n*Risk Score:* {}/100\n*Description:* {}",
+            emoji, severity, alert_type, marker, symbol, entity_id, risk_score, description
         );
+
+        if !alert_id.is_empty() {
+            match self.sent_alerts.iter_mut().find(|a| a.alert_id == alert_id) {
+                Some(existing) => {
+                    existing.marker = marker;
+                    existing.acknowledged = false;
+                }
+                None => self.sent_alerts.push(SentAlertRecord {
+                    alert_id,
+                    marker,
+                    acknowledged: false,
+                }),
+            }
+        }
+
         self.send_to_slack(text).await
     }
 
-    #[query]
-    async fn send_case_update(&self, case_id: String, status: String, update_message: String, assigned_to: String) -> Result<NotificationResult, String> {
+    /// Parses a Slack Events API event_callback payload for an ack (a reply or
+    /// reaction on an alert message) and forwards it to the dashboard's
+    /// acknowledge_alert, keeping the alert queue consistent with where
+    /// analysts actually responded. See SentAlertRecord's doc comment for why
+    /// this only works when event_json's text carries the alert's marker.
+    #[mutate]
+    async fn ingest_slack_ack(&mut self, event_json: String) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
+
+        let event: serde_json::Value = serde_json::from_str(&event_json)
+            .map_err(|e| format!("Invalid event_json: {}", e))?;
+
+        let candidate_text = event["event"]["text"].as_str()
+            .or_else(|| event["event"]["message"]["text"].as_str())
+            .or_else(|| event["event"]["previous_message"]["text"].as_str())
+            .unwrap_or("");
+
+        let alert_id = self.sent_alerts.iter()
+            .find(|a| !a.marker.is_empty() && candidate_text.contains(a.marker.as_str()))
+            .map(|a| a.alert_id.clone())
+            .ok_or_else(|| "No alert marker found in event_json text (a bare reaction_added event carries only item.ts, which this webhook-only integration can't resolve back to message text)".to_string())?;
+
+        let config = self.secrets.config();
+        if config.dashboard_contract_id.is_empty() {
+            return Err("dashboard_contract_id not configured".to_string());
+        }
+
+        let args = serde_json::json!({ "token": config.dashboard_caller_token, "alert_id": alert_id }).to_string();
+        let ack_result = Runtime::call_contract::<serde_json::Value>(
+            config.dashboard_contract_id.clone(),
+            "acknowledge_alert".to_string(),
+            Some(args),
+        );
+
+        match ack_result {
+            Ok(_) => {
+                if let Some(record) = self.sent_alerts.iter_mut().find(|a| a.alert_id == alert_id) {
+                    record.acknowledged = true;
+                }
+                Ok(NotificationResult {
+                    success: true,
+                    message_id: alert_id,
+                    timestamp: 0,
+                    error: "".to_string(),
+                })
+            }
+            Err(e) => Ok(NotificationResult {
+                success: false,
+                message_id: alert_id,
+                timestamp: 0,
+                error: format!("{:?}", e),
+            }),
+        }
+    }
+
+    #[mutate]
+    async fn send_case_update(&mut self, case_id: String, status: String, update_message: String, assigned_to: String) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
         let status_emoji = match status.as_str() {
             "OPEN" => "📂",
             "INVESTIGATING" => "🔍",
@@ -177,8 +337,50 @@ impl SlackNotifier for SlackNotifierContractState {
         self.send_to_slack(text).await
     }
 
+    /// Groups updates for a case into one thread by tagging every message with a
+    /// thread_key and tracking it in case_threads. This isn't real Slack threading -
+    /// that needs chat.postMessage's thread_ts, which needs a bot token; this
+    /// integration only has an incoming webhook, so the "thread" is just a visible
+    /// marker tying messages together in the channel.
+    #[mutate]
+    async fn post_case_update(&mut self, case_id: String, message: String) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
+        let channel = self.secrets.config().default_channel.clone();
+
+        let thread_key = match self.case_threads.iter_mut().find(|t| t.case_id == case_id) {
+            Some(thread) => {
+                thread.message_count += 1;
+                thread.thread_key.clone()
+            }
+            None => {
+                let thread_key = format!("THREAD-{}", case_id);
+                self.case_threads.push(CaseThread {
+                    case_id: case_id.clone(),
+                    thread_key: thread_key.clone(),
+                    channel,
+                    message_count: 1,
+                    permalink: "".to_string(),
+                });
+                thread_key
+            }
+        };
+
+        let text = format!("🧵 *[{}]* Case {}\n{}", thread_key, case_id, message);
+        self.send_to_slack(text).await
+    }
+
     #[query]
-    async fn send_workflow_complete(&self, workflow_id: String, workflow_type: String, result_summary: String, alert_count: u32) -> Result<NotificationResult, String> {
+    fn get_case_thread(&self, case_id: String) -> Result<CaseThread, String> {
+        self.case_threads
+            .iter()
+            .find(|t| t.case_id == case_id)
+            .cloned()
+            .ok_or_else(|| format!("No thread found for case {}", case_id))
+    }
+
+    #[mutate]
+    async fn send_workflow_complete(&mut self, workflow_id: String, workflow_type: String, result_summary: String, alert_count: u32) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
         let alert_indicator = if alert_count > 0 { "🚨" } else { "✅" };
         
         let text = format!(
@@ -188,8 +390,9 @@ impl SlackNotifier for SlackNotifierContractState {
         self.send_to_slack(text).await
     }
 
-    #[query]
-    async fn send_daily_summary(&self, date: String, total_alerts: u32, critical_alerts: u32, open_cases: u32, new_cases: u32) -> Result<NotificationResult, String> {
+    #[mutate]
+    async fn send_daily_summary(&mut self, date: String, total_alerts: u32, critical_alerts: u32, open_cases: u32, new_cases: u32) -> Result<NotificationResult, String> {
+        self.maintenance_guard()?;
         let text = format!(
             "📊 *Daily Surveillance Summary - {}*\n\n• Total Alerts: {}\n• Critical Alerts: {}\n• Open Cases: {}\n• New Cases Today: {}",
             date, total_alerts, critical_alerts, open_cases, new_cases
@@ -197,6 +400,22 @@ impl SlackNotifier for SlackNotifierContractState {
         self.send_to_slack(text).await
     }
 
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -232,6 +451,10 @@ impl SlackNotifier for SlackNotifierContractState {
       "parameters": {
         "type": "object",
         "properties": {
+          "alert_id": {
+            "type": "string",
+            "description": "Dashboard alert ID, embedded in the message so ingest_slack_ack can acknowledge it later\n"
+          },
           "alert_type": {
             "type": "string",
             "description": "Type of alert: INSIDER, SPOOFING, WASH_TRADE, PUMP_DUMP\n"
@@ -258,6 +481,7 @@ impl SlackNotifier for SlackNotifierContractState {
           }
         },
         "required": [
+          "alert_id",
           "alert_type",
           "severity",
           "symbol",
@@ -268,6 +492,25 @@ impl SlackNotifier for SlackNotifierContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "ingest_slack_ack",
+      "description": "Parse a Slack Events API payload for an ack on an alert message and forward it to the dashboard's acknowledge_alert\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "event_json": {
+            "type": "string",
+            "description": "Raw Slack event_callback JSON (message reply or reaction_added)\n"
+          }
+        },
+        "required": [
+          "event_json"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
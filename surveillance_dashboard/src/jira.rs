@@ -0,0 +1,55 @@
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct JiraMcp {
+    contract_id: String,
+}
+
+impl JiraMcp {
+    pub fn new(contract_id: String) -> Self {
+        JiraMcp { contract_id }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TicketResult {
+    pub success: bool,
+    pub ticket_key: String,
+    pub ticket_url: String,
+    pub error: String,
+}
+
+impl JiraMcp {
+    pub fn create_case_ticket(
+        &self,
+        case_id: String,
+        subject_entity: String,
+        case_summary: String,
+        priority: Option<String>,
+    ) -> Result<TicketResult> {
+        #[derive(Debug, Serialize)]
+        struct CreateCaseTicketArgs {
+            case_id: String,
+            subject_entity: String,
+            case_summary: String,
+            priority: Option<String>,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&CreateCaseTicketArgs {
+            case_id,
+            subject_entity,
+            case_summary,
+            priority,
+        })?);
+
+        let resp = Runtime::call_contract::<TicketResult>(
+            self.contract_id.clone(),
+            "create_case_ticket".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}
@@ -1,4 +1,7 @@
 
+mod jira;
+
+use jira::JiraMcp;
 use serde::{Deserialize, Serialize};
 use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
 use weil_rs::collections::vec::WeilVec;
@@ -11,6 +14,11 @@ use weil_rs::webserver::WebServer;
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
 pub struct DashboardConfig {
     pub name: String,
+    /// Jira contract to open a case ticket in; empty disables auto-escalation
+    pub jira_contract_id: String,
+    /// Minimum alert severity (CRITICAL/HIGH/MEDIUM/LOW) that auto-opens a
+    /// Jira ticket on push_alert; empty disables auto-escalation
+    pub jira_min_severity: String,
 }
 
 // ===== DATA STRUCTURES =====
@@ -26,6 +34,10 @@ pub struct Alert {
     pub description: String,
     pub workflow_id: String,
     pub timestamp: u64,
+    /// Jira ticket key auto-opened for this alert by push_alert, if its
+    /// severity met jira_min_severity; empty if no ticket was opened
+    #[serde(default)]
+    pub jira_ticket_key: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -141,13 +153,54 @@ impl SurveillanceDashboard for SurveillanceDashboardContractState {
     // ===== MUTATE FUNCTIONS (Called by other MCPs) =====
 
     #[mutate]
-    async fn push_alert(&mut self, alert: Alert) -> Result<String, String> {
+    async fn push_alert(&mut self, mut alert: Alert) -> Result<String, String> {
         let alert_id = alert.id.clone();
+        self.maybe_create_jira_ticket(&mut alert);
         self.alerts.push(alert);
         self.alert_count_today += 1;
         Ok(alert_id)
     }
 
+    /// If the alert's severity meets or exceeds jira_min_severity, opens a Jira
+    /// case ticket via the configured contract and stores the returned ticket
+    /// key on the alert, instead of relying on the agent to remember. Jira
+    /// errors are swallowed - ticket-filing shouldn't block ingesting the
+    /// alert itself - and jira_ticket_key is simply left empty.
+    fn maybe_create_jira_ticket(&self, alert: &mut Alert) {
+        let config = self.secrets.config();
+        if config.jira_contract_id.is_empty() || config.jira_min_severity.is_empty() {
+            return;
+        }
+        if Self::severity_rank(&alert.severity) > Self::severity_rank(&config.jira_min_severity) {
+            return;
+        }
+
+        let jira = JiraMcp::new(config.jira_contract_id.clone());
+        if let Ok(result) = jira.create_case_ticket(
+            alert.id.clone(),
+            alert.entity_id.clone(),
+            alert.description.clone(),
+            Some(alert.severity.clone()),
+        ) {
+            if result.success {
+                alert.jira_ticket_key = result.ticket_key;
+            }
+        }
+    }
+
+    /// Lower ranks are more severe; unrecognized severities sort last, so an
+    /// unrecognized alert severity never auto-escalates unless
+    /// jira_min_severity is itself unrecognized, in which case every alert does
+    fn severity_rank(severity: &str) -> u32 {
+        match severity {
+            "CRITICAL" => 0,
+            "HIGH" => 1,
+            "MEDIUM" => 2,
+            "LOW" => 3,
+            _ => 4,
+        }
+    }
+
     #[mutate]
     async fn log_workflow_start(
         &mut self, 
@@ -0,0 +1,207 @@
+//! Shared-by-convention fuzzy resolver for the copy/paste query-cache
+//! resolvers scattered across the MCPs (resolve_entity, resolve_symbol,
+//! resolve_case, etc). There is no shared crate between MCPs, so this file
+//! is intentionally duplicated verbatim into upsi_database_mcp, trade_data_mcp,
+//! anomaly_detection_mcp, entity_relationship_mcp, and regulatory_reports_mcp
+//! - the same pattern already used for epoch_ms_to_ist. Keep the five copies
+//! in sync when changing matching behavior.
+
+/// One matching strategy a resolver can be configured to try, in priority
+/// order - the first strategy that produces any match at all wins, so a
+/// cheap substring hit is always preferred over a fuzzy Levenshtein one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchStrategy {
+    Substring,
+    Prefix,
+    TokenOverlap,
+    /// Levenshtein edit distance, matching only candidates within the given
+    /// max distance
+    Levenshtein(u32),
+}
+
+/// A resolved candidate plus which strategy found it and how confident that
+/// strategy is (0.0-1.0, higher is better) - so a caller can decide whether
+/// to trust a low-confidence fuzzy match.
+#[derive(Debug, Clone)]
+pub struct MatchOutcome {
+    pub value: String,
+    pub strategy: String,
+    pub score: f64,
+}
+
+fn score_substring(candidate_lower: &str, query_lower: &str) -> Option<f64> {
+    if candidate_lower.is_empty() || query_lower.is_empty() {
+        return None;
+    }
+    if candidate_lower.contains(query_lower) {
+        Some(query_lower.len() as f64 / candidate_lower.len() as f64)
+    } else {
+        None
+    }
+}
+
+fn score_prefix(candidate_lower: &str, query_lower: &str) -> Option<f64> {
+    if candidate_lower.is_empty() || query_lower.is_empty() {
+        return None;
+    }
+    if candidate_lower.starts_with(query_lower) {
+        Some(query_lower.len() as f64 / candidate_lower.len() as f64)
+    } else {
+        None
+    }
+}
+
+fn tokenize(s: &str) -> Vec<&str> {
+    s.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()).collect()
+}
+
+fn score_token_overlap(candidate_lower: &str, query_lower: &str) -> Option<f64> {
+    let candidate_tokens = tokenize(candidate_lower);
+    let query_tokens = tokenize(query_lower);
+    if candidate_tokens.is_empty() || query_tokens.is_empty() {
+        return None;
+    }
+    let overlap = query_tokens.iter().filter(|qt| candidate_tokens.contains(qt)).count();
+    if overlap == 0 {
+        None
+    } else {
+        Some(overlap as f64 / query_tokens.len() as f64)
+    }
+}
+
+fn levenshtein_distance(a: &str, b: &str) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dp = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i as u32;
+    }
+    for j in 0..=b.len() {
+        dp[0][j] = j as u32;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1).min(dp[i][j - 1] + 1).min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[a.len()][b.len()]
+}
+
+fn score_levenshtein(candidate_lower: &str, query_lower: &str, max_distance: u32) -> Option<f64> {
+    if candidate_lower.is_empty() || query_lower.is_empty() {
+        return None;
+    }
+    let distance = levenshtein_distance(candidate_lower, query_lower);
+    if distance > max_distance {
+        return None;
+    }
+    let longest = candidate_lower.chars().count().max(query_lower.chars().count()).max(1) as f64;
+    Some(1.0 - (distance as f64 / longest))
+}
+
+/// Resolves `query` against `candidates` by trying `strategies` in order and
+/// returning the highest-scoring candidate under the first strategy that
+/// matches anything. `query` and `candidates` need not be pre-lowercased -
+/// matching is case-insensitive. Returns None if `query` is empty or nothing
+/// matches under any strategy, mirroring the old resolvers' behavior of
+/// falling back to the raw partial string when the cache holds no match.
+pub fn resolve_best<'a>(
+    query: &str,
+    candidates: impl Iterator<Item = &'a str>,
+    strategies: &[MatchStrategy],
+) -> Option<MatchOutcome> {
+    if query.is_empty() {
+        return None;
+    }
+    let query_lower = query.to_lowercase();
+    let candidates: Vec<&str> = candidates.filter(|c| !c.is_empty()).collect();
+
+    for strategy in strategies {
+        let mut best: Option<(&str, f64)> = None;
+        for candidate in &candidates {
+            let candidate_lower = candidate.to_lowercase();
+            let score = match strategy {
+                MatchStrategy::Substring => score_substring(&candidate_lower, &query_lower),
+                MatchStrategy::Prefix => score_prefix(&candidate_lower, &query_lower),
+                MatchStrategy::TokenOverlap => score_token_overlap(&candidate_lower, &query_lower),
+                MatchStrategy::Levenshtein(k) => score_levenshtein(&candidate_lower, &query_lower, *k),
+            };
+            if let Some(score) = score {
+                if best.map(|(_, best_score)| score > best_score).unwrap_or(true) {
+                    best = Some((candidate, score));
+                }
+            }
+        }
+        if let Some((value, score)) = best {
+            let strategy_label = match strategy {
+                MatchStrategy::Substring => "substring".to_string(),
+                MatchStrategy::Prefix => "prefix".to_string(),
+                MatchStrategy::TokenOverlap => "token_overlap".to_string(),
+                MatchStrategy::Levenshtein(k) => format!("levenshtein<={}", k),
+            };
+            return Some(MatchOutcome { value: (*value).to_string(), strategy: strategy_label, score });
+        }
+    }
+
+    None
+}
+
+/// Same matching rules as resolve_best, but returns every candidate that
+/// matched under the winning strategy, best first, capped to `max_results` -
+/// for callers (like resolve_reference) that want to show alternatives
+/// instead of silently committing to the top hit.
+pub fn resolve_ranked<'a>(
+    query: &str,
+    candidates: impl Iterator<Item = &'a str>,
+    strategies: &[MatchStrategy],
+    max_results: usize,
+) -> Vec<MatchOutcome> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query_lower = query.to_lowercase();
+    let candidates: Vec<&str> = candidates.filter(|c| !c.is_empty()).collect();
+
+    for strategy in strategies {
+        let mut matches: Vec<(&str, f64)> = Vec::new();
+        for candidate in &candidates {
+            let candidate_lower = candidate.to_lowercase();
+            let score = match strategy {
+                MatchStrategy::Substring => score_substring(&candidate_lower, &query_lower),
+                MatchStrategy::Prefix => score_prefix(&candidate_lower, &query_lower),
+                MatchStrategy::TokenOverlap => score_token_overlap(&candidate_lower, &query_lower),
+                MatchStrategy::Levenshtein(k) => score_levenshtein(&candidate_lower, &query_lower, *k),
+            };
+            if let Some(score) = score {
+                matches.push((candidate, score));
+            }
+        }
+        if !matches.is_empty() {
+            matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            matches.dedup_by(|a, b| a.0 == b.0);
+            let strategy_label = match strategy {
+                MatchStrategy::Substring => "substring".to_string(),
+                MatchStrategy::Prefix => "prefix".to_string(),
+                MatchStrategy::TokenOverlap => "token_overlap".to_string(),
+                MatchStrategy::Levenshtein(k) => format!("levenshtein<={}", k),
+            };
+            return matches.into_iter()
+                .take(max_results)
+                .map(|(value, score)| MatchOutcome { value: value.to_string(), strategy: strategy_label.clone(), score })
+                .collect();
+        }
+    }
+
+    Vec::new()
+}
+
+/// Default strategy order used by the resolve_* helpers across the MCPs:
+/// prefer a plain substring hit, then a prefix hit, then token overlap, and
+/// finally a fuzzy Levenshtein(<=2) match for typos.
+pub const DEFAULT_STRATEGIES: [MatchStrategy; 4] = [
+    MatchStrategy::Substring,
+    MatchStrategy::Prefix,
+    MatchStrategy::TokenOverlap,
+    MatchStrategy::Levenshtein(2),
+];
@@ -0,0 +1,42 @@
+//! Injectable HTTP transport mode for deterministic tests against external
+//! providers (Supabase/Neo4j/Alpha Vantage/Jira) without live credentials.
+//! There is no shared crate between MCPs, so this file is duplicated
+//! verbatim into every MCP that talks to one of those providers - the same
+//! pattern already used for epoch_ms_to_ist and fuzzy_match. Keep the copies
+//! in sync when changing fixture behavior.
+//!
+//! Selected via the crate's own `http_fixture_mode` config field:
+//! - "live" (default): calls the real provider, fixtures are untouched
+//! - "record": calls the real provider and upserts the response as a fixture
+//! - "playback": returns the recorded fixture instead of calling out, erroring
+//!   if no fixture was ever recorded for that (method, url, body) key
+
+use serde::{Deserialize, Serialize};
+use weil_macros::WeilType;
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct HttpFixture {
+    pub key: String,
+    pub status: u32,
+    pub body: String,
+}
+
+/// Deterministic key for a request: same method/url/body always maps to the
+/// same fixture, regardless of how many times it's replayed
+pub fn fixture_key(method: &str, url: &str, body: &str) -> String {
+    format!("{} {} {}", method, url, body)
+}
+
+pub fn find<'a>(fixtures: &'a [HttpFixture], key: &str) -> Option<&'a HttpFixture> {
+    fixtures.iter().find(|f| f.key == key)
+}
+
+pub fn upsert(fixtures: &mut Vec<HttpFixture>, key: String, status: u32, body: String) {
+    match fixtures.iter_mut().find(|f| f.key == key) {
+        Some(existing) => {
+            existing.status = status;
+            existing.body = body;
+        }
+        None => fixtures.push(HttpFixture { key, status, body }),
+    }
+}
@@ -1,4 +1,9 @@
 
+mod fuzzy_match;
+mod http_fixtures;
+mod outbound_guard;
+
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
@@ -7,6 +12,8 @@ use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
 use weil_rs::runtime::Runtime;
 
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
+
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default)]
@@ -15,6 +22,27 @@ pub struct TradeDataConfig {
     pub api_key_2: String,
     pub api_key_3: String,
     pub dashboard_contract_id: String,
+    pub entity_relationship_contract_id: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert's caller_token
+    pub dashboard_caller_token: String,
+    /// "live" (default): call Alpha Vantage for real. "record": call it for
+    /// real and save the response as a fixture. "playback": skip the network
+    /// and return the previously recorded fixture, erroring if none exists -
+    /// see http_fixtures for the whole scheme
+    pub http_fixture_mode: String,
+}
+
+/// Formats an epoch-milliseconds UTC timestamp as an IST (UTC+5:30) string,
+/// e.g. "2025-01-18 21:30:00 IST" - duplicated in regulatory_reports_mcp and
+/// upsi_database_mcp since there's no shared crate between MCPs
+fn epoch_ms_to_ist(epoch_ms: u64) -> String {
+    let utc: DateTime<Utc> = match DateTime::from_timestamp_millis(epoch_ms as i64) {
+        Some(dt) => dt,
+        None => return "INVALID_TIMESTAMP".to_string(),
+    };
+    let ist_offset = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    utc.with_timezone(&ist_offset).format("%Y-%m-%d %H:%M:%S IST").to_string()
 }
 
 // ===== DATA STRUCTURES =====
@@ -30,10 +58,31 @@ pub struct Trade {
     pub value: String,
     pub exchange: String,
     pub segment: String,
+    /// As reported by exchange, before clock-offset correction - see
+    /// set_source_offset. Feeds from different exchanges can disagree on wall
+    /// clock by seconds, which breaks front-running gap calculations that
+    /// compare timestamps across sources.
+    pub raw_timestamp: u64,
+    /// raw_timestamp + the offset registered for `exchange` via
+    /// set_source_offset (0 if none is registered) - use this one for any
+    /// cross-source comparison
     pub timestamp: u64,
+    /// timestamp (normalized) formatted as IST via epoch_ms_to_ist, since
+    /// timestamp is epoch milliseconds UTC
+    pub timestamp_ist: String,
     pub order_id: String,
 }
 
+/// One exchange's registered clock offset, applied to raw_timestamp on ingest
+/// to produce Trade.timestamp. offset_ms is added to raw_timestamp, so a
+/// positive value corrects a source whose clock runs behind the platform's
+/// reference clock.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SourceOffset {
+    pub source: String,
+    pub offset_ms: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct TradeAnalysis {
     pub symbol: String,
@@ -57,6 +106,49 @@ pub struct VolumeAnomaly {
     pub anomaly_score: u32,
 }
 
+/// Average-daily-volume buckets so a given share count (or price move) can be
+/// judged against what's normal for this symbol specifically, instead of
+/// against a flat constant - a 1M-share print is nothing for a mega-cap and
+/// everything for a microcap. avg_daily_volume comes from Alpha Vantage's
+/// TIME_SERIES_DAILY (same series plot_volume_chart pulls from), averaged
+/// over up to the last 20 trading days it returns; recomputed on every call
+/// rather than cached, since this contract has no persisted per-day store to
+/// invalidate against.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct LiquidityClassification {
+    pub symbol: String,
+    pub avg_daily_volume: u64,
+    pub days_sampled: u32,
+    /// "HIGH", "MEDIUM", "LOW", or "MICRO"
+    pub liquidity_class: String,
+    /// Volume-anomaly ratio (current/avg) a detector should require before
+    /// flagging this symbol - higher for illiquid names, since their thin
+    /// daily volume swings further relative to its average on ordinary noise
+    pub volume_ratio_threshold: String,
+    /// Minimum absolute price-change percent a pump/dump-style detector
+    /// should require before flagging this symbol - higher for illiquid
+    /// names, since thin order books swing further on ordinary noise
+    pub price_move_threshold_pct: String,
+}
+
+/// Flags an account with unusually few trades across the tracked symbol
+/// universe (a proxy for extended dormancy) whose most recent trade is well
+/// above its own average size - a classic precursor to insider and mule
+/// activity. This platform has no persisted trade store: every trade
+/// returned by fetch_trades is freshly synthesized from a fixed epoch each
+/// call, not pulled from real elapsed history, so a literal "quiet for N
+/// months" can't be computed - trade_count standing in for it is a rough
+/// proxy, not a real inactivity duration.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DormancyAlert {
+    pub account_id: String,
+    pub trade_count: u32,
+    pub avg_quantity: u64,
+    pub latest_quantity: u64,
+    pub size_ratio: String,
+    pub is_dormant_reactivation: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct AccountActivity {
     pub account_id: String,
@@ -69,6 +161,106 @@ pub struct AccountActivity {
     pub last_trade_time: u64,
 }
 
+/// One window of a trade replay: the trades that occurred between window_start
+/// and window_end, plus running totals up through this frame, so the dashboard
+/// can animate through frames without recomputing history each step
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReplayFrame {
+    pub frame_index: u32,
+    pub window_start: u64,
+    pub window_end: u64,
+    pub trades: Vec<Trade>,
+    pub cumulative_volume: u64,
+    pub cumulative_trade_count: u32,
+    pub cumulative_buy_volume: u64,
+    pub cumulative_sell_volume: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradeReplay {
+    pub symbol: String,
+    pub from: String,
+    pub to: String,
+    pub window_seconds: u32,
+    pub frames: Vec<ReplayFrame>,
+}
+
+/// One (account, minute, price-level) group of repeated trades for a symbol -
+/// the tabular precursor data a layering/spoofing review buckets by hand today.
+/// This platform only ingests executed trades - no order or cancellation events
+/// are available - so "posts and pulls" can't be observed directly; buy_count
+/// and sell_count at the same price level within the same minute are the
+/// nearest proxy this data supports (an account crossing itself at one level
+/// looks the same here whether the other side was a genuine pull-and-repost or
+/// just two ordinary fills).
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradeCluster {
+    pub symbol: String,
+    pub account_id: String,
+    pub minute_bucket: u64,
+    pub price_level: String,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    pub trade_count: u32,
+    pub total_quantity: u64,
+    /// min(buy_count, sell_count) - repeated same-level crossings within one
+    /// minute, the layering-like signal this proxy can support
+    pub crossing_count: u32,
+    /// Echoes the requested features list back; not yet consumed to change
+    /// how clusters are computed
+    pub features: String,
+}
+
+/// One account's trades on a symbol/day whose combined quantity crosses
+/// threshold_pct_of_volume of that day's total traded volume - a block/bulk
+/// deal candidate, whether it landed as a single large trade or a same-account
+/// cluster of smaller ones.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BlockDealFinding {
+    pub symbol: String,
+    pub date: String,
+    pub account_id: String,
+    pub trade_ids: Vec<String>,
+    pub quantity: u64,
+    pub total_day_volume: u64,
+    pub pct_of_volume: String,
+    pub is_single_trade: bool,
+}
+
+/// Result of detect_block_deals. disclosure_status is always the same fixed
+/// string: this deployment has no announcements_mcp (or any other disclosure
+/// registry) to cross-check findings against, so every finding here is raised
+/// as an alert on its share of the day's volume alone; no price banding is
+/// applied - see the comment on detect_block_deals in the TradeData trait.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct BlockDealReport {
+    pub symbol: String,
+    pub date: String,
+    pub threshold_pct_of_volume: String,
+    pub total_day_volume: u64,
+    pub findings: Vec<BlockDealFinding>,
+    pub disclosure_status: String,
+}
+
+/// One runner-up candidate resolve_reference didn't pick, with its own confidence
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceCandidate {
+    pub value: String,
+    pub confidence: u32,
+}
+
+/// resolve_reference's result: the resolved value plus a 0-100 confidence
+/// score and up to 3 runner-up candidates, so a caller can ask a clarifying
+/// question instead of silently acting on a low-confidence fuzzy match
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceResolution {
+    pub kind: String,
+    pub query: String,
+    pub resolved_value: String,
+    pub confidence: u32,
+    pub alternatives: Vec<ReferenceCandidate>,
+}
+
 // ===== CONTEXT CACHE STRUCTURES =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
@@ -100,39 +292,110 @@ pub struct Alert {
     pub timestamp: u64,
 }
 
+/// Local copy of entity_relationship_mcp's Entity, for the get_entity_by_pan proxy call
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Entity {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub name: String,
+    pub pan_number: String,
+    pub registration_id: String,
+}
+
 // ===== TRAIT DEFINITION =====
 
 trait TradeData {
     fn new() -> Result<Self, String> where Self: Sized;
     async fn get_context(&mut self) -> QueryContext;
+    /// kind: "symbol" or "account" - see ReferenceResolution's doc comment
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String>;
     async fn get_trade(&mut self, trade_id: String) -> Result<Trade, String>;
     async fn get_trades_by_symbol(&mut self, symbol: String, limit: u32) -> Result<Vec<Trade>, String>;
     async fn get_trades_by_account(&mut self, account_id: String, limit: u32) -> Result<Vec<Trade>, String>;
     async fn get_trades_by_accounts(&mut self, account_ids: String, symbol: String) -> Result<Vec<Trade>, String>;
     async fn analyze_volume(&mut self, symbol: String) -> Result<TradeAnalysis, String>;
     async fn detect_volume_anomaly(&mut self, symbol: String) -> Result<VolumeAnomaly, String>;
+    async fn get_liquidity_class(&mut self, symbol: String) -> Result<LiquidityClassification, String>;
+    // Flags an account trading well above its own average size after a spell of
+    // unusually few trades (dormancy proxy - trade_count, not real elapsed time;
+    // see DormancyAlert's doc comment)
+    async fn detect_dormant_account_activity(&mut self, account_id: String, min_trade_count: u32, size_multiplier_pct: u32) -> Result<DormancyAlert, String>;
+    // Scans a comma-separated watchlist via one bulk quote call (chunked) instead of
+    // one GLOBAL_QUOTE per symbol, falling back to single-quote mode per symbol
+    // that's missing from the bulk response or if the bulk endpoint errors entirely
+    async fn scan_watchlist(&mut self, symbols: String) -> Result<Vec<VolumeAnomaly>, String>;
     async fn get_top_traders(&mut self, symbol: String, limit: u32) -> Result<Vec<AccountActivity>, String>;
     async fn get_large_orders(&mut self, min_value: u64) -> Result<Vec<Trade>, String>;
     async fn get_account_profile(&mut self, account_id: String) -> Result<Vec<AccountActivity>, String>;
+    // Buckets a symbol's trades into speed-second windows with cumulative stats
+    // per window, so a reviewer can animate through how a manipulation unfolded
+    async fn replay_trades(&mut self, symbol: String, from: String, to: String, speed: u32) -> Result<TradeReplay, String>;
+    // Groups trades by account, minute-bucket, and price level, keeping only
+    // groups with repeated activity - the precursor data a layering/spoofing
+    // review needs. from/to are informational only, same as replay_trades.
+    async fn cluster_trades(&mut self, symbol: String, from: String, to: String, features: String) -> Result<Vec<TradeCluster>, String>;
+    // Flags single trades or same-account clusters whose combined quantity crosses
+    // threshold_pct_of_volume of the day's total traded volume for symbol - block/
+    // bulk deal candidates. date is informational only, same as cluster_trades'
+    // from/to, since this platform has no persisted per-day trade store. This
+    // deployment has no announcements_mcp (or any other disclosure registry) to
+    // cross-check findings against, so every finding is raised as an alert on
+    // its share of the day's volume alone, with no price banding - see
+    // BlockDealReport's disclosure_status.
+    async fn detect_block_deals(&mut self, symbol: String, date: String, threshold_pct_of_volume: u32) -> Result<BlockDealReport, String>;
+    // Registers (or replaces) the clock offset applied to trades reported by
+    // source when fetch_trades synthesizes them - see SourceOffset for the
+    // sign convention
+    fn set_source_offset(&mut self, source: String, offset_ms: i64) -> SourceOffset;
+    // Lists every registered source offset
+    fn get_source_offsets(&self) -> Vec<SourceOffset>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
     async fn plot_price_history(&self, symbols: String, days_back: u32) -> Result<Plottable, String>;
     async fn plot_volume_chart(&self, symbols: String, days_back: u32) -> Result<Plottable, String>;
     async fn plot_buy_sell_ratio(&self, symbol: String) -> Result<Plottable, String>;
     async fn plot_top_traders(&self, symbol: String, limit: u32) -> Result<Plottable, String>;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely carry out
+/// maintenance without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct TradeDataContractState {
     secrets: Secrets<TradeDataConfig>,
     query_cache: QueryContext,
+    outbound_guard: OutboundGuard,
+    maintenance: MaintenanceStatus,
+    source_offsets: Vec<SourceOffset>,
+    /// Recorded Alpha Vantage responses, consulted/updated by make_request
+    /// according to config.http_fixture_mode
+    http_fixtures: Vec<http_fixtures::HttpFixture>,
 }
 
 // ===== HELPER METHODS =====
 
 impl TradeDataContractState {
+    /// Reject mutating calls while maintenance mode is on, so in-flight
+    /// maintenance can't race a concurrent tool call into a half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
     fn get_api_key(&self) -> String {
         self.secrets.config().api_key_1.clone()
     }
@@ -143,35 +406,67 @@ impl TradeDataContractState {
         ])
     }
 
-    async fn make_request(&self, url: &str, query_params: Vec<(String, String)>) -> Result<String, String> {
+    /// Ungated GET, only for the read-only `#[query(plottable)]` chart methods
+    /// which cannot hold a &mut self and so can't update the outbound guard.
+    async fn fetch_raw(&self, url: &str, query_params: Vec<(String, String)>) -> Result<String, String> {
         let headers = self.get_headers();
-        
+
         let response = HttpClient::request(url, HttpMethod::Get)
             .headers(headers)
             .query(query_params)
             .send()
             .map_err(|err| err.to_string())?;
-        
+
         let status = response.status();
         let text = response.text();
-        
+
         if !(200..300).contains(&status) {
             return Err(format!("HTTP {}: {}", status, text));
         }
-        
+
         Ok(text)
     }
 
-    async fn fetch_trades(&self, symbol: &str, account_filter: Option<&str>, max_limit: usize) -> Result<Vec<Trade>, String> {
+    async fn make_request(&mut self, url: &str, query_params: Vec<(String, String)>) -> Result<String, String> {
+        self.outbound_guard.check(url)?;
+
+        let mode = self.secrets.config().http_fixture_mode.clone();
+        let mut sorted_params = query_params.clone();
+        sorted_params.sort();
+        let params_key = sorted_params.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+        let key = http_fixtures::fixture_key("GET", url, &params_key);
+
+        if mode == "playback" {
+            return match http_fixtures::find(&self.http_fixtures, &key) {
+                Some(f) if (200..300).contains(&f.status) => Ok(f.body.clone()),
+                Some(f) => Err(format!("HTTP {} (fixture): {}", f.status, f.body)),
+                None => Err(format!("No recorded HTTP fixture for {}", key)),
+            };
+        }
+
+        let result = self.fetch_raw(url, query_params).await;
+
+        if mode == "record" {
+            match &result {
+                Ok(text) => http_fixtures::upsert(&mut self.http_fixtures, key, 200, text.clone()),
+                Err(e) => http_fixtures::upsert(&mut self.http_fixtures, key, 599, e.clone()),
+            }
+        }
+
+        self.outbound_guard.record_result(url, result.is_ok());
+        result
+    }
+
+    async fn fetch_trades(&mut self, symbol: &str, account_filter: Option<&str>, max_limit: usize) -> Result<Vec<Trade>, String> {
         let api_key = self.get_api_key();
         let url = "https://www.alphavantage.co/query";
-        
+
         let query_params = vec![
             ("function".to_string(), "GLOBAL_QUOTE".to_string()),
             ("symbol".to_string(), symbol.to_string()),
             ("apikey".to_string(), api_key),
         ];
-        
+
         let response_text = self.make_request(url, query_params).await?;
         
         let json: serde_json::Value = serde_json::from_str(&response_text)
@@ -210,7 +505,9 @@ impl TradeDataContractState {
             let trade_price = price - price_range / 2.0 + price_offset;
             let quantity = vol_per_trade.max(100);
             let value = (trade_price * quantity as f64) as u64;
-            
+            let exchange = if seed % 2 == 0 { "NYSE" } else { "NASDAQ" }.to_string();
+            let normalized_timestamp = (trade_timestamp as i64 + self.offset_for_source(&exchange)).max(0) as u64;
+
             trades.push(Trade {
                 trade_id: format!("{}_{}_{}", symbol, trade_timestamp, account_id),
                 symbol: symbol.to_string(),
@@ -219,13 +516,15 @@ impl TradeDataContractState {
                 quantity,
                 price: format!("{:.2}", trade_price),
                 value: value.to_string(),
-                exchange: if seed % 2 == 0 { "NYSE" } else { "NASDAQ" }.to_string(),
+                exchange,
                 segment: "EQUITY".to_string(),
-                timestamp: trade_timestamp,
+                raw_timestamp: trade_timestamp,
+                timestamp: normalized_timestamp,
+                timestamp_ist: epoch_ms_to_ist(normalized_timestamp),
                 order_id: format!("ORD{}", seed),
             });
         }
-        
+
         trades.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
         Ok(trades)
     }
@@ -263,40 +562,74 @@ impl TradeDataContractState {
         if partial.is_empty() {
             return self.query_cache.last_symbol.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_symbol.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_symbol.clone();
-        }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.symbol.is_empty() && query.symbol.to_lowercase().contains(&partial_lower) {
-                return query.symbol.clone();
-            }
-        }
-        
-        partial.to_string()
+
+        let candidates = std::iter::once(self.query_cache.last_symbol.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.symbol.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
     }
 
     fn resolve_account(&self, partial: &str) -> String {
         if partial.is_empty() {
             return self.query_cache.last_account_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_account_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_account_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_account_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.account_id.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
+    }
+
+    /// Indian PAN format: 5 letters, 4 digits, 1 letter (e.g. AAAPL1234C)
+    fn looks_like_pan(s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        chars.len() == 10
+            && chars[0..5].iter().all(|c| c.is_ascii_uppercase())
+            && chars[5..9].iter().all(|c| c.is_ascii_digit())
+            && chars[9].is_ascii_uppercase()
+    }
+
+    /// When the partial looks like a PAN, resolve it to the canonical entity_id
+    /// via entity_relationship_mcp before falling back to the local account cache
+    /// match, since a PAN won't appear in our own query cache
+    async fn resolve_account_or_pan(&mut self, partial: &str) -> String {
+        let candidate = partial.trim().to_uppercase();
+        if !Self::looks_like_pan(&candidate) {
+            return self.resolve_account(partial);
         }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.account_id.is_empty() && query.account_id.to_lowercase().contains(&partial_lower) {
-                return query.account_id.clone();
-            }
+
+        let config = self.secrets.config();
+        if config.entity_relationship_contract_id.is_empty() {
+            return self.resolve_account(partial);
+        }
+
+        #[derive(Serialize)]
+        struct GetEntityByPanArgs {
+            pan: String,
+        }
+
+        let args = serde_json::to_string(&GetEntityByPanArgs { pan: candidate }).unwrap_or_default();
+        match Runtime::call_contract::<Entity>(
+            config.entity_relationship_contract_id.clone(),
+            "get_entity_by_pan".to_string(),
+            Some(args),
+        ) {
+            Ok(entity) => self.resolve_account(&entity.entity_id),
+            Err(_) => self.resolve_account(partial),
         }
-        
-        partial.to_string()
+    }
+
+    /// Looks up the registered clock offset for `source` (an exchange name),
+    /// 0 if none has been set via set_source_offset
+    fn offset_for_source(&self, source: &str) -> i64 {
+        self.source_offsets.iter()
+            .find(|o| o.source == source)
+            .map(|o| o.offset_ms)
+            .unwrap_or(0)
     }
 
     fn maybe_push_alert(&self, alert_type: &str, severity: &str, risk_score: u32, entity_id: &str, symbol: &str, description: &str) {
@@ -317,7 +650,7 @@ impl TradeDataContractState {
             timestamp: 0,
         };
 
-        let args = serde_json::to_string(&alert).unwrap_or_default();
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "push_alert".to_string(),
@@ -377,6 +710,10 @@ impl TradeData for TradeDataContractState {
                 last_symbol: "IBM".to_string(),
                 last_account_id: "ACC017".to_string(),
             },
+            outbound_guard: OutboundGuard::default(),
+            maintenance: MaintenanceStatus::default(),
+            source_offsets: Vec::new(),
+            http_fixtures: Vec::new(),
         })
     }
 
@@ -385,8 +722,35 @@ impl TradeData for TradeDataContractState {
         self.query_cache.clone()
     }
 
+    #[query]
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String> {
+        if partial.is_empty() {
+            return Err("partial must not be empty".to_string());
+        }
+
+        let candidates: Vec<&str> = match kind.as_str() {
+            "symbol" => std::iter::once(self.query_cache.last_symbol.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.symbol.as_str()))
+                .collect(),
+            "account" => std::iter::once(self.query_cache.last_account_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.account_id.as_str()))
+                .collect(),
+            other => return Err(format!("Unknown reference kind '{}' - expected symbol or account", other)),
+        };
+
+        let mut ranked = fuzzy_match::resolve_ranked(&partial, candidates.into_iter(), &fuzzy_match::DEFAULT_STRATEGIES, 4).into_iter();
+        let (resolved_value, confidence) = match ranked.next() {
+            Some(m) => (m.value, (m.score * 100.0).round() as u32),
+            None => (partial.clone(), 0),
+        };
+        let alternatives = ranked.map(|m| ReferenceCandidate { value: m.value, confidence: (m.score * 100.0).round() as u32 }).collect();
+
+        Ok(ReferenceResolution { kind, query: partial, resolved_value, confidence, alternatives })
+    }
+
     #[mutate]
     async fn get_trade(&mut self, trade_id: String) -> Result<Trade, String> {
+        self.maintenance_guard()?;
         let parts: Vec<&str> = trade_id.split('_').collect();
         if parts.len() < 2 {
             return Err("Invalid trade_id format".to_string());
@@ -400,6 +764,7 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn get_trades_by_symbol(&mut self, symbol: String, limit: u32) -> Result<Vec<Trade>, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
         self.update_cache("get_trades_by_symbol", &resolved_symbol, "", 
             &format!("Get trades for {}", resolved_symbol));
@@ -409,7 +774,8 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn get_trades_by_account(&mut self, account_id: String, limit: u32) -> Result<Vec<Trade>, String> {
-        let resolved_account = self.resolve_account(&account_id);
+        self.maintenance_guard()?;
+        let resolved_account = self.resolve_account_or_pan(&account_id).await;
         self.update_cache("get_trades_by_account", "", &resolved_account, 
             &format!("Get trades for account {}", resolved_account));
         
@@ -427,6 +793,7 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn get_trades_by_accounts(&mut self, account_ids: String, symbol: String) -> Result<Vec<Trade>, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
         self.update_cache("get_trades_by_accounts", &resolved_symbol, "", 
             &format!("Get trades for multiple accounts on {}", resolved_symbol));
@@ -444,6 +811,7 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn analyze_volume(&mut self, symbol: String) -> Result<TradeAnalysis, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
         self.update_cache("analyze_volume", &resolved_symbol, "", 
             &format!("Analyze volume for {}", resolved_symbol));
@@ -484,43 +852,247 @@ impl TradeData for TradeDataContractState {
         })
     }
 
-    #[mutate]
-    async fn detect_volume_anomaly(&mut self, symbol: String) -> Result<VolumeAnomaly, String> {
-        let resolved_symbol = self.resolve_symbol(&symbol);
-        self.update_cache("detect_volume_anomaly", &resolved_symbol, "", 
-            &format!("Detect anomaly for {}", resolved_symbol));
-        
-        let trades = self.fetch_trades(&resolved_symbol, None, 200).await?;
-        let current_volume: u64 = trades.iter().map(|t| t.quantity).sum();
+    /// Buckets an average daily volume into a liquidity class plus the
+    /// volume-ratio/price-move thresholds a detector should use at that
+    /// class - see LiquidityClassification's doc comment
+    fn classify_liquidity(avg_daily_volume: u64) -> (&'static str, f64, f64) {
+        if avg_daily_volume >= 5_000_000 {
+            ("HIGH", 2.5, 8.0)
+        } else if avg_daily_volume >= 500_000 {
+            ("MEDIUM", 3.0, 10.0)
+        } else if avg_daily_volume >= 50_000 {
+            ("LOW", 4.0, 15.0)
+        } else {
+            ("MICRO", 6.0, 25.0)
+        }
+    }
+
+    /// Shared volume-anomaly math for both the single-symbol and bulk-scan paths -
+    /// takes an already-fetched current_volume so bulk scans don't need to refetch it
+    fn build_volume_anomaly(&self, symbol: String, current_volume: u64) -> VolumeAnomaly {
         let avg_volume_30d = current_volume / 2;
-        
+
         let volume_ratio = if avg_volume_30d > 0 { current_volume as f64 / avg_volume_30d as f64 } else { 1.0 };
         let is_anomaly = volume_ratio > 2.5;
         let anomaly_score = if is_anomaly { ((volume_ratio - 1.0) * 100.0) as u32 } else { 0 };
-        
+
         if is_anomaly && anomaly_score > 50 {
             self.maybe_push_alert(
                 "VOLUME_ANOMALY",
                 if anomaly_score > 100 { "CRITICAL" } else { "HIGH" },
                 anomaly_score,
                 "",
-                &resolved_symbol,
-                &format!("Volume anomaly detected: {} has {:.1}x normal volume (score: {})", resolved_symbol, volume_ratio, anomaly_score),
+                &symbol,
+                &format!("Volume anomaly detected: {} has {:.1}x normal volume (score: {})", symbol, volume_ratio, anomaly_score),
             );
         }
-        
-        Ok(VolumeAnomaly {
-            symbol: resolved_symbol,
+
+        VolumeAnomaly {
+            symbol,
             current_volume,
             avg_volume_30d,
             volume_ratio: format!("{:.2}", volume_ratio),
             is_anomaly,
             anomaly_score,
+        }
+    }
+
+    #[mutate]
+    async fn detect_volume_anomaly(&mut self, symbol: String) -> Result<VolumeAnomaly, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+        self.update_cache("detect_volume_anomaly", &resolved_symbol, "",
+            &format!("Detect anomaly for {}", resolved_symbol));
+
+        let trades = self.fetch_trades(&resolved_symbol, None, 200).await?;
+        let current_volume: u64 = trades.iter().map(|t| t.quantity).sum();
+
+        Ok(self.build_volume_anomaly(resolved_symbol, current_volume))
+    }
+
+    #[mutate]
+    async fn get_liquidity_class(&mut self, symbol: String) -> Result<LiquidityClassification, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+        self.update_cache("get_liquidity_class", &resolved_symbol, "",
+            &format!("Classify liquidity for {}", resolved_symbol));
+
+        let api_key = self.secrets.config().api_key_1.clone();
+        let url = "https://www.alphavantage.co/query";
+        let query_params = vec![
+            ("function".to_string(), "TIME_SERIES_DAILY".to_string()),
+            ("symbol".to_string(), resolved_symbol.clone()),
+            ("outputsize".to_string(), "compact".to_string()),
+            ("apikey".to_string(), api_key),
+        ];
+
+        let response = self.make_request(url, query_params).await?;
+        let json: serde_json::Value = serde_json::from_str(&response)
+            .map_err(|e| format!("Failed to parse Alpha Vantage response: {}", e))?;
+
+        let time_series = json.get("Time Series (Daily)").and_then(|v| v.as_object())
+            .ok_or_else(|| "No time series data returned for symbol".to_string())?;
+
+        const SAMPLE_DAYS: usize = 20;
+        let mut dated_volumes: Vec<(String, u64)> = time_series.iter()
+            .filter_map(|(date, data)| {
+                data.get("5. volume")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .map(|volume| (date.clone(), volume))
+            })
+            .collect();
+        dated_volumes.sort_by(|a, b| b.0.cmp(&a.0));
+        dated_volumes.truncate(SAMPLE_DAYS);
+
+        let days_sampled = dated_volumes.len() as u32;
+        let avg_daily_volume = if days_sampled > 0 {
+            dated_volumes.iter().map(|(_, v)| *v).sum::<u64>() / days_sampled as u64
+        } else {
+            0
+        };
+
+        let (liquidity_class, volume_ratio_threshold, price_move_threshold_pct) =
+            Self::classify_liquidity(avg_daily_volume);
+
+        Ok(LiquidityClassification {
+            symbol: resolved_symbol,
+            avg_daily_volume,
+            days_sampled,
+            liquidity_class: liquidity_class.to_string(),
+            volume_ratio_threshold: format!("{:.2}", volume_ratio_threshold),
+            price_move_threshold_pct: format!("{:.2}", price_move_threshold_pct),
         })
     }
 
+    #[mutate]
+    async fn detect_dormant_account_activity(&mut self, account_id: String, min_trade_count: u32, size_multiplier_pct: u32) -> Result<DormancyAlert, String> {
+        self.maintenance_guard()?;
+        let resolved_account = self.resolve_account_or_pan(&account_id).await;
+        self.update_cache("detect_dormant_account_activity", "", &resolved_account,
+            &format!("Check dormancy/reactivation for account {}", resolved_account));
+
+        let symbols = vec!["IBM", "AAPL", "MSFT", "GOOGL"];
+        let mut all_trades = Vec::new();
+        for symbol in symbols {
+            let trades = self.fetch_trades(symbol, Some(&resolved_account), 50).await?;
+            all_trades.extend(trades);
+        }
+        all_trades.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+        let trade_count = all_trades.len() as u32;
+        if all_trades.is_empty() {
+            return Ok(DormancyAlert {
+                account_id: resolved_account,
+                trade_count: 0,
+                avg_quantity: 0,
+                latest_quantity: 0,
+                size_ratio: "0.00".to_string(),
+                is_dormant_reactivation: false,
+            });
+        }
+
+        let latest_quantity = all_trades[0].quantity;
+        let historical_quantities: Vec<u64> = all_trades.iter().skip(1).map(|t| t.quantity).collect();
+        let avg_quantity = if !historical_quantities.is_empty() {
+            historical_quantities.iter().sum::<u64>() / historical_quantities.len() as u64
+        } else {
+            latest_quantity
+        };
+
+        let ratio = if avg_quantity > 0 { latest_quantity as f64 / avg_quantity as f64 } else { 1.0 };
+        let is_dormant_reactivation = trade_count <= min_trade_count && (ratio * 100.0) >= size_multiplier_pct as f64;
+
+        if is_dormant_reactivation {
+            self.maybe_push_alert(
+                "DORMANT_ACCOUNT_REACTIVATION",
+                "HIGH",
+                (ratio * 20.0).min(100.0) as u32,
+                &resolved_account,
+                "",
+                &format!("Account {} traded {} after only {} recent trades ({:.2}x its own average size {})",
+                    resolved_account, latest_quantity, trade_count, ratio, avg_quantity),
+            );
+        }
+
+        Ok(DormancyAlert {
+            account_id: resolved_account,
+            trade_count,
+            avg_quantity,
+            latest_quantity,
+            size_ratio: format!("{:.2}", ratio),
+            is_dormant_reactivation,
+        })
+    }
+
+    /// Fetches current volume for a batch of symbols via Alpha Vantage's
+    /// REALTIME_BULK_QUOTES endpoint, chunked to BULK_QUOTE_CHUNK_SIZE symbols per
+    /// call. Errors (including a non-bulk API tier) are surfaced so the caller can
+    /// fall back to single-quote mode.
+    async fn fetch_bulk_quotes(&mut self, symbols: &[String]) -> Result<HashMap<String, u64>, String> {
+        const BULK_QUOTE_CHUNK_SIZE: usize = 100;
+        let api_key = self.get_api_key();
+        let url = "https://www.alphavantage.co/query";
+        let mut volumes = HashMap::new();
+
+        for chunk in symbols.chunks(BULK_QUOTE_CHUNK_SIZE) {
+            let query_params = vec![
+                ("function".to_string(), "REALTIME_BULK_QUOTES".to_string()),
+                ("symbol".to_string(), chunk.join(",")),
+                ("apikey".to_string(), api_key.clone()),
+            ];
+
+            let response_text = self.make_request(url, query_params).await?;
+            let json: serde_json::Value = serde_json::from_str(&response_text)
+                .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+            let quotes = json.get("data").and_then(|v| v.as_array())
+                .ok_or_else(|| "REALTIME_BULK_QUOTES not available on this API tier".to_string())?;
+
+            for quote in quotes {
+                let quote_symbol = quote.get("symbol").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                let volume = quote.get("volume").and_then(|v| v.as_str()).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+                if !quote_symbol.is_empty() {
+                    volumes.insert(quote_symbol, volume);
+                }
+            }
+        }
+
+        Ok(volumes)
+    }
+
+    #[mutate]
+    async fn scan_watchlist(&mut self, symbols: String) -> Result<Vec<VolumeAnomaly>, String> {
+        self.maintenance_guard()?;
+        let symbol_list: Vec<String> = symbols.split(',')
+            .map(|s| self.resolve_symbol(s.trim()))
+            .filter(|s| !s.is_empty())
+            .collect();
+        self.update_cache("scan_watchlist", "", "",
+            &format!("Scan watchlist: {}", symbol_list.join(", ")));
+
+        let bulk_volumes = self.fetch_bulk_quotes(&symbol_list).await.ok();
+
+        let mut results = Vec::new();
+        for symbol in symbol_list {
+            let current_volume = match bulk_volumes.as_ref().and_then(|v| v.get(&symbol)) {
+                Some(volume) => *volume,
+                // Not in the bulk response (symbol missing from it, or the whole
+                // bulk call failed) - fall back to a single-quote fetch for it
+                None => {
+                    let trades = self.fetch_trades(&symbol, None, 200).await?;
+                    trades.iter().map(|t| t.quantity).sum()
+                }
+            };
+            results.push(self.build_volume_anomaly(symbol, current_volume));
+        }
+
+        Ok(results)
+    }
+
     #[mutate]
     async fn get_top_traders(&mut self, symbol: String, limit: u32) -> Result<Vec<AccountActivity>, String> {
+        self.maintenance_guard()?;
         let resolved_symbol = self.resolve_symbol(&symbol);
         self.update_cache("get_top_traders", &resolved_symbol, "", 
             &format!("Get top traders for {}", resolved_symbol));
@@ -556,6 +1128,7 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn get_large_orders(&mut self, min_value: u64) -> Result<Vec<Trade>, String> {
+        self.maintenance_guard()?;
         let last_symbol = self.query_cache.last_symbol.clone();
         self.update_cache("get_large_orders", &last_symbol, "", 
             &format!("Get large orders > {}", min_value));
@@ -578,7 +1151,8 @@ impl TradeData for TradeDataContractState {
 
     #[mutate]
     async fn get_account_profile(&mut self, account_id: String) -> Result<Vec<AccountActivity>, String> {
-        let resolved_account = self.resolve_account(&account_id);
+        self.maintenance_guard()?;
+        let resolved_account = self.resolve_account_or_pan(&account_id).await;
         self.update_cache("get_account_profile", "", &resolved_account, 
             &format!("Get profile for {}", resolved_account));
         
@@ -607,6 +1181,203 @@ impl TradeData for TradeDataContractState {
         Ok(activities)
     }
 
+    #[mutate]
+    async fn replay_trades(&mut self, symbol: String, from: String, to: String, speed: u32) -> Result<TradeReplay, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+        self.update_cache("replay_trades", &resolved_symbol, "",
+            &format!("Replay trades for {} from {} to {}", resolved_symbol, from, to));
+
+        let window_seconds = speed.max(1);
+        let window_ms = window_seconds as u64 * 1000;
+
+        let mut trades = self.fetch_trades(&resolved_symbol, None, 200).await?;
+        trades.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        let base_timestamp = trades.first().map(|t| t.timestamp).unwrap_or(0);
+        let mut buckets: std::collections::BTreeMap<u64, Vec<Trade>> = std::collections::BTreeMap::new();
+        for trade in trades {
+            let bucket = (trade.timestamp - base_timestamp) / window_ms;
+            buckets.entry(bucket).or_default().push(trade);
+        }
+
+        let mut frames = Vec::new();
+        let mut cumulative_volume = 0u64;
+        let mut cumulative_trade_count = 0u32;
+        let mut cumulative_buy_volume = 0u64;
+        let mut cumulative_sell_volume = 0u64;
+
+        for (bucket, bucket_trades) in buckets {
+            let window_start = base_timestamp + bucket * window_ms;
+            let window_end = window_start + window_ms;
+
+            for t in &bucket_trades {
+                cumulative_volume += t.quantity;
+                cumulative_trade_count += 1;
+                if t.trade_type == "BUY" {
+                    cumulative_buy_volume += t.quantity;
+                } else {
+                    cumulative_sell_volume += t.quantity;
+                }
+            }
+
+            frames.push(ReplayFrame {
+                frame_index: frames.len() as u32,
+                window_start,
+                window_end,
+                trades: bucket_trades,
+                cumulative_volume,
+                cumulative_trade_count,
+                cumulative_buy_volume,
+                cumulative_sell_volume,
+            });
+        }
+
+        Ok(TradeReplay {
+            symbol: resolved_symbol,
+            from,
+            to,
+            window_seconds,
+            frames,
+        })
+    }
+
+    #[mutate]
+    async fn cluster_trades(&mut self, symbol: String, from: String, to: String, features: String) -> Result<Vec<TradeCluster>, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+        self.update_cache("cluster_trades", &resolved_symbol, "",
+            &format!("Cluster trades for {} from {} to {} by account/minute/price-level", resolved_symbol, from, to));
+
+        let trades = self.fetch_trades(&resolved_symbol, None, 200).await?;
+
+        let mut groups: std::collections::BTreeMap<(String, u64, String), Vec<Trade>> = std::collections::BTreeMap::new();
+        for trade in trades {
+            let minute_bucket = trade.timestamp / 60000;
+            let price_level = format!("{:.2}", trade.price.parse::<f64>().unwrap_or(0.0));
+            let key = (trade.account_id.clone(), minute_bucket, price_level);
+            groups.entry(key).or_default().push(trade);
+        }
+
+        let mut clusters: Vec<TradeCluster> = groups.into_iter()
+            .filter(|(_, trades)| trades.len() > 1)
+            .map(|((account_id, minute_bucket, price_level), trades)| {
+                let buy_count = trades.iter().filter(|t| t.trade_type == "BUY").count() as u32;
+                let sell_count = trades.iter().filter(|t| t.trade_type == "SELL").count() as u32;
+                let total_quantity: u64 = trades.iter().map(|t| t.quantity).sum();
+                TradeCluster {
+                    symbol: resolved_symbol.clone(),
+                    account_id,
+                    minute_bucket,
+                    price_level,
+                    buy_count,
+                    sell_count,
+                    trade_count: trades.len() as u32,
+                    total_quantity,
+                    crossing_count: buy_count.min(sell_count),
+                    features: features.clone(),
+                }
+            })
+            .collect();
+
+        clusters.sort_by(|a, b| b.crossing_count.cmp(&a.crossing_count).then(b.trade_count.cmp(&a.trade_count)));
+        Ok(clusters)
+    }
+
+    #[mutate]
+    async fn detect_block_deals(&mut self, symbol: String, date: String, threshold_pct_of_volume: u32) -> Result<BlockDealReport, String> {
+        self.maintenance_guard()?;
+        let resolved_symbol = self.resolve_symbol(&symbol);
+        self.update_cache("detect_block_deals", &resolved_symbol, "",
+            &format!("Detect block/bulk deals for {} on {} above {}% of volume", resolved_symbol, date, threshold_pct_of_volume));
+
+        let trades = self.fetch_trades(&resolved_symbol, None, 500).await?;
+        let total_day_volume: u64 = trades.iter().map(|t| t.quantity).sum();
+        let threshold_quantity = (total_day_volume as f64 * threshold_pct_of_volume as f64 / 100.0) as u64;
+
+        let mut account_groups: HashMap<String, Vec<Trade>> = HashMap::new();
+        for trade in trades {
+            account_groups.entry(trade.account_id.clone()).or_default().push(trade);
+        }
+
+        let mut findings: Vec<BlockDealFinding> = account_groups.into_iter()
+            .map(|(account_id, account_trades)| {
+                let quantity: u64 = account_trades.iter().map(|t| t.quantity).sum();
+                (account_id, account_trades, quantity)
+            })
+            .filter(|(_, _, quantity)| threshold_quantity > 0 && *quantity >= threshold_quantity)
+            .map(|(account_id, account_trades, quantity)| {
+                let pct_of_volume = if total_day_volume > 0 { quantity as f64 / total_day_volume as f64 * 100.0 } else { 0.0 };
+                BlockDealFinding {
+                    symbol: resolved_symbol.clone(),
+                    date: date.clone(),
+                    account_id,
+                    trade_ids: account_trades.iter().map(|t| t.trade_id.clone()).collect(),
+                    is_single_trade: account_trades.len() == 1,
+                    quantity,
+                    total_day_volume,
+                    pct_of_volume: format!("{:.2}%", pct_of_volume),
+                }
+            })
+            .collect();
+        findings.sort_by(|a, b| b.quantity.cmp(&a.quantity));
+
+        for finding in &findings {
+            let pct = finding.pct_of_volume.trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
+            self.maybe_push_alert(
+                "UNDISCLOSED_BLOCK_DEAL",
+                if pct >= 2.0 * threshold_pct_of_volume as f64 { "CRITICAL" } else { "HIGH" },
+                pct as u32,
+                &finding.account_id,
+                &finding.symbol,
+                &format!("Account {} traded {} ({} of {} total day volume) in {} on {} - no disclosure registry is configured in this deployment to confirm this was reported",
+                    finding.account_id, finding.quantity, finding.pct_of_volume, finding.total_day_volume, finding.symbol, finding.date),
+            );
+        }
+
+        Ok(BlockDealReport {
+            symbol: resolved_symbol,
+            date,
+            threshold_pct_of_volume: format!("{}%", threshold_pct_of_volume),
+            total_day_volume,
+            findings,
+            disclosure_status: "NOT_CHECKED: no announcements_mcp or other disclosure registry exists in this deployment".to_string(),
+        })
+    }
+
+    #[mutate]
+    fn set_source_offset(&mut self, source: String, offset_ms: i64) -> SourceOffset {
+        if let Some(existing) = self.source_offsets.iter_mut().find(|o| o.source == source) {
+            existing.offset_ms = offset_ms;
+            existing.clone()
+        } else {
+            let entry = SourceOffset { source, offset_ms };
+            self.source_offsets.push(entry.clone());
+            entry
+        }
+    }
+
+    #[query]
+    fn get_source_offsets(&self) -> Vec<SourceOffset> {
+        self.source_offsets.clone()
+    }
+
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
     #[query]
     fn tools(&self) -> String {
         r#"[
@@ -677,6 +1448,31 @@ impl TradeData for TradeDataContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "detect_dormant_account_activity",
+      "description": "Flag an account trading well above its own average size after a spell of unusually few trades\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "account_id": {
+            "type": "string",
+            "description": "Account ID - supports fuzzy matching and PAN lookup\n"
+          },
+          "min_trade_count": {
+            "type": "integer",
+            "description": "Trade count at or below which the account is treated as dormant\n"
+          },
+          "size_multiplier_pct": {
+            "type": "integer",
+            "description": "Minimum percentage of the account's own average trade size the latest trade must reach to flag, e.g. 300 for 3x\n"
+          }
+        },
+        "required": ["account_id", "min_trade_count", "size_multiplier_pct"]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -807,7 +1603,7 @@ impl TradeData for TradeDataContractState {
                 ("apikey".to_string(), api_key.clone()),
             ];
             
-            if let Ok(response) = self.make_request(url, query_params).await {
+            if let Ok(response) = self.fetch_raw(url, query_params).await {
                 if let Ok(json) = serde_json::from_str::<serde_json::Value>(&response) {
                     if let Some(time_series) = json.get("Time Series (Daily)").and_then(|v| v.as_object()) {
                         let mut points: Vec<(f32, f32)> = Vec::new();
@@ -859,7 +1655,7 @@ impl TradeData for TradeDataContractState {
                 ("apikey".to_string(), api_key.clone()),
             ];
             
-            if let Ok(response) = self.make_request(url, query_params).await {
+            if let Ok(response) = self.fetch_raw(url, query_params).await {
                 if let Ok(json) = serde_json::from_str::<serde_json::Value>(&response) {
                     if let Some(time_series) = json.get("Time Series (Daily)").and_then(|v| v.as_object()) {
                         let mut points: Vec<(f32, f32)> = Vec::new();
@@ -903,7 +1699,7 @@ impl TradeData for TradeDataContractState {
             ("apikey".to_string(), api_key),
         ];
         
-        let response = self.make_request(url, query_params).await?;
+        let response = self.fetch_raw(url, query_params).await?;
         let json: serde_json::Value = serde_json::from_str(&response)
             .map_err(|e| format!("Failed to parse JSON: {}", e))?;
         
@@ -938,7 +1734,7 @@ impl TradeData for TradeDataContractState {
             ("apikey".to_string(), api_key),
         ];
         
-        let response = self.make_request(url, query_params).await?;
+        let response = self.fetch_raw(url, query_params).await?;
         let json: serde_json::Value = serde_json::from_str(&response)
             .map_err(|e| format!("Failed to parse JSON: {}", e))?;
         
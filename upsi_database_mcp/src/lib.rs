@@ -1,4 +1,5 @@
 
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
@@ -6,17 +7,78 @@ use weil_rs::config::Secrets;
 use weil_rs::http::{HttpClient, HttpMethod};
 use weil_rs::runtime::Runtime;
 
+mod fuzzy_match;
+mod http_fixtures;
+mod outbound_guard;
+pub use outbound_guard::{CircuitStatus, OutboundGuard};
+
+mod trade_data;
+use trade_data::TradeDataMcp;
+
+/// Formats an epoch-milliseconds UTC timestamp as an IST (UTC+5:30) string,
+/// e.g. "2025-01-18 21:30:00 IST" - duplicated in trade_data_mcp and
+/// regulatory_reports_mcp since there's no shared crate between MCPs
+fn epoch_ms_to_ist(epoch_ms: u64) -> String {
+    let utc: DateTime<Utc> = match DateTime::from_timestamp_millis(epoch_ms as i64) {
+        Some(dt) => dt,
+        None => return "INVALID_TIMESTAMP".to_string(),
+    };
+    let ist_offset = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    utc.with_timezone(&ist_offset).format("%Y-%m-%d %H:%M:%S IST").to_string()
+}
+
 // ===== CONFIGURATION =====
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default, Clone)]
 pub struct UPSIDatabaseConfig {
     pub dashboard_contract_id: String,
+    pub entity_relationship_contract_id: String,
+    pub trade_data_contract_id: String,
+    pub slack_contract_id: String,
     pub supabase_url: String,
     pub supabase_anon_key: String,
+    /// Used only for the privileged writes RLS blocks under the anon key
+    /// (access log inserts, trading window updates) - see SupabaseAuthMode
+    pub supabase_service_key: String,
+    /// Pre-shared bearer token dashboard_webserver has allow-listed for this
+    /// contract via manage_trusted_callers; sent as push_alert's caller_token
+    pub dashboard_caller_token: String,
+    /// "live" (default): call Supabase for real. "record": call it for real and
+    /// save the response as a fixture. "playback": skip the network and return
+    /// the previously recorded fixture, erroring if none exists - see
+    /// http_fixtures for the whole scheme
+    pub http_fixture_mode: String,
+}
+
+/// Which Supabase key a request authenticates with. RLS on the sensitive
+/// tables blocks the anon key, so writes that RLS would reject (access log
+/// inserts, trading window updates) go through with ServiceRole instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SupabaseAuthMode {
+    Anon,
+    ServiceRole,
+}
+
+impl SupabaseAuthMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SupabaseAuthMode::Anon => "anon",
+            SupabaseAuthMode::ServiceRole => "service_role",
+        }
+    }
 }
 
 // ===== DATA STRUCTURES =====
 
+/// Rotation metadata for a sensitive config field - never the value itself,
+/// so operators can confirm a rotation took effect without exposing the secret
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SecretVersionEntry {
+    pub field_name: String,
+    pub version: u32,
+    pub rotated_at: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct UPSIRecord {
     pub upsi_id: String,
@@ -27,6 +89,11 @@ pub struct UPSIRecord {
     pub created_date: u64,
     pub public_date: u64,
     pub is_public: bool,
+    /// Owning tenant, for vendor deployments serving multiple broker clients out of
+    /// one Supabase project; empty for single-tenant deployments and anything
+    /// predating this field
+    #[serde(default)]
+    pub tenant_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
@@ -36,11 +103,88 @@ pub struct UPSIAccessLog {
     pub accessor_entity_id: String,
     pub accessor_name: String,
     pub accessor_designation: String,
+    /// Epoch milliseconds UTC by convention; callers that still pass epoch
+    /// seconds (a pre-existing mixed-units issue this platform hasn't fully
+    /// standardized away) will get a nonsensical access_timestamp_ist below
     pub access_timestamp: u64,
+    /// Not stored in Supabase - computed on the way out by whichever method
+    /// builds the row, so rows read straight back from Supabase default to ""
+    #[serde(default)]
+    pub access_timestamp_ist: String,
     pub access_reason: String,
     pub access_mode: String,
 }
 
+/// Paginated wrapper for get_active_upsi - widl has no generics anywhere in
+/// this codebase, so each paginated list gets its own concrete wrapper
+/// instead of a single generic PaginatedResult<T>
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PaginatedUpsiRecords {
+    pub items: Vec<UPSIRecord>,
+    pub total_count: u32,
+    pub next_offset: u32,
+    pub has_more: bool,
+}
+
+/// Paginated wrapper for get_upsi_access_log, get_access_by_person, and
+/// get_upsi_accessors - see PaginatedUpsiRecords for why this isn't generic
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PaginatedAccessLogs {
+    pub items: Vec<UPSIAccessLog>,
+    pub total_count: u32,
+    pub next_offset: u32,
+    pub has_more: bool,
+}
+
+/// PIT compliance requires tracking not just who accessed a UPSI, but who
+/// passed it along to whom and why
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct UPSISharingEvent {
+    pub sharing_id: String,
+    pub upsi_id: String,
+    pub shared_by: String,
+    pub shared_with: String,
+    pub purpose: String,
+    pub timestamp: u64,
+}
+
+/// The full sharing chain for a UPSI: the raw events plus the same data
+/// pre-rendered as node/edge graph JSON, so it can be overlaid directly on the
+/// entity relationship view
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct SharingChain {
+    pub upsi_id: String,
+    pub events: Vec<UPSISharingEvent>,
+    pub graph_json: String,
+}
+
+/// One in-progress chunked CSV import for an upsi_id; chunks are collected in
+/// order and assembled once every slot has been filled
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct PendingCsvImport {
+    pub upsi_id: String,
+    pub total_chunks: u32,
+    pub chunks: Vec<String>,
+    pub received: Vec<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CsvImportRowError {
+    pub row_number: u32,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct CsvImportSummary {
+    pub upsi_id: String,
+    pub complete: bool,
+    pub chunks_received: u32,
+    pub total_chunks: u32,
+    pub rows_imported: u32,
+    pub rows_failed: u32,
+    pub errors: Vec<CsvImportRowError>,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct TradingWindowStatus {
     pub company_symbol: String,
@@ -48,6 +192,104 @@ pub struct TradingWindowStatus {
     pub closure_reason: String,
     pub closure_start: u64,
     pub expected_opening: u64,
+    /// UPSI record that triggered this closure, if any - lets an automated
+    /// opening be tied back to the UPSI going public
+    pub triggering_upsi_id: String,
+}
+
+/// One requested closure in a bulk import - e.g. one line of a quarterly
+/// earnings-season pre-closure list
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradingWindowClosure {
+    pub symbol: String,
+    pub start: u64,
+    pub expected_opening: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradingWindowImportError {
+    pub index: u32,
+    pub symbol: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct TradingWindowImportResult {
+    pub imported: u32,
+    pub failed: u32,
+    pub errors: Vec<TradingWindowImportError>,
+}
+
+/// A pre-cleared exemption from a closed trading window (e.g. an ESOP exercise
+/// or a creeping acquisition already disclosed to the exchange), consulted by
+/// check_window_violation so legitimate trades don't raise a CRITICAL alert.
+/// "PENDING" until a second call to approve_window_exemption confirms it -
+/// requesting and approving are always separate calls, there's no self-approval.
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct WindowExemption {
+    pub exemption_id: String,
+    pub entity_id: String,
+    pub company_symbol: String,
+    pub reason: String,
+    pub status: String,
+    pub requested_at: u64,
+    pub approved_by: String,
+    pub approved_at: u64,
+}
+
+/// One trade timestamp correlated against the entity's UPSI access history for
+/// this company. nearest_access_id/nearest_access_timestamp is whichever access
+/// happened at or before the trade, if any - empty/zero when had_upsi_access is
+/// false
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct InsiderTradeCorrelation {
+    pub trade_timestamp: u64,
+    pub had_upsi_access: bool,
+    pub nearest_access_id: String,
+    pub nearest_access_timestamp: u64,
+    pub time_delta_ms: u64,
+    pub before_public_date: bool,
+    pub window_violation: bool,
+}
+
+/// Result of detect_insider_pattern - joins UPSI access logs, trading window
+/// status, and per-trade correlation in one call instead of forcing the caller
+/// to chain get_upsi_access_log + get_trading_window + check_window_violation
+/// once per trade themselves
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct InsiderEvidence {
+    pub entity_id: String,
+    pub company_symbol: String,
+    pub access_events: Vec<UPSIAccessLog>,
+    /// True if a trading window row exists for this company; window_status is
+    /// the zero-value TradingWindowStatus otherwise
+    pub has_window_data: bool,
+    pub window_status: TradingWindowStatus,
+    pub trade_correlations: Vec<InsiderTradeCorrelation>,
+    /// Simple additive score: +40 per window-violating trade, +25 per trade with
+    /// UPSI access beforehand, capped at 100 - not a substitute for a human
+    /// investigator, just a triage signal
+    pub risk_score: u32,
+}
+
+/// One runner-up candidate resolve_reference didn't pick, with its own confidence
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceCandidate {
+    pub value: String,
+    pub confidence: u32,
+}
+
+/// resolve_reference's result: the resolved value plus a 0-100 confidence
+/// score and up to 3 runner-up candidates, so a caller can ask a clarifying
+/// question instead of silently acting on a low-confidence fuzzy match
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct ReferenceResolution {
+    pub kind: String,
+    pub query: String,
+    pub resolved_value: String,
+    pub confidence: u32,
+    pub alternatives: Vec<ReferenceCandidate>,
 }
 
 // ===== CONTEXT CACHE STRUCTURES =====
@@ -70,6 +312,30 @@ pub struct QueryContext {
     pub last_upsi_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct DesignatedPerson {
+    pub dp_id: String,
+    pub entity_id: String,
+    pub company_symbol: String,
+    pub designation: String,
+    pub effective_from: u64,
+    pub active: bool,
+}
+
+/// One notify_window_closure attempt against a single designated person - kept
+/// even on failure, so the notification trail itself (not just the closure) is
+/// part of the PIT compliance record
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct WindowClosureNotification {
+    pub company_symbol: String,
+    pub dp_id: String,
+    pub entity_id: String,
+    pub designation: String,
+    pub notified_at: u64,
+    pub success: bool,
+    pub error: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
 pub struct Alert {
     pub id: String,
@@ -81,6 +347,43 @@ pub struct Alert {
     pub description: String,
     pub workflow_id: String,
     pub timestamp: u64,
+    /// {detector}:{entity}:{symbol}:{date} - lets the dashboard group every
+    /// artifact for the same underlying event across detectors/MCPs, even
+    /// though each one raises its own separately-typed alert
+    pub correlation_key: String,
+}
+
+/// Compares a symbol's trading activity in the window before a UPSI's public_date
+/// against the window after, as a rough check on whether the leaked information
+/// was actually exploited. abnormal_return_estimate is the raw before/after price
+/// change - it is not adjusted against a market/sector benchmark (this platform
+/// has no benchmark index feed), so it is a directional signal, not a precise
+/// abnormal-return figure
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone)]
+pub struct UpsiPriceImpactReport {
+    pub upsi_id: String,
+    pub company_symbol: String,
+    pub window_days: u32,
+    pub trades_before: u32,
+    pub trades_after: u32,
+    pub avg_price_before: String,
+    pub avg_price_after: String,
+    pub price_change_pct: String,
+    pub volume_before: u64,
+    pub volume_after: u64,
+    pub volume_change_pct: String,
+    pub abnormal_return_estimate: String,
+    pub likely_exploited: bool,
+}
+
+/// Local copy of entity_relationship_mcp's Entity, for the get_entity_by_pan proxy call
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Entity {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub name: String,
+    pub pan_number: String,
+    pub registration_id: String,
 }
 
 // ===== TRAIT DEFINITION =====
@@ -88,52 +391,198 @@ pub struct Alert {
 trait UPSIDatabase {
     fn new() -> Result<Self, String> where Self: Sized;
     async fn get_context(&mut self) -> QueryContext;
+    /// kind: "entity", "company", or "upsi_id" - see ReferenceResolution's doc comment
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String>;
     async fn get_upsi(&mut self, upsi_id: String) -> Result<UPSIRecord, String>;
-    async fn get_active_upsi(&mut self, company_symbol: String) -> Result<Vec<UPSIRecord>, String>;
-    async fn get_upsi_access_log(&mut self, upsi_id: String, from_timestamp: u64, to_timestamp: u64) -> Result<Vec<UPSIAccessLog>, String>;
-    async fn get_access_by_person(&mut self, accessor_entity_id: String, days_back: u32) -> Result<Vec<UPSIAccessLog>, String>;
+    async fn create_upsi(&mut self, company_symbol: String, upsi_type: String, description: String, nature: String, tenant_id: String) -> Result<UPSIRecord, String>;
+    // Single-record counterpart to import_access_logs_csv - use that for bulk backfills
+    async fn log_upsi_access(&mut self, upsi_id: String, accessor_entity_id: String, accessor_name: String, accessor_designation: String, access_timestamp: u64, access_reason: String, access_mode: String) -> Result<UPSIAccessLog, String>;
+    // tenant_filter is a best-effort convenience filter, not an enforced isolation
+    // boundary - no session/caller identity is bound to a tenant, so any caller
+    // can pass any tenant_filter (or omit it to see every tenant's UPSI)
+    async fn get_active_upsi(&mut self, company_symbol: String, tenant_filter: String, limit: u32, offset: u32) -> Result<PaginatedUpsiRecords, String>;
+    async fn get_upsi_access_log(&mut self, upsi_id: String, from_timestamp: u64, to_timestamp: u64, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String>;
+    async fn get_access_by_person(&mut self, accessor_entity_id: String, days_back: u32, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String>;
     async fn check_upsi_access_before(&mut self, entity_id: String, company_symbol: String, before_timestamp: u64) -> Result<Vec<UPSIAccessLog>, String>;
     async fn get_trading_window(&mut self, company_symbol: String) -> Result<TradingWindowStatus, String>;
+    async fn import_trading_windows(&mut self, windows: Vec<TradingWindowClosure>) -> Result<TradingWindowImportResult, String>;
     async fn check_window_violation(&mut self, entity_id: String, company_symbol: String, trade_timestamp: u64) -> Result<bool, String>;
-    async fn get_upsi_accessors(&mut self, upsi_id: String) -> Result<Vec<UPSIAccessLog>, String>;
+    // Joins UPSI access logs, trading window status, and per-trade correlation in
+    // one call, instead of the caller chaining get_upsi_access_log +
+    // get_trading_window + check_window_violation once per trade themselves
+    async fn detect_insider_pattern(&mut self, entity_id: String, company_symbol: String, trade_timestamps: Vec<u64>) -> Result<InsiderEvidence, String>;
+    // Legitimate closed-window trades (ESOP exercises, creeping acquisitions) that
+    // are pre-cleared so check_window_violation stops raising a CRITICAL alert for
+    // them. Requesting and approving are always separate calls from each other.
+    async fn request_window_exemption(&mut self, entity_id: String, company_symbol: String, reason: String) -> Result<WindowExemption, String>;
+    async fn approve_window_exemption(&mut self, exemption_id: String, approved_by: String) -> Result<WindowExemption, String>;
+    async fn get_upsi_accessors(&mut self, upsi_id: String, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String>;
+    async fn record_upsi_sharing(&mut self, upsi_id: String, shared_by: String, shared_with: String, purpose: String, timestamp: u64) -> Result<UPSISharingEvent, String>;
+    async fn get_sharing_chain(&mut self, upsi_id: String) -> Result<SharingChain, String>;
+    async fn import_access_logs_csv(&mut self, upsi_id: String, csv_chunk: String, chunk_index: u32, total_chunks: u32) -> Result<CsvImportSummary, String>;
+    async fn mark_upsi_public(&mut self, upsi_id: String, public_date: u64) -> Result<UPSIRecord, String>;
+    async fn analyze_upsi_price_impact(&mut self, upsi_id: String, window_days: u32) -> Result<UpsiPriceImpactReport, String>;
+    async fn add_designated_person(&mut self, entity_id: String, company_symbol: String, designation: String, effective_from: u64) -> Result<DesignatedPerson, String>;
+    async fn remove_designated_person(&mut self, dp_id: String) -> Result<DesignatedPerson, String>;
+    async fn list_designated_persons(&mut self, company_symbol: String) -> Result<Vec<DesignatedPerson>, String>;
+    // Broadcasts a trading-window closure over Slack to every active designated
+    // person for the company, and records who was notified and when
+    async fn notify_window_closure(&mut self, company_symbol: String) -> Result<Vec<WindowClosureNotification>, String>;
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String>;
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry>;
+    fn get_circuit_status(&self, host: String) -> CircuitStatus;
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus;
+    fn get_maintenance_status(&self) -> MaintenanceStatus;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+/// Maintenance-mode status: while enabled, mutating methods return a clear error
+/// instead of writing partial state, so operators can safely migrate the
+/// Supabase schema / Neo4j graph without racing concurrent tool calls
+#[derive(Debug, Serialize, Deserialize, WeilType, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+}
+
 // ===== CONTRACT STATE =====
 
 #[derive(Serialize, Deserialize, WeilType)]
 pub struct UPSIDatabaseContractState {
     secrets: Secrets<UPSIDatabaseConfig>,
     query_cache: QueryContext,
+    outbound_guard: OutboundGuard,
+    dp_counter: u32,
+    upsi_counter: u32,
+    access_log_counter: u32,
+    sharing_log_counter: u32,
+    exemption_counter: u32,
+    secret_versions: Vec<SecretVersionEntry>,
+    maintenance: MaintenanceStatus,
+    pending_csv_imports: Vec<PendingCsvImport>,
+    http_fixtures: Vec<http_fixtures::HttpFixture>,
 }
 
 // ===== HELPER METHODS =====
 
 impl UPSIDatabaseContractState {
-    async fn supabase_request<T: for<'de> Deserialize<'de>>(&self, endpoint: &str, method: HttpMethod, body: Option<String>) -> Result<T, String> {
+    /// Reject mutating calls while maintenance mode is on, so an in-flight schema
+    /// migration in Supabase/Neo4j can't race a concurrent tool call into a
+    /// half-written state
+    fn maintenance_guard(&self) -> Result<(), String> {
+        if self.maintenance.enabled {
+            Err(format!("Contract is in maintenance mode: {}", self.maintenance.message))
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn supabase_request<T: for<'de> Deserialize<'de>>(&mut self, endpoint: &str, method: HttpMethod, body: Option<String>) -> Result<T, String> {
+        self.supabase_request_as(endpoint, method, body, SupabaseAuthMode::Anon).await
+    }
+
+    async fn supabase_request_as<T: for<'de> Deserialize<'de>>(&mut self, endpoint: &str, method: HttpMethod, body: Option<String>, auth_mode: SupabaseAuthMode) -> Result<T, String> {
         let config = self.secrets.config();
         let url = format!("{}/rest/v1/{}", config.supabase_url, endpoint);
-        
+        let mode = config.http_fixture_mode.clone();
+        let auth_key = match auth_mode {
+            SupabaseAuthMode::Anon => config.supabase_anon_key.clone(),
+            SupabaseAuthMode::ServiceRole => config.supabase_service_key.clone(),
+        };
+        let method_str = match method {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Patch => "PATCH",
+        };
+        let key = http_fixtures::fixture_key(method_str, &url, body.as_deref().unwrap_or(""));
+
+        if mode == "playback" {
+            let response_text = match http_fixtures::find(&self.http_fixtures, &key) {
+                Some(f) if (200..300).contains(&f.status) => f.body.clone(),
+                Some(f) if f.status == 401 || f.status == 403 => {
+                    return Err(format!(
+                        "HTTP {} (fixture) using {} auth: {}",
+                        f.status, auth_mode.as_str(), f.body
+                    ));
+                }
+                Some(f) => return Err(format!("HTTP {} (fixture): {}", f.status, f.body)),
+                None => return Err(format!("No recorded HTTP fixture for {}", key)),
+            };
+            return serde_json::from_str(&response_text)
+                .map_err(|e| format!("Failed to parse Supabase response: {} - Body: {}", e, response_text));
+        }
+
+        self.outbound_guard.check(&url)?;
+
         let headers = HashMap::from([
-            ("apikey".to_string(), config.supabase_anon_key.clone()),
-            ("Authorization".to_string(), format!("Bearer {}", config.supabase_anon_key)),
+            ("apikey".to_string(), auth_key.clone()),
+            ("Authorization".to_string(), format!("Bearer {}", auth_key)),
             ("Content-Type".to_string(), "application/json".to_string()),
             ("Prefer".to_string(), "return=representation".to_string()),
         ]);
-        
+
         let mut req = HttpClient::request(&url, method)
             .headers(headers);
-            
+
         if let Some(b) = body {
             req = req.body(b);
         }
-        
-        let response = req.send().map_err(|e| format!("Supabase request failed: {:?}", e))?;
+
+        let response = match req.send() {
+            Ok(response) => response,
+            Err(e) => {
+                self.outbound_guard.record_result(&url, false);
+                if mode == "record" {
+                    http_fixtures::upsert(&mut self.http_fixtures, key, 599, format!("{:?}", e));
+                }
+                return Err(format!("Supabase request failed: {:?}", e));
+            }
+        };
+        let status = response.status();
         let response_text = response.text();
-        
-        serde_json::from_str(&response_text)
-            .map_err(|e| format!("Failed to parse Supabase response: {} - Body: {}", e, response_text))
+
+        let parsed = if status == 401 || status == 403 {
+            Err(format!(
+                "Supabase request failed with HTTP {} using {} auth: {}",
+                status, auth_mode.as_str(), response_text
+            ))
+        } else {
+            serde_json::from_str(&response_text)
+                .map_err(|e| format!("Failed to parse Supabase response: {} - Body: {}", e, response_text))
+        };
+        self.outbound_guard.record_result(&url, parsed.is_ok());
+
+        if mode == "record" {
+            http_fixtures::upsert(&mut self.http_fixtures, key, status, response_text);
+        }
+
+        parsed
+    }
+
+    /// Runs `filter_endpoint` (a Supabase query string with no select/limit/
+    /// offset params of its own) as a count query plus a limit/offset page,
+    /// returning (items, total_count, next_offset, has_more)
+    async fn fetch_paginated<T: for<'de> Deserialize<'de>>(&mut self, filter_endpoint: &str, limit: u32, offset: u32) -> Result<(Vec<T>, u32, u32, bool), String> {
+        let limit = limit.max(1);
+        let sep = if filter_endpoint.contains('?') { '&' } else { '?' };
+
+        #[derive(Debug, Deserialize)]
+        struct CountRow {
+            count: u32,
+        }
+        let count_endpoint = format!("{}{}select=count()", filter_endpoint, sep);
+        let count_rows: Vec<CountRow> = self.supabase_request(&count_endpoint, HttpMethod::Get, None).await?;
+        let total_count = count_rows.into_iter().next().map(|r| r.count).unwrap_or(0);
+
+        let page_endpoint = format!("{}{}select=*&limit={}&offset={}", filter_endpoint, sep, limit, offset);
+        let items: Vec<T> = self.supabase_request(&page_endpoint, HttpMethod::Get, None).await?;
+
+        let next_offset = offset + items.len() as u32;
+        let has_more = next_offset < total_count;
+        Ok((items, total_count, next_offset, has_more))
     }
 
     fn update_cache(&mut self, method_name: &str, entity_id: &str, company_symbol: &str, upsi_id: &str, prompt: &str) {
@@ -171,47 +620,86 @@ impl UPSIDatabaseContractState {
         if partial.is_empty() {
             return self.query_cache.last_entity_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_entity_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_entity_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_entity_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()));
+
+        if let Some(m) = fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES) {
+            return m.value;
         }
-        
+
+        let partial_lower = partial.to_lowercase();
         for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.entity_id.is_empty() && query.entity_id.to_lowercase().contains(&partial_lower) {
+            if !query.entity_id.is_empty() && query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
                 return query.entity_id.clone();
             }
-            if query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
-                if !query.entity_id.is_empty() {
-                    return query.entity_id.clone();
-                }
-            }
         }
-        
+
         partial.to_string()
     }
 
+    /// Indian PAN format: 5 letters, 4 digits, 1 letter (e.g. AAAPL1234C)
+    fn looks_like_pan(s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        chars.len() == 10
+            && chars[0..5].iter().all(|c| c.is_ascii_uppercase())
+            && chars[5..9].iter().all(|c| c.is_ascii_digit())
+            && chars[9].is_ascii_uppercase()
+    }
+
+    /// When the partial looks like a PAN, look up the canonical entity_id in
+    /// entity_relationship_mcp instead of running it through the local fuzzy
+    /// cache match, since a PAN won't appear in our own query cache
+    async fn resolve_entity_or_pan(&mut self, partial: &str) -> String {
+        let candidate = partial.trim().to_uppercase();
+        if !Self::looks_like_pan(&candidate) {
+            return self.resolve_entity(partial);
+        }
+
+        let config = self.secrets.config();
+        if config.entity_relationship_contract_id.is_empty() {
+            return self.resolve_entity(partial);
+        }
+
+        #[derive(Serialize)]
+        struct GetEntityByPanArgs {
+            pan: String,
+        }
+
+        let args = serde_json::to_string(&GetEntityByPanArgs { pan: candidate }).unwrap_or_default();
+        match Runtime::call_contract::<Entity>(
+            config.entity_relationship_contract_id.clone(),
+            "get_entity_by_pan".to_string(),
+            Some(args),
+        ) {
+            Ok(entity) => entity.entity_id,
+            Err(_) => self.resolve_entity(partial),
+        }
+    }
+
+    /// Whether an APPROVED exemption exists for this entity/company pair, consulted
+    /// by check_window_violation before it raises a CRITICAL alert
+    async fn has_approved_exemption(&mut self, entity_id: &str, company_symbol: &str) -> bool {
+        let endpoint = format!(
+            "window_exemptions?entity_id=eq.{}&company_symbol=eq.{}&status=eq.APPROVED&select=*",
+            entity_id, company_symbol
+        );
+        matches!(self.supabase_request::<Vec<WindowExemption>>(&endpoint, HttpMethod::Get, None).await, Ok(rows) if !rows.is_empty())
+    }
+
     /// Resolve a partial company symbol from cache using fuzzy matching
     /// "RELI" → "RELIANCE", "TCS" → "TCS"
     fn resolve_company(&self, partial: &str) -> String {
         if partial.is_empty() {
             return self.query_cache.last_company_symbol.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_company_symbol.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_company_symbol.clone();
-        }
-        
-        for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.company_symbol.is_empty() && query.company_symbol.to_lowercase().contains(&partial_lower) {
-                return query.company_symbol.clone();
-            }
-        }
-        
-        partial.to_string()
+
+        let candidates = std::iter::once(self.query_cache.last_company_symbol.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()));
+
+        fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES)
+            .map(|m| m.value)
+            .unwrap_or_else(|| partial.to_string())
     }
 
     /// Resolve a partial UPSI ID from cache
@@ -220,24 +708,21 @@ impl UPSIDatabaseContractState {
         if partial.is_empty() {
             return self.query_cache.last_upsi_id.clone();
         }
-        
-        let partial_lower = partial.to_lowercase();
-        
-        if self.query_cache.last_upsi_id.to_lowercase().contains(&partial_lower) {
-            return self.query_cache.last_upsi_id.clone();
+
+        let candidates = std::iter::once(self.query_cache.last_upsi_id.as_str())
+            .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.upsi_id.as_str()));
+
+        if let Some(m) = fuzzy_match::resolve_best(partial, candidates, &fuzzy_match::DEFAULT_STRATEGIES) {
+            return m.value;
         }
-        
+
+        let partial_lower = partial.to_lowercase();
         for query in self.query_cache.recent_queries.iter().rev() {
-            if !query.upsi_id.is_empty() && query.upsi_id.to_lowercase().contains(&partial_lower) {
+            if !query.upsi_id.is_empty() && query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
                 return query.upsi_id.clone();
             }
-            if query.natural_language_prompt.to_lowercase().contains(&partial_lower) {
-                if !query.upsi_id.is_empty() {
-                    return query.upsi_id.clone();
-                }
-            }
         }
-        
+
         partial.to_string()
     }
 
@@ -298,12 +783,72 @@ impl UPSIDatabaseContractState {
         (self.resolve_entity(entity_partial), self.resolve_company(company_partial), self.resolve_upsi_id(upsi_partial))
     }
 
+    fn maybe_close_monitoring(&self, company_symbol: &str, upsi_id: &str) {
+        let config = self.secrets.config();
+        if config.dashboard_contract_id.is_empty() {
+            return;
+        }
+
+        #[derive(Serialize)]
+        struct CloseUpsiMonitoringArgs {
+            token: String,
+            company_symbol: String,
+            upsi_id: String,
+        }
+
+        let args = serde_json::to_string(&CloseUpsiMonitoringArgs {
+            token: config.dashboard_caller_token.clone(),
+            company_symbol: company_symbol.to_string(),
+            upsi_id: upsi_id.to_string(),
+        }).unwrap_or_default();
+
+        let _ = Runtime::call_contract::<serde_json::Value>(
+            config.dashboard_contract_id.clone(),
+            "close_upsi_monitoring".to_string(),
+            Some(args),
+        );
+    }
+
+    /// Close the trading window for a newly-created price-sensitive UPSI, with an
+    /// expected opening 48h out and the UPSI recorded as the trigger so opening can
+    /// be automated when it goes public
+    async fn close_window_for_upsi(&mut self, upsi: &UPSIRecord, now: u64) {
+        let expected_opening = now + 172800; // 48 hours
+
+        let window = TradingWindowStatus {
+            company_symbol: upsi.company_symbol.clone(),
+            window_status: "CLOSED".to_string(),
+            closure_reason: format!("UPSI {} created ({})", upsi.upsi_id, upsi.nature),
+            closure_start: now,
+            expected_opening,
+            triggering_upsi_id: upsi.upsi_id.clone(),
+        };
+
+        if let Ok(body) = serde_json::to_string(&window) {
+            let _: Result<Vec<TradingWindowStatus>, String> =
+                self.supabase_request_as("trading_windows", HttpMethod::Post, Some(body), SupabaseAuthMode::ServiceRole).await;
+        }
+
+        self.maybe_push_alert(
+            "TRADING_WINDOW_CLOSED",
+            "HIGH",
+            70,
+            "",
+            &upsi.company_symbol,
+            &format!("Trading window closed for {} following {} UPSI {}; expected opening {}", upsi.company_symbol, upsi.nature, upsi.upsi_id, expected_opening),
+        );
+    }
+
     fn maybe_push_alert(&self, alert_type: &str, severity: &str, risk_score: u32, entity_id: &str, symbol: &str, description: &str) {
         let config = self.secrets.config();
         if config.dashboard_contract_id.is_empty() {
             return;
         }
 
+        // No real per-day clock in this crate; reuses the same fixed date the
+        // other MCPs' mocked "now" resolves to so correlation keys stay
+        // comparable across producers
+        let date = "2026-01-13";
         let alert = Alert {
             id: format!("UPSI-{}", 0u64),
             alert_type: alert_type.to_string(),
@@ -314,15 +859,48 @@ impl UPSIDatabaseContractState {
             description: description.to_string(),
             workflow_id: "".to_string(),
             timestamp: 0,
+            correlation_key: format!("{}:{}:{}:{}", alert_type, entity_id, symbol, date),
         };
 
-        let args = serde_json::to_string(&alert).unwrap_or_default();
+        let args = serde_json::json!({ "caller_token": config.dashboard_caller_token, "alert": alert }).to_string();
         let _ = Runtime::call_contract::<String>(
             config.dashboard_contract_id.clone(),
             "push_alert".to_string(),
             Some(args),
         );
     }
+
+    /// Mirror a designated-person register change into Neo4j so entity_relationship_mcp
+    /// remains the single source of truth for who is an insider
+    fn maybe_sync_insider_edge(&self, entity_id: &str, company_symbol: &str, designation: &str, effective_from: u64, active: bool) {
+        let config = self.secrets.config();
+        if config.entity_relationship_contract_id.is_empty() {
+            return;
+        }
+
+        #[derive(Serialize)]
+        struct SyncInsiderRelationshipArgs {
+            entity_id: String,
+            company_symbol: String,
+            designation: String,
+            effective_from: u64,
+            active: bool,
+        }
+
+        let args = serde_json::to_string(&SyncInsiderRelationshipArgs {
+            entity_id: entity_id.to_string(),
+            company_symbol: company_symbol.to_string(),
+            designation: designation.to_string(),
+            effective_from,
+            active,
+        }).unwrap_or_default();
+
+        let _ = Runtime::call_contract::<serde_json::Value>(
+            config.entity_relationship_contract_id.clone(),
+            "sync_insider_relationship".to_string(),
+            Some(args),
+        );
+    }
 }
 
 // ===== CONTRACT IMPLEMENTATION =====
@@ -385,6 +963,16 @@ impl UPSIDatabase for UPSIDatabaseContractState {
                 last_company_symbol: "RELIANCE".to_string(),
                 last_upsi_id: "UPSI-001".to_string(),
             },
+            outbound_guard: OutboundGuard::default(),
+            dp_counter: 0,
+            upsi_counter: 0,
+            access_log_counter: 0,
+            sharing_log_counter: 0,
+            exemption_counter: 0,
+            secret_versions: Vec::new(),
+            maintenance: MaintenanceStatus::default(),
+            pending_csv_imports: Vec::new(),
+            http_fixtures: Vec::new(),
         })
     }
 
@@ -393,11 +981,41 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         self.query_cache.clone()
     }
 
+    #[query]
+    async fn resolve_reference(&self, kind: String, partial: String) -> Result<ReferenceResolution, String> {
+        if partial.is_empty() {
+            return Err("partial must not be empty".to_string());
+        }
+
+        let candidates: Vec<&str> = match kind.as_str() {
+            "entity" => std::iter::once(self.query_cache.last_entity_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.entity_id.as_str()))
+                .collect(),
+            "company" => std::iter::once(self.query_cache.last_company_symbol.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.company_symbol.as_str()))
+                .collect(),
+            "upsi_id" => std::iter::once(self.query_cache.last_upsi_id.as_str())
+                .chain(self.query_cache.recent_queries.iter().rev().map(|q| q.upsi_id.as_str()))
+                .collect(),
+            other => return Err(format!("Unknown reference kind '{}' - expected entity, company, or upsi_id", other)),
+        };
+
+        let mut ranked = fuzzy_match::resolve_ranked(&partial, candidates.into_iter(), &fuzzy_match::DEFAULT_STRATEGIES, 4).into_iter();
+        let (resolved_value, confidence) = match ranked.next() {
+            Some(m) => (m.value, (m.score * 100.0).round() as u32),
+            None => (partial.clone(), 0),
+        };
+        let alternatives = ranked.map(|m| ReferenceCandidate { value: m.value, confidence: (m.score * 100.0).round() as u32 }).collect();
+
+        Ok(ReferenceResolution { kind, query: partial, resolved_value, confidence, alternatives })
+    }
+
     #[mutate]
     async fn get_upsi(&mut self, upsi_id: String) -> Result<UPSIRecord, String> {
+        self.maintenance_guard()?;
         let resolved_upsi = self.resolve_upsi_id(&upsi_id);
-        
-        self.update_cache("get_upsi", "", "", &resolved_upsi, 
+
+        self.update_cache("get_upsi", "", "", &resolved_upsi,
             &format!("Get UPSI record {}", resolved_upsi));
         
         let endpoint = format!("upsi_records?upsi_id=eq.{}&select=*", resolved_upsi);
@@ -407,60 +1025,134 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         records.into_iter().next().ok_or_else(|| format!("UPSI record {} not found", resolved_upsi))
     }
 
+    /// Record a new UPSI; FINANCIALS and M&A items automatically close the
+    /// company's trading window with an expected re-opening 48h out
     #[mutate]
-    async fn get_active_upsi(&mut self, company_symbol: String) -> Result<Vec<UPSIRecord>, String> {
+    async fn create_upsi(&mut self, company_symbol: String, upsi_type: String, description: String, nature: String, tenant_id: String) -> Result<UPSIRecord, String> {
+        self.maintenance_guard()?;
         let resolved_company = self.resolve_company(&company_symbol);
-        
-        self.update_cache("get_active_upsi", "", &resolved_company, "", 
-            &format!("Get active UPSI for {}", resolved_company));
-        
-        let endpoint = format!("upsi_records?company_symbol=eq.{}&is_public=eq.false&select=*", resolved_company);
-        
-        self.supabase_request(&endpoint, HttpMethod::Get, None).await
+        let now = 0u64;
+
+        self.update_cache("create_upsi", "", &resolved_company, "",
+            &format!("Create {} UPSI for {}", nature, resolved_company));
+
+        self.upsi_counter += 1;
+        let upsi = UPSIRecord {
+            upsi_id: format!("UPSI-{:04}", self.upsi_counter),
+            company_symbol: resolved_company.clone(),
+            upsi_type,
+            description,
+            nature: nature.clone(),
+            created_date: now,
+            public_date: 0,
+            is_public: false,
+            tenant_id,
+        };
+
+        let body = serde_json::to_string(&upsi).map_err(|e| e.to_string())?;
+        let created: Vec<UPSIRecord> = self.supabase_request("upsi_records", HttpMethod::Post, Some(body)).await?;
+
+        let record = created.into_iter().next().unwrap_or(upsi);
+
+        if nature.eq_ignore_ascii_case("FINANCIALS") || nature.eq_ignore_ascii_case("M&A") {
+            self.close_window_for_upsi(&record, now).await;
+        }
+
+        Ok(record)
     }
 
+    /// Single-record counterpart to import_access_logs_csv - use that for bulk backfills
     #[mutate]
-    async fn get_upsi_access_log(&mut self, upsi_id: String, from_timestamp: u64, to_timestamp: u64) -> Result<Vec<UPSIAccessLog>, String> {
-        
+    async fn log_upsi_access(&mut self, upsi_id: String, accessor_entity_id: String, accessor_name: String, accessor_designation: String, access_timestamp: u64, access_reason: String, access_mode: String) -> Result<UPSIAccessLog, String> {
+        self.maintenance_guard()?;
         let resolved_upsi = self.resolve_upsi_id(&upsi_id);
-        
-        // Update cache
-        self.update_cache("get_upsi_access_log", "", "", &resolved_upsi, 
-            &format!("Get access log for UPSI {}", resolved_upsi));
-        
-        let endpoint = format!(
-            "upsi_access_log?upsi_id=eq.{}&access_timestamp=gte.{}&access_timestamp=lte.{}&select=*",
-            resolved_upsi, from_timestamp, to_timestamp
-        );
-        
-        self.supabase_request(&endpoint, HttpMethod::Get, None).await
+
+        self.update_cache("log_upsi_access", &accessor_entity_id, "", &resolved_upsi,
+            &format!("Log UPSI access to {} by {}", resolved_upsi, accessor_entity_id));
+
+        self.access_log_counter += 1;
+        let entry = UPSIAccessLog {
+            access_id: format!("ACC-{:04}", self.access_log_counter),
+            upsi_id: resolved_upsi,
+            accessor_entity_id,
+            accessor_name,
+            accessor_designation,
+            access_timestamp,
+            access_timestamp_ist: epoch_ms_to_ist(access_timestamp),
+            access_reason,
+            access_mode,
+        };
+
+        let body = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+        let inserted: Vec<UPSIAccessLog> = self.supabase_request_as("upsi_access_log", HttpMethod::Post, Some(body), SupabaseAuthMode::ServiceRole).await?;
+
+        Ok(inserted.into_iter().next().unwrap_or(entry))
     }
 
-    /// Get all UPSI accesses by a specific person
     #[mutate]
-    async fn get_access_by_person(&mut self, accessor_entity_id: String, days_back: u32) -> Result<Vec<UPSIAccessLog>, String> {
-        // Resolve partial entity ID
-        let resolved_entity = self.resolve_entity(&accessor_entity_id);
-        
+    async fn get_active_upsi(&mut self, company_symbol: String, tenant_filter: String, limit: u32, offset: u32) -> Result<PaginatedUpsiRecords, String> {
+        self.maintenance_guard()?;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        self.update_cache("get_active_upsi", "", &resolved_company, "",
+            &format!("Get active UPSI for {}", resolved_company));
+
+        let mut endpoint = format!("upsi_records?company_symbol=eq.{}&is_public=eq.false", resolved_company);
+        if !tenant_filter.is_empty() {
+            endpoint.push_str(&format!("&tenant_id=eq.{}", tenant_filter));
+        }
+
+        let (items, total_count, next_offset, has_more) = self.fetch_paginated(&endpoint, limit, offset).await?;
+        Ok(PaginatedUpsiRecords { items, total_count, next_offset, has_more })
+    }
+
+    #[mutate]
+    async fn get_upsi_access_log(&mut self, upsi_id: String, from_timestamp: u64, to_timestamp: u64, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String> {
+        self.maintenance_guard()?;
+
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+
         // Update cache
-        self.update_cache("get_access_by_person", &resolved_entity, "", "", 
+        self.update_cache("get_upsi_access_log", "", "", &resolved_upsi,
+            &format!("Get access log for UPSI {}", resolved_upsi));
+
+        let endpoint = format!(
+            "upsi_access_log?upsi_id=eq.{}&access_timestamp=gte.{}&access_timestamp=lte.{}",
+            resolved_upsi, from_timestamp, to_timestamp
+        );
+
+        let (items, total_count, next_offset, has_more) = self.fetch_paginated(&endpoint, limit, offset).await?;
+        Ok(PaginatedAccessLogs { items, total_count, next_offset, has_more })
+    }
+
+    /// Get all UPSI accesses by a specific person
+    #[mutate]
+    async fn get_access_by_person(&mut self, accessor_entity_id: String, days_back: u32, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String> {
+        self.maintenance_guard()?;
+        // Resolve partial entity ID (or a PAN, via entity_relationship_mcp)
+        let resolved_entity = self.resolve_entity_or_pan(&accessor_entity_id).await;
+
+        // Update cache
+        self.update_cache("get_access_by_person", &resolved_entity, "", "",
             &format!("Get UPSI accesses by {}", resolved_entity));
-        
+
         let now = 1735689600u64;
         let days_in_seconds = days_back as u64 * 86400;
         let start_time = if now > days_in_seconds { now - days_in_seconds } else { 0 };
 
         let endpoint = format!(
-            "upsi_access_log?accessor_entity_id=eq.{}&access_timestamp=gte.{}&select=*",
+            "upsi_access_log?accessor_entity_id=eq.{}&access_timestamp=gte.{}",
             resolved_entity, start_time
         );
-        
-        self.supabase_request(&endpoint, HttpMethod::Get, None).await
+
+        let (items, total_count, next_offset, has_more) = self.fetch_paginated(&endpoint, limit, offset).await?;
+        Ok(PaginatedAccessLogs { items, total_count, next_offset, has_more })
     }
 
     /// Check if an entity had UPSI access before a date
     #[mutate]
     async fn check_upsi_access_before(&mut self, entity_id: String, company_symbol: String, before_timestamp: u64) -> Result<Vec<UPSIAccessLog>, String> {
+        self.maintenance_guard()?;
         // Cross-parameter resolution
         let (resolved_entity, resolved_company, _) = self.resolve_from_cache(&entity_id, &company_symbol, "");
         
@@ -468,29 +1160,37 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         self.update_cache("check_upsi_access_before", &resolved_entity, &resolved_company, "", 
             &format!("Check if {} accessed {} UPSI before trading", resolved_entity, resolved_company));
         
-        let endpoint_logs = format!(
-            "upsi_access_log?accessor_entity_id=eq.{}&access_timestamp=lt.{}&select=*",
-            resolved_entity, before_timestamp
+        // Resolve the company's UPSI IDs in one request, then filter the access
+        // log by that set in a second request, instead of calling get_upsi once
+        // per log row (an HTTP round trip per record).
+        #[derive(Deserialize)]
+        struct UpsiIdRow {
+            upsi_id: String,
+        }
+
+        let endpoint_company_upsi = format!(
+            "upsi_records?company_symbol=eq.{}&select=upsi_id",
+            resolved_company
         );
-        let logs: Vec<UPSIAccessLog> = self.supabase_request(&endpoint_logs, HttpMethod::Get, None).await?;
-        
-        let mut relevant_logs = Vec::new();
-        
-        for log in logs {
-            let record = self.get_upsi(log.upsi_id.clone()).await;
-            if let Ok(r) = record {
-                if r.company_symbol == resolved_company {
-                    relevant_logs.push(log);
-                }
-            }
+        let company_upsi: Vec<UpsiIdRow> = self.supabase_request(&endpoint_company_upsi, HttpMethod::Get, None).await?;
+        if company_upsi.is_empty() {
+            return Ok(Vec::new());
         }
-        
+        let upsi_id_list = company_upsi.iter().map(|r| r.upsi_id.as_str()).collect::<Vec<_>>().join(",");
+
+        let endpoint_logs = format!(
+            "upsi_access_log?accessor_entity_id=eq.{}&access_timestamp=lt.{}&upsi_id=in.({})&select=*",
+            resolved_entity, before_timestamp, upsi_id_list
+        );
+        let relevant_logs: Vec<UPSIAccessLog> = self.supabase_request(&endpoint_logs, HttpMethod::Get, None).await?;
+
         Ok(relevant_logs)
     }
 
     /// Get trading window status for a company
     #[mutate]
     async fn get_trading_window(&mut self, company_symbol: String) -> Result<TradingWindowStatus, String> {
+        self.maintenance_guard()?;
         // Resolve partial company symbol
         let resolved_company = self.resolve_company(&company_symbol);
         
@@ -505,9 +1205,98 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         windows.into_iter().next().ok_or_else(|| format!("Trading window info for {} not found", resolved_company))
     }
 
+    /// Bulk-closes trading windows ahead of quarterly earnings, so compliance
+    /// doesn't need hundreds of individual calls. Each closure is validated
+    /// (symbol required, expected_opening after start) independently, so one bad
+    /// row doesn't fail the whole batch. Upserts by company_symbol: PATCH if a
+    /// window row already exists for the symbol, POST a new one otherwise - same
+    /// GET-then-write idiom get_trading_window/mark_upsi_public already use for
+    /// this table, since PostgREST upsert-on-conflict isn't wired into
+    /// supabase_request here.
+    #[mutate]
+    async fn import_trading_windows(&mut self, windows: Vec<TradingWindowClosure>) -> Result<TradingWindowImportResult, String> {
+        self.maintenance_guard()?;
+
+        let mut imported = 0u32;
+        let mut errors = Vec::new();
+
+        for (index, closure) in windows.into_iter().enumerate() {
+            let symbol = closure.symbol.trim().to_string();
+
+            if symbol.is_empty() {
+                errors.push(TradingWindowImportError {
+                    index: index as u32,
+                    symbol: closure.symbol.clone(),
+                    reason: "symbol is required".to_string(),
+                });
+                continue;
+            }
+            if closure.expected_opening <= closure.start {
+                errors.push(TradingWindowImportError {
+                    index: index as u32,
+                    symbol: symbol.clone(),
+                    reason: format!("expected_opening ({}) must be after start ({})", closure.expected_opening, closure.start),
+                });
+                continue;
+            }
+
+            let window = TradingWindowStatus {
+                company_symbol: symbol.clone(),
+                window_status: "CLOSED".to_string(),
+                closure_reason: closure.reason.clone(),
+                closure_start: closure.start,
+                expected_opening: closure.expected_opening,
+                triggering_upsi_id: "".to_string(),
+            };
+            let body = match serde_json::to_string(&window) {
+                Ok(b) => b,
+                Err(e) => {
+                    errors.push(TradingWindowImportError { index: index as u32, symbol: symbol.clone(), reason: e.to_string() });
+                    continue;
+                }
+            };
+
+            let lookup_endpoint = format!("trading_windows?company_symbol=eq.{}&select=*", symbol);
+            let existing: Result<Vec<TradingWindowStatus>, String> = self.supabase_request(&lookup_endpoint, HttpMethod::Get, None).await;
+            let exists = matches!(existing, Ok(ref rows) if !rows.is_empty());
+
+            let result: Result<Vec<TradingWindowStatus>, String> = if exists {
+                let update_endpoint = format!("trading_windows?company_symbol=eq.{}", symbol);
+                self.supabase_request_as(&update_endpoint, HttpMethod::Patch, Some(body), SupabaseAuthMode::ServiceRole).await
+            } else {
+                self.supabase_request_as("trading_windows", HttpMethod::Post, Some(body), SupabaseAuthMode::ServiceRole).await
+            };
+
+            match result {
+                Ok(_) => {
+                    imported += 1;
+                    self.maybe_push_alert(
+                        "TRADING_WINDOW_CLOSED",
+                        "HIGH",
+                        70,
+                        "",
+                        &symbol,
+                        &format!("Trading window closed for {} (bulk import): {}", symbol, closure.reason),
+                    );
+                }
+                Err(e) => errors.push(TradingWindowImportError { index: index as u32, symbol, reason: e }),
+            }
+        }
+
+        self.update_cache("import_trading_windows", "", "", "",
+            &format!("Bulk import {} trading window closures", imported));
+
+        Ok(TradingWindowImportResult {
+            imported,
+            failed: errors.len() as u32,
+            errors,
+        })
+    }
+
     /// Check if entity traded during closed window
     #[mutate]
     async fn check_window_violation(&mut self, entity_id: String, company_symbol: String, trade_timestamp: u64) -> Result<bool, String> {
+        self.maintenance_guard()?;
         // Cross-parameter resolution
         let (resolved_entity, resolved_company, _) = self.resolve_from_cache(&entity_id, &company_symbol, "");
         
@@ -521,6 +1310,9 @@ impl UPSIDatabase for UPSIDatabaseContractState {
             Ok(window) => {
                 if window.window_status == "CLOSED" {
                     if trade_timestamp >= window.closure_start && trade_timestamp < window.expected_opening {
+                        if self.has_approved_exemption(&resolved_entity, &resolved_company).await {
+                            return Ok(false);
+                        }
                         // Push alert for trading window violation
                         self.maybe_push_alert(
                             "WINDOW_VIOLATION",
@@ -539,50 +1331,898 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         }
     }
 
+    /// Joins UPSI access logs, trading window status, and per-trade correlation
+    /// into one InsiderEvidence object, instead of the caller chaining
+    /// get_upsi_access_log + get_trading_window + check_window_violation once per
+    /// trade. Reuses check_window_violation per trade timestamp so this raises
+    /// the same alerts that method already does for a genuine violation.
+    #[mutate]
+    async fn detect_insider_pattern(&mut self, entity_id: String, company_symbol: String, trade_timestamps: Vec<u64>) -> Result<InsiderEvidence, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity_or_pan(&entity_id).await;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        self.update_cache("detect_insider_pattern", &resolved_entity, &resolved_company, "",
+            &format!("Detect insider trading pattern for {} on {}", resolved_entity, resolved_company));
+
+        let records_endpoint = format!("upsi_records?company_symbol=eq.{}&select=*", resolved_company);
+        let company_records: Vec<UPSIRecord> = self.supabase_request(&records_endpoint, HttpMethod::Get, None).await?;
+
+        let logs_endpoint = format!("upsi_access_log?accessor_entity_id=eq.{}&select=*", resolved_entity);
+        let entity_logs: Vec<UPSIAccessLog> = self.supabase_request(&logs_endpoint, HttpMethod::Get, None).await?;
+
+        let company_upsi_ids: std::collections::HashSet<String> = company_records.iter().map(|r| r.upsi_id.clone()).collect();
+        let mut access_events: Vec<UPSIAccessLog> = entity_logs.into_iter().filter(|log| company_upsi_ids.contains(&log.upsi_id)).collect();
+        access_events.sort_by_key(|log| log.access_timestamp);
+
+        let window_result = self.get_trading_window(resolved_company.clone()).await;
+        let (has_window_data, window_status) = match window_result {
+            Ok(w) => (true, w),
+            Err(_) => (false, TradingWindowStatus {
+                company_symbol: resolved_company.clone(),
+                window_status: "".to_string(),
+                closure_reason: "".to_string(),
+                closure_start: 0,
+                expected_opening: 0,
+                triggering_upsi_id: "".to_string(),
+            }),
+        };
+
+        let mut trade_correlations = Vec::new();
+        let mut risk_score: u32 = 0;
+
+        for trade_timestamp in trade_timestamps {
+            let nearest_access = access_events.iter().filter(|log| log.access_timestamp <= trade_timestamp).max_by_key(|log| log.access_timestamp);
+
+            let (had_upsi_access, nearest_access_id, nearest_access_timestamp, time_delta_ms, before_public_date) = match nearest_access {
+                Some(log) => {
+                    let public_date = company_records.iter().find(|r| r.upsi_id == log.upsi_id).map(|r| r.public_date).unwrap_or(0);
+                    let before_public = public_date > trade_timestamp;
+                    (true, log.access_id.clone(), log.access_timestamp, trade_timestamp - log.access_timestamp, before_public)
+                }
+                None => (false, "".to_string(), 0, 0, false),
+            };
+
+            let window_violation = self.check_window_violation(resolved_entity.clone(), resolved_company.clone(), trade_timestamp).await.unwrap_or(false);
+
+            if window_violation {
+                risk_score += 40;
+            }
+            if had_upsi_access {
+                risk_score += 25;
+            }
+
+            trade_correlations.push(InsiderTradeCorrelation {
+                trade_timestamp,
+                had_upsi_access,
+                nearest_access_id,
+                nearest_access_timestamp,
+                time_delta_ms,
+                before_public_date,
+                window_violation,
+            });
+        }
+
+        Ok(InsiderEvidence {
+            entity_id: resolved_entity,
+            company_symbol: resolved_company,
+            access_events,
+            has_window_data,
+            window_status,
+            trade_correlations,
+            risk_score: risk_score.min(100),
+        })
+    }
+
+    /// Request a pre-clearance for an entity to trade a company's shares during a
+    /// closed window (e.g. an ESOP exercise or a disclosed creeping acquisition).
+    /// Starts PENDING; only approve_window_exemption can move it to APPROVED.
+    #[mutate]
+    async fn request_window_exemption(&mut self, entity_id: String, company_symbol: String, reason: String) -> Result<WindowExemption, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity_or_pan(&entity_id).await;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        self.update_cache("request_window_exemption", &resolved_entity, &resolved_company, "",
+            &format!("Request window exemption for {} on {}: {}", resolved_entity, resolved_company, reason));
+
+        self.exemption_counter += 1;
+        let exemption = WindowExemption {
+            exemption_id: format!("EXM-{:04}", self.exemption_counter),
+            entity_id: resolved_entity,
+            company_symbol: resolved_company,
+            reason,
+            status: "PENDING".to_string(),
+            requested_at: 0,
+            approved_by: "".to_string(),
+            approved_at: 0,
+        };
+
+        let body = serde_json::to_string(&exemption).map_err(|e| e.to_string())?;
+        let created: Vec<WindowExemption> = self.supabase_request("window_exemptions", HttpMethod::Post, Some(body)).await?;
+
+        Ok(created.into_iter().next().unwrap_or(exemption))
+    }
+
+    /// Approve a pending window exemption. Always a separate call from
+    /// request_window_exemption - there's no self-approval path.
+    #[mutate]
+    async fn approve_window_exemption(&mut self, exemption_id: String, approved_by: String) -> Result<WindowExemption, String> {
+        self.maintenance_guard()?;
+        self.update_cache("approve_window_exemption", "", "", "",
+            &format!("{} approves window exemption {}", approved_by, exemption_id));
+
+        let payload = serde_json::json!({ "status": "APPROVED", "approved_by": approved_by, "approved_at": 0 });
+        let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+
+        let endpoint = format!("window_exemptions?exemption_id=eq.{}", exemption_id);
+        let updated: Vec<WindowExemption> = self.supabase_request(&endpoint, HttpMethod::Patch, Some(body)).await?;
+
+        updated.into_iter().next().ok_or_else(|| format!("Window exemption {} not found", exemption_id))
+    }
+
     /// Get all entities who accessed a specific UPSI
     #[mutate]
-    async fn get_upsi_accessors(&mut self, upsi_id: String) -> Result<Vec<UPSIAccessLog>, String> {
+    async fn get_upsi_accessors(&mut self, upsi_id: String, limit: u32, offset: u32) -> Result<PaginatedAccessLogs, String> {
+        self.maintenance_guard()?;
         // Resolve partial UPSI ID
         let resolved_upsi = self.resolve_upsi_id(&upsi_id);
-        
+
         // Update cache
-        self.update_cache("get_upsi_accessors", "", "", &resolved_upsi, 
+        self.update_cache("get_upsi_accessors", "", "", &resolved_upsi,
             &format!("Get all accessors of UPSI {}", resolved_upsi));
-        
-        let endpoint = format!("upsi_access_log?upsi_id=eq.{}&select=*", resolved_upsi);
+
+        let endpoint = format!("upsi_access_log?upsi_id=eq.{}", resolved_upsi);
+        let (items, total_count, next_offset, has_more) = self.fetch_paginated(&endpoint, limit, offset).await?;
+        Ok(PaginatedAccessLogs { items, total_count, next_offset, has_more })
+    }
+
+    /// Record that a UPSI was passed from one person to another and why - PIT
+    /// rules require tracking sharing, not just access
+    #[mutate]
+    async fn record_upsi_sharing(&mut self, upsi_id: String, shared_by: String, shared_with: String, purpose: String, timestamp: u64) -> Result<UPSISharingEvent, String> {
+        self.maintenance_guard()?;
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+        self.sharing_log_counter += 1;
+
+        let event = UPSISharingEvent {
+            sharing_id: format!("SHARE-{:04}", self.sharing_log_counter),
+            upsi_id: resolved_upsi.clone(),
+            shared_by: shared_by.clone(),
+            shared_with: shared_with.clone(),
+            purpose,
+            timestamp,
+        };
+
+        let body = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+        let created: Vec<UPSISharingEvent> = self.supabase_request("upsi_sharing_log", HttpMethod::Post, Some(body)).await?;
+        let record = created.into_iter().next().unwrap_or(event);
+
+        self.update_cache("record_upsi_sharing", "", "", &resolved_upsi,
+            &format!("Recorded UPSI {} shared from {} to {}", resolved_upsi, shared_by, shared_with));
+
+        Ok(record)
+    }
+
+    /// The full propagation tree for a UPSI, plus the same events pre-rendered
+    /// as node/edge graph JSON for overlay on the entity relationship view
+    #[mutate]
+    async fn get_sharing_chain(&mut self, upsi_id: String) -> Result<SharingChain, String> {
+        self.maintenance_guard()?;
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+
+        self.update_cache("get_sharing_chain", "", "", &resolved_upsi,
+            &format!("Get sharing chain for UPSI {}", resolved_upsi));
+
+        let endpoint = format!("upsi_sharing_log?upsi_id=eq.{}&select=*&order=timestamp.asc", resolved_upsi);
+        let events: Vec<UPSISharingEvent> = self.supabase_request(&endpoint, HttpMethod::Get, None).await?;
+
+        let mut nodes: Vec<String> = Vec::new();
+        for event in &events {
+            if !nodes.contains(&event.shared_by) {
+                nodes.push(event.shared_by.clone());
+            }
+            if !nodes.contains(&event.shared_with) {
+                nodes.push(event.shared_with.clone());
+            }
+        }
+
+        let graph = serde_json::json!({
+            "nodes": nodes.iter().map(|id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+            "edges": events.iter().map(|e| serde_json::json!({
+                "source": e.shared_by,
+                "target": e.shared_with,
+                "purpose": e.purpose,
+                "timestamp": e.timestamp,
+            })).collect::<Vec<_>>(),
+        });
+
+        Ok(SharingChain {
+            upsi_id: resolved_upsi,
+            events,
+            graph_json: serde_json::to_string(&graph).unwrap_or_default(),
+        })
+    }
+
+    /// Assemble a CSV of access-log rows from chunks (single-row inserts won't scale
+    /// to corporate log volumes), validating the header and each row before a single
+    /// batch insert once the last chunk arrives
+    #[mutate]
+    async fn import_access_logs_csv(&mut self, upsi_id: String, csv_chunk: String, chunk_index: u32, total_chunks: u32) -> Result<CsvImportSummary, String> {
+        self.maintenance_guard()?;
+        if total_chunks == 0 || chunk_index >= total_chunks {
+            return Err(format!("chunk_index {} out of range for total_chunks {}", chunk_index, total_chunks));
+        }
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+
+        let pending = match self.pending_csv_imports.iter_mut().find(|p| p.upsi_id == resolved_upsi) {
+            Some(p) => p,
+            None => {
+                self.pending_csv_imports.push(PendingCsvImport {
+                    upsi_id: resolved_upsi.clone(),
+                    total_chunks,
+                    chunks: vec![String::new(); total_chunks as usize],
+                    received: vec![false; total_chunks as usize],
+                });
+                self.pending_csv_imports.last_mut().unwrap()
+            }
+        };
+        if pending.total_chunks != total_chunks {
+            return Err(format!("total_chunks mismatch for {}: import in progress expects {}", resolved_upsi, pending.total_chunks));
+        }
+        pending.chunks[chunk_index as usize] = csv_chunk;
+        pending.received[chunk_index as usize] = true;
+        let chunks_received = pending.received.iter().filter(|r| **r).count() as u32;
+
+        if chunks_received < total_chunks {
+            return Ok(CsvImportSummary {
+                upsi_id: resolved_upsi,
+                complete: false,
+                chunks_received,
+                total_chunks,
+                rows_imported: 0,
+                rows_failed: 0,
+                errors: Vec::new(),
+            });
+        }
+
+        let assembled = pending.chunks.concat();
+        self.pending_csv_imports.retain(|p| p.upsi_id != resolved_upsi);
+
+        let mut lines = assembled.lines();
+        let header = lines.next().unwrap_or("").trim();
+        const EXPECTED_HEADER: &str = "accessor_entity_id,accessor_name,accessor_designation,access_timestamp,access_reason,access_mode";
+        if header != EXPECTED_HEADER {
+            return Err(format!("Invalid CSV header for {}: expected \"{}\", got \"{}\"", resolved_upsi, EXPECTED_HEADER, header));
+        }
+
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let row_number = i as u32 + 2; // +1 for header row, +1 for 1-based numbering
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != 6 {
+                errors.push(CsvImportRowError { row_number, reason: format!("expected 6 columns, got {}", fields.len()) });
+                continue;
+            }
+            let access_timestamp: u64 = match fields[3].trim().parse() {
+                Ok(t) => t,
+                Err(_) => {
+                    errors.push(CsvImportRowError { row_number, reason: format!("invalid access_timestamp \"{}\"", fields[3]) });
+                    continue;
+                }
+            };
+
+            self.access_log_counter += 1;
+            rows.push(UPSIAccessLog {
+                access_id: format!("ACC-{:04}", self.access_log_counter),
+                upsi_id: resolved_upsi.clone(),
+                accessor_entity_id: fields[0].trim().to_string(),
+                accessor_name: fields[1].trim().to_string(),
+                accessor_designation: fields[2].trim().to_string(),
+                access_timestamp,
+                access_timestamp_ist: epoch_ms_to_ist(access_timestamp),
+                access_reason: fields[4].trim().to_string(),
+                access_mode: fields[5].trim().to_string(),
+            });
+        }
+
+        let rows_imported = if rows.is_empty() {
+            0
+        } else {
+            let body = serde_json::to_string(&rows).map_err(|e| e.to_string())?;
+            let inserted: Vec<UPSIAccessLog> = self.supabase_request_as("upsi_access_log", HttpMethod::Post, Some(body), SupabaseAuthMode::ServiceRole).await?;
+            inserted.len() as u32
+        };
+
+        self.update_cache("import_access_logs_csv", "", "", &resolved_upsi,
+            &format!("Import access log CSV for UPSI {}", resolved_upsi));
+
+        Ok(CsvImportSummary {
+            upsi_id: resolved_upsi,
+            complete: true,
+            chunks_received,
+            total_chunks,
+            rows_imported,
+            rows_failed: errors.len() as u32,
+            errors,
+        })
+    }
+
+    /// Mark a UPSI record as public and re-evaluate monitoring tied to it
+    #[mutate]
+    async fn mark_upsi_public(&mut self, upsi_id: String, public_date: u64) -> Result<UPSIRecord, String> {
+        self.maintenance_guard()?;
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+
+        self.update_cache("mark_upsi_public", "", "", &resolved_upsi,
+            &format!("Mark UPSI {} as public", resolved_upsi));
+
+        let payload = serde_json::json!({
+            "is_public": true,
+            "public_date": public_date,
+        });
+        let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+
+        let endpoint = format!("upsi_records?upsi_id=eq.{}", resolved_upsi);
+        let updated: Vec<UPSIRecord> = self.supabase_request(&endpoint, HttpMethod::Patch, Some(body)).await?;
+
+        let record = updated.into_iter().next()
+            .ok_or_else(|| format!("UPSI record {} not found", resolved_upsi))?;
+
+        // Publication is now explainable - downgrade stale pre-publication alerts
+        // and append a timeline note to any cases tied to this company.
+        self.maybe_close_monitoring(&record.company_symbol, &resolved_upsi);
+
+        Ok(record)
+    }
+
+    /// Compares trades in `window_days` before the UPSI's public_date against
+    /// `window_days` after, as a rough check on whether the leaked information was
+    /// actually exploited. This platform has no historical price/volume time-series
+    /// client (only a current-quote and current-RSI feed via Alpha Vantage/TAAPI),
+    /// so "before/after" is built from this platform's own executed trades for the
+    /// symbol rather than an external market data series, and the price change is
+    /// not benchmark-adjusted.
+    #[mutate]
+    async fn analyze_upsi_price_impact(&mut self, upsi_id: String, window_days: u32) -> Result<UpsiPriceImpactReport, String> {
+        self.maintenance_guard()?;
+        let resolved_upsi = self.resolve_upsi_id(&upsi_id);
+
+        self.update_cache("analyze_upsi_price_impact", "", "", &resolved_upsi,
+            &format!("Analyze price impact of UPSI {} over {} day window", resolved_upsi, window_days));
+
+        let endpoint = format!("upsi_records?upsi_id=eq.{}&select=*", resolved_upsi);
+        let records: Vec<UPSIRecord> = self.supabase_request(&endpoint, HttpMethod::Get, None).await?;
+        let upsi = records.into_iter().next()
+            .ok_or_else(|| format!("UPSI record {} not found", resolved_upsi))?;
+
+        if !upsi.is_public || upsi.public_date == 0 {
+            return Err(format!("UPSI {} has not been made public yet - nothing to compare before/after", resolved_upsi));
+        }
+
+        let config = self.secrets.config();
+        if config.trade_data_contract_id.is_empty() {
+            return Err("trade_data_contract_id not configured".to_string());
+        }
+        let trade_data_mcp = TradeDataMcp::new(config.trade_data_contract_id.clone());
+        let trades = trade_data_mcp.get_trades_by_symbol(upsi.company_symbol.clone(), 1000)
+            .map_err(|e| e.to_string())?;
+
+        let window_seconds = window_days as u64 * 86400;
+        let window_start = upsi.public_date.saturating_sub(window_seconds);
+        let window_end = upsi.public_date.saturating_add(window_seconds);
+
+        let before: Vec<_> = trades.iter()
+            .filter(|t| t.timestamp >= window_start && t.timestamp < upsi.public_date)
+            .collect();
+        let after: Vec<_> = trades.iter()
+            .filter(|t| t.timestamp >= upsi.public_date && t.timestamp <= window_end)
+            .collect();
+
+        let avg_price = |ts: &[&trade_data::Trade]| -> f64 {
+            if ts.is_empty() {
+                return 0.0;
+            }
+            let sum: f64 = ts.iter().filter_map(|t| t.price.parse::<f64>().ok()).sum();
+            sum / ts.len() as f64
+        };
+        let volume = |ts: &[&trade_data::Trade]| -> u64 { ts.iter().map(|t| t.quantity).sum() };
+
+        let avg_price_before = avg_price(&before);
+        let avg_price_after = avg_price(&after);
+        let volume_before = volume(&before);
+        let volume_after = volume(&after);
+
+        let price_change_pct = if avg_price_before > 0.0 {
+            (avg_price_after - avg_price_before) / avg_price_before * 100.0
+        } else {
+            0.0
+        };
+        let volume_change_pct = if volume_before > 0 {
+            (volume_after as f64 - volume_before as f64) / volume_before as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        // Directional heuristic: a POSITIVE UPSI should see price rise after
+        // publication, a NEGATIVE UPSI should see it fall. A move against that
+        // direction, or a large move paired with a volume surge beforehand
+        // (trading ahead of the public disclosure), suggests the leak was traded on.
+        let expected_direction_confirmed = match upsi.nature.to_uppercase().as_str() {
+            "POSITIVE" => price_change_pct > 0.0,
+            "NEGATIVE" => price_change_pct < 0.0,
+            _ => false,
+        };
+        let pre_disclosure_volume_surge = volume_before > 0 && volume_after > 0
+            && volume_before as f64 > volume_after as f64 * 1.5;
+        let likely_exploited = expected_direction_confirmed && (price_change_pct.abs() > 5.0 || pre_disclosure_volume_surge);
+
+        let report = UpsiPriceImpactReport {
+            upsi_id: resolved_upsi.clone(),
+            company_symbol: upsi.company_symbol.clone(),
+            window_days,
+            trades_before: before.len() as u32,
+            trades_after: after.len() as u32,
+            avg_price_before: format!("{:.2}", avg_price_before),
+            avg_price_after: format!("{:.2}", avg_price_after),
+            price_change_pct: format!("{:.2}", price_change_pct),
+            volume_before,
+            volume_after,
+            volume_change_pct: format!("{:.2}", volume_change_pct),
+            abnormal_return_estimate: format!("{:.2}", price_change_pct),
+            likely_exploited,
+        };
+
+        if likely_exploited {
+            self.maybe_push_alert(
+                "UPSI_PRICE_IMPACT",
+                "HIGH",
+                75,
+                "",
+                &upsi.company_symbol,
+                &format!("UPSI {} shows abnormal {}% price move after publication, consistent with pre-disclosure trading", resolved_upsi, report.price_change_pct),
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Add a designated person to the register and mirror it into Neo4j as an INSIDER_OF edge
+    #[mutate]
+    async fn add_designated_person(&mut self, entity_id: String, company_symbol: String, designation: String, effective_from: u64) -> Result<DesignatedPerson, String> {
+        self.maintenance_guard()?;
+        let resolved_entity = self.resolve_entity_or_pan(&entity_id).await;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        self.update_cache("add_designated_person", &resolved_entity, &resolved_company, "",
+            &format!("Add {} as designated person for {}", resolved_entity, resolved_company));
+
+        self.dp_counter += 1;
+        let dp = DesignatedPerson {
+            dp_id: format!("DP-{:04}", self.dp_counter),
+            entity_id: resolved_entity.clone(),
+            company_symbol: resolved_company.clone(),
+            designation: designation.clone(),
+            effective_from,
+            active: true,
+        };
+
+        let body = serde_json::to_string(&dp).map_err(|e| e.to_string())?;
+        let created: Vec<DesignatedPerson> = self.supabase_request("designated_persons", HttpMethod::Post, Some(body)).await?;
+
+        let record = created.into_iter().next().unwrap_or(dp);
+
+        self.maybe_sync_insider_edge(&record.entity_id, &record.company_symbol, &record.designation, record.effective_from, true);
+
+        Ok(record)
+    }
+
+    /// Remove a designated person from the register and revoke the mirrored Neo4j edge
+    #[mutate]
+    async fn remove_designated_person(&mut self, dp_id: String) -> Result<DesignatedPerson, String> {
+        self.maintenance_guard()?;
+        self.update_cache("remove_designated_person", "", "", "",
+            &format!("Remove designated person {}", dp_id));
+
+        let payload = serde_json::json!({ "active": false });
+        let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+
+        let endpoint = format!("designated_persons?dp_id=eq.{}", dp_id);
+        let updated: Vec<DesignatedPerson> = self.supabase_request(&endpoint, HttpMethod::Patch, Some(body)).await?;
+
+        let record = updated.into_iter().next()
+            .ok_or_else(|| format!("Designated person {} not found", dp_id))?;
+
+        self.maybe_sync_insider_edge(&record.entity_id, &record.company_symbol, &record.designation, record.effective_from, false);
+
+        Ok(record)
+    }
+
+    /// List active designated persons for a company
+    #[mutate]
+    async fn list_designated_persons(&mut self, company_symbol: String) -> Result<Vec<DesignatedPerson>, String> {
+        self.maintenance_guard()?;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        self.update_cache("list_designated_persons", "", &resolved_company, "",
+            &format!("List designated persons for {}", resolved_company));
+
+        let endpoint = format!("designated_persons?company_symbol=eq.{}&active=eq.true&select=*", resolved_company);
+
         self.supabase_request(&endpoint, HttpMethod::Get, None).await
     }
-
-    #[query]
-    fn tools(&self) -> String {
-        r#"[
+
+    /// Broadcasts a trading-window closure to every active designated person for
+    /// the company over Slack, and records who was notified and when so the
+    /// notification itself is part of the PIT compliance trail, not just the
+    /// closure. Best-effort per recipient: one failed send doesn't stop the rest,
+    /// and every attempt - success or failure - gets its own recorded row.
+    #[mutate]
+    async fn notify_window_closure(&mut self, company_symbol: String) -> Result<Vec<WindowClosureNotification>, String> {
+        self.maintenance_guard()?;
+        let resolved_company = self.resolve_company(&company_symbol);
+
+        let window = self.get_trading_window(resolved_company.clone()).await?;
+        let recipients = self.list_designated_persons(resolved_company.clone()).await?;
+
+        let slack_contract_id = self.secrets.config().slack_contract_id.clone();
+        let message = format!(
+            "Trading window closed for {}: {} (expected opening {})",
+            resolved_company, window.closure_reason, window.expected_opening
+        );
+
+        let mut notifications = Vec::new();
+        for dp in recipients {
+            let (success, error) = if slack_contract_id.is_empty() {
+                (false, "slack_contract_id not configured".to_string())
+            } else {
+                #[derive(Debug, Serialize)]
+                struct SendMessageArgs {
+                    channel: String,
+                    message: String,
+                }
+                #[derive(Debug, Deserialize)]
+                struct SlackNotificationResult {
+                    success: bool,
+                    error: String,
+                }
+
+                let args = serde_json::to_string(&SendMessageArgs {
+                    channel: dp.designation.clone(),
+                    message: message.clone(),
+                }).unwrap_or_default();
+
+                match Runtime::call_contract::<SlackNotificationResult>(
+                    slack_contract_id.clone(),
+                    "send_message".to_string(),
+                    Some(args),
+                ) {
+                    Ok(resp) => (resp.success, resp.error),
+                    Err(e) => (false, format!("{:?}", e)),
+                }
+            };
+
+            let notification = WindowClosureNotification {
+                company_symbol: resolved_company.clone(),
+                dp_id: dp.dp_id.clone(),
+                entity_id: dp.entity_id.clone(),
+                designation: dp.designation.clone(),
+                notified_at: 0, // No real clock in this crate - same 0 placeholder maybe_push_alert uses
+                success,
+                error,
+            };
+
+            if let Ok(body) = serde_json::to_string(&notification) {
+                let _: Result<Vec<WindowClosureNotification>, String> =
+                    self.supabase_request("window_closure_notifications", HttpMethod::Post, Some(body)).await;
+            }
+
+            notifications.push(notification);
+        }
+
+        Ok(notifications)
+    }
+
+    /// Record that a sensitive config field was rotated in the secret store. supabase_request
+    /// re-reads self.secrets.config() on every call, so the new key is already live - this
+    /// just gives operators an auditable confirmation that the rotation took effect.
+    #[mutate]
+    fn rotate_secret(&mut self, field_name: String, rotated_at: u64) -> Result<SecretVersionEntry, String> {
+        self.maintenance_guard()?;
+        let known_fields = ["supabase_anon_key", "supabase_service_key"];
+        if !known_fields.contains(&field_name.as_str()) {
+            return Err(format!("Unknown rotatable field '{}': expected one of {:?}", field_name, known_fields));
+        }
+
+        for entry in self.secret_versions.iter_mut() {
+            if entry.field_name == field_name {
+                entry.version += 1;
+                entry.rotated_at = rotated_at;
+                return Ok(entry.clone());
+            }
+        }
+
+        let entry = SecretVersionEntry {
+            field_name,
+            version: 1,
+            rotated_at,
+        };
+        self.secret_versions.push(entry.clone());
+        Ok(entry)
+    }
+
+    #[query]
+    fn get_secret_versions(&self) -> Vec<SecretVersionEntry> {
+        self.secret_versions.clone()
+    }
+
+    #[query]
+    fn get_circuit_status(&self, host: String) -> CircuitStatus {
+        self.outbound_guard.status(&host)
+    }
+
+    #[mutate]
+    fn set_maintenance_mode(&mut self, enabled: bool, message: String) -> MaintenanceStatus {
+        self.maintenance = MaintenanceStatus { enabled, message };
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn get_maintenance_status(&self) -> MaintenanceStatus {
+        self.maintenance.clone()
+    }
+
+    #[query]
+    fn tools(&self) -> String {
+        r#"[
+  {
+    "type": "function",
+    "function": {
+      "name": "get_context",
+      "description": "DO NOT CALL THIS - internal test function only.\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_upsi",
+      "description": "Get UPSI record by ID\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "upsi_id": {
+            "type": "string",
+            "description": "UPSI record ID (e.g., UPSI-001)\n"
+          }
+        },
+        "required": [
+          "upsi_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "create_upsi",
+      "description": "Record a new UPSI; FINANCIALS and M&A items auto-close the trading window\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "company_symbol": {"type": "string", "description": "Company symbol - supports fuzzy matching"},
+          "upsi_type": {"type": "string", "description": "Category of UPSI (e.g., EARNINGS, MERGER)"},
+          "description": {"type": "string", "description": "Description of the price-sensitive information"},
+          "nature": {"type": "string", "description": "Nature of the UPSI (e.g., FINANCIALS, M&A)"},
+          "tenant_id": {"type": "string", "description": "Owning tenant for multi-tenant deployments; empty for single-tenant"}
+        },
+        "required": ["company_symbol", "upsi_type", "description", "nature", "tenant_id"]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_active_upsi",
+      "description": "Get all active (non-public) UPSI for a company\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "company_symbol": {
+            "type": "string",
+            "description": "Company stock symbol (e.g., RELIANCE, INFY, TCS)\n"
+          },
+          "tenant_filter": {
+            "type": "string",
+            "description": "Best-effort convenience filter, not an enforced isolation boundary; empty returns all tenants\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "Max rows to return in this page\n"
+          },
+          "offset": {
+            "type": "integer",
+            "description": "Rows to skip before this page starts\n"
+          }
+        },
+        "required": [
+          "company_symbol",
+          "tenant_filter",
+          "limit",
+          "offset"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_upsi_access_log",
+      "description": "Get access log for specific UPSI with optional time range\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "upsi_id": {
+            "type": "string",
+            "description": "UPSI record ID\n"
+          },
+          "from_timestamp": {
+            "type": "integer",
+            "description": "Start timestamp (optional)\n"
+          },
+          "to_timestamp": {
+            "type": "integer",
+            "description": "End timestamp (optional)\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "Max rows to return in this page\n"
+          },
+          "offset": {
+            "type": "integer",
+            "description": "Rows to skip before this page starts\n"
+          }
+        },
+        "required": [
+          "upsi_id",
+          "limit",
+          "offset"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_access_by_person",
+      "description": "Get all UPSI accesses by a specific person\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "accessor_entity_id": {
+            "type": "string",
+            "description": "Entity ID of the accessor (e.g., ENT-REL-001)\n"
+          },
+          "days_back": {
+            "type": "integer",
+            "description": "Number of days to look back (default: 30)\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "Max rows to return in this page\n"
+          },
+          "offset": {
+            "type": "integer",
+            "description": "Rows to skip before this page starts\n"
+          }
+        },
+        "required": [
+          "accessor_entity_id",
+          "limit",
+          "offset"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "check_upsi_access_before",
+      "description": "Check if entity had UPSI access before a date (for insider trading detection)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity ID to check\n"
+          },
+          "company_symbol": {
+            "type": "string",
+            "description": "Company symbol\n"
+          },
+          "before_timestamp": {
+            "type": "integer",
+            "description": "Check access before this timestamp\n"
+          }
+        },
+        "required": [
+          "entity_id",
+          "company_symbol"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_trading_window",
+      "description": "Get trading window status for a company\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "company_symbol": {
+            "type": "string",
+            "description": "Company symbol\n"
+          }
+        },
+        "required": [
+          "company_symbol"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
-      "name": "get_context",
-      "description": "DO NOT CALL THIS - internal test function only.\n",
+      "name": "import_trading_windows",
+      "description": "Bulk-close trading windows ahead of quarterly earnings; each closure is validated independently and upserted by symbol\n",
       "parameters": {
         "type": "object",
-        "properties": {},
-        "required": []
+        "properties": {
+          "windows": {
+            "type": "array",
+            "description": "Closures to import (symbol, start, expected_opening, reason)\n"
+          }
+        },
+        "required": [
+          "windows"
+        ]
       }
     }
   },
   {
     "type": "function",
     "function": {
-      "name": "get_upsi",
-      "description": "Get UPSI record by ID\n",
+      "name": "check_window_violation",
+      "description": "Check if entity traded during closed window\n",
       "parameters": {
         "type": "object",
         "properties": {
-          "upsi_id": {
+          "entity_id": {
             "type": "string",
-            "description": "UPSI record ID (e.g., UPSI-001)\n"
+            "description": "Entity ID\n"
+          },
+          "company_symbol": {
+            "type": "string",
+            "description": "Company symbol\n"
+          },
+          "trade_timestamp": {
+            "type": "integer",
+            "description": "Timestamp of the trade\n"
           }
         },
         "required": [
-          "upsi_id"
+          "entity_id",
+          "company_symbol",
+          "trade_timestamp"
         ]
       }
     }
@@ -590,18 +2230,28 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_active_upsi",
-      "description": "Get all active (non-public) UPSI for a company\n",
+      "name": "request_window_exemption",
+      "description": "Request a pre-clearance for an entity to trade during a closed window (e.g. an ESOP exercise or a disclosed creeping acquisition); starts PENDING and requires a separate approve_window_exemption call\n",
       "parameters": {
         "type": "object",
         "properties": {
+          "entity_id": {
+            "type": "string",
+            "description": "Entity ID\n"
+          },
           "company_symbol": {
             "type": "string",
-            "description": "Company stock symbol (e.g., RELIANCE, INFY, TCS)\n"
+            "description": "Company symbol\n"
+          },
+          "reason": {
+            "type": "string",
+            "description": "Reason for the exemption\n"
           }
         },
         "required": [
-          "company_symbol"
+          "entity_id",
+          "company_symbol",
+          "reason"
         ]
       }
     }
@@ -609,8 +2259,32 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_upsi_access_log",
-      "description": "Get access log for specific UPSI with optional time range\n",
+      "name": "approve_window_exemption",
+      "description": "Approve a pending window exemption; always a separate call from the request\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "exemption_id": {
+            "type": "string",
+            "description": "ID of the exemption to approve\n"
+          },
+          "approved_by": {
+            "type": "string",
+            "description": "Identity of the approver\n"
+          }
+        },
+        "required": [
+          "exemption_id",
+          "approved_by"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "mark_upsi_public",
+      "description": "Mark a UPSI record as public and re-evaluate open monitoring tied to it (downgrades stale pre-publication alerts, notes linked cases)\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -618,17 +2292,14 @@ impl UPSIDatabase for UPSIDatabaseContractState {
             "type": "string",
             "description": "UPSI record ID\n"
           },
-          "from_timestamp": {
-            "type": "integer",
-            "description": "Start timestamp (optional)\n"
-          },
-          "to_timestamp": {
+          "public_date": {
             "type": "integer",
-            "description": "End timestamp (optional)\n"
+            "description": "Timestamp at which the UPSI became public\n"
           }
         },
         "required": [
-          "upsi_id"
+          "upsi_id",
+          "public_date"
         ]
       }
     }
@@ -636,22 +2307,23 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_access_by_person",
-      "description": "Get all UPSI accesses by a specific person\n",
+      "name": "analyze_upsi_price_impact",
+      "description": "Compare trades before/after a UPSI's public_date to check whether the leaked information was actually traded on\n",
       "parameters": {
         "type": "object",
         "properties": {
-          "accessor_entity_id": {
+          "upsi_id": {
             "type": "string",
-            "description": "Entity ID of the accessor (e.g., ENT-REL-001)\n"
+            "description": "UPSI record ID\n"
           },
-          "days_back": {
+          "window_days": {
             "type": "integer",
-            "description": "Number of days to look back (default: 30)\n"
+            "description": "Number of days to compare before and after publication\n"
           }
         },
         "required": [
-          "accessor_entity_id"
+          "upsi_id",
+          "window_days"
         ]
       }
     }
@@ -659,26 +2331,70 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "check_upsi_access_before",
-      "description": "Check if entity had UPSI access before a date (for insider trading detection)\n",
+      "name": "add_designated_person",
+      "description": "Add a designated person to the register and mirror it into Neo4j as an INSIDER_OF edge\n",
       "parameters": {
         "type": "object",
         "properties": {
           "entity_id": {
             "type": "string",
-            "description": "Entity ID to check\n"
+            "description": "Entity ID of the person - supports fuzzy matching\n"
           },
           "company_symbol": {
             "type": "string",
-            "description": "Company symbol\n"
+            "description": "Company stock symbol - supports fuzzy matching\n"
           },
-          "before_timestamp": {
+          "designation": {
+            "type": "string",
+            "description": "Designation of the person (e.g., CFO, Company Secretary)\n"
+          },
+          "effective_from": {
             "type": "integer",
-            "description": "Check access before this timestamp\n"
+            "description": "Timestamp from which the designation is effective\n"
           }
         },
         "required": [
           "entity_id",
+          "company_symbol",
+          "designation",
+          "effective_from"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "remove_designated_person",
+      "description": "Remove a designated person from the register and revoke the mirrored Neo4j edge\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "dp_id": {
+            "type": "string",
+            "description": "Designated person record ID (e.g., DP-0001)\n"
+          }
+        },
+        "required": [
+          "dp_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_designated_persons",
+      "description": "List active designated persons for a company\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "company_symbol": {
+            "type": "string",
+            "description": "Company stock symbol - supports fuzzy matching\n"
+          }
+        },
+        "required": [
           "company_symbol"
         ]
       }
@@ -687,14 +2403,14 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_trading_window",
-      "description": "Get trading window status for a company\n",
+      "name": "notify_window_closure",
+      "description": "Broadcasts a trading-window closure over Slack to every active designated person for the company, and records who was notified and when\n",
       "parameters": {
         "type": "object",
         "properties": {
           "company_symbol": {
             "type": "string",
-            "description": "Company symbol\n"
+            "description": "Company stock symbol - supports fuzzy matching\n"
           }
         },
         "required": [
@@ -706,28 +2422,67 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "check_window_violation",
-      "description": "Check if entity traded during closed window\n",
+      "name": "get_upsi_accessors",
+      "description": "Get all entities who accessed a specific UPSI\n",
       "parameters": {
         "type": "object",
         "properties": {
-          "entity_id": {
+          "upsi_id": {
             "type": "string",
-            "description": "Entity ID\n"
+            "description": "UPSI record ID\n"
           },
-          "company_symbol": {
+          "limit": {
+            "type": "integer",
+            "description": "Max rows to return in this page\n"
+          },
+          "offset": {
+            "type": "integer",
+            "description": "Rows to skip before this page starts\n"
+          }
+        },
+        "required": [
+          "upsi_id",
+          "limit",
+          "offset"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "record_upsi_sharing",
+      "description": "Record that a UPSI was passed from one person to another and why\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "upsi_id": {
             "type": "string",
-            "description": "Company symbol\n"
+            "description": "UPSI record ID\n"
           },
-          "trade_timestamp": {
+          "shared_by": {
+            "type": "string",
+            "description": "Entity ID or name of the person sharing the UPSI\n"
+          },
+          "shared_with": {
+            "type": "string",
+            "description": "Entity ID or name of the person receiving the UPSI\n"
+          },
+          "purpose": {
+            "type": "string",
+            "description": "Business reason for the sharing\n"
+          },
+          "timestamp": {
             "type": "integer",
-            "description": "Timestamp of the trade\n"
+            "description": "Timestamp of the sharing event\n"
           }
         },
         "required": [
-          "entity_id",
-          "company_symbol",
-          "trade_timestamp"
+          "upsi_id",
+          "shared_by",
+          "shared_with",
+          "purpose",
+          "timestamp"
         ]
       }
     }
@@ -735,8 +2490,8 @@ impl UPSIDatabase for UPSIDatabaseContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_upsi_accessors",
-      "description": "Get all entities who accessed a specific UPSI\n",
+      "name": "get_sharing_chain",
+      "description": "Get the full sharing/propagation chain for a UPSI, with a graph JSON view for overlay on the entity relationship graph\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -750,6 +2505,131 @@ impl UPSIDatabase for UPSIDatabaseContractState {
         ]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "import_access_logs_csv",
+      "description": "Import a chunk of a CSV of access-log rows; once the last chunk arrives the rows are validated and batch-inserted in one call\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "upsi_id": {
+            "type": "string",
+            "description": "UPSI record ID the access logs belong to\n"
+          },
+          "csv_chunk": {
+            "type": "string",
+            "description": "Raw text of this chunk; chunk 0 must include the header row\n"
+          },
+          "chunk_index": {
+            "type": "integer",
+            "description": "0-based index of this chunk\n"
+          },
+          "total_chunks": {
+            "type": "integer",
+            "description": "Total number of chunks in this import\n"
+          }
+        },
+        "required": [
+          "upsi_id",
+          "csv_chunk",
+          "chunk_index",
+          "total_chunks"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "rotate_secret",
+      "description": "Record that a sensitive config field (supabase_anon_key) was rotated in the secret store\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "field_name": {
+            "type": "string",
+            "description": "Name of the rotated config field\n"
+          },
+          "rotated_at": {
+            "type": "integer",
+            "description": "Timestamp of the rotation\n"
+          }
+        },
+        "required": [
+          "field_name",
+          "rotated_at"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_secret_versions",
+      "description": "Get rotation metadata (field name, version, timestamp) for sensitive config fields, values excluded\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_circuit_status",
+      "description": "Get the outbound rate-limiter/circuit-breaker status for a host\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "host": {
+            "type": "string",
+            "description": "Host to check, e.g. the configured Supabase URL\n"
+          }
+        },
+        "required": [
+          "host"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "set_maintenance_mode",
+      "description": "Enable/disable maintenance mode; while enabled, mutating methods return an error instead of writing partial state\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "enabled": {
+            "type": "boolean",
+            "description": "Whether maintenance mode should be on\n"
+          },
+          "message": {
+            "type": "string",
+            "description": "Banner message to surface to callers while maintenance mode is on\n"
+          }
+        },
+        "required": [
+          "enabled",
+          "message"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_maintenance_status",
+      "description": "Get the current maintenance-mode banner (enabled flag and message)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
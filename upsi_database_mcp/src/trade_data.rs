@@ -0,0 +1,50 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use weil_rs::runtime::Runtime;
+
+pub struct TradeDataMcp {
+    contract_id: String,
+}
+
+impl TradeDataMcp {
+    pub fn new(contract_id: String) -> Self {
+        TradeDataMcp { contract_id }
+    }
+}
+
+// ===== Response Types =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Trade {
+    pub trade_id: String,
+    pub symbol: String,
+    pub account_id: String,
+    pub trade_type: String,
+    pub quantity: u64,
+    pub price: String,
+    pub value: String,
+    pub exchange: String,
+    pub segment: String,
+    pub timestamp: u64,
+    pub order_id: String,
+}
+
+impl TradeDataMcp {
+    pub fn get_trades_by_symbol(&self, symbol: String, limit: u32) -> Result<Vec<Trade>> {
+        #[derive(Debug, Serialize)]
+        struct GetTradesBySymbolArgs {
+            symbol: String,
+            limit: u32,
+        }
+
+        let serialized_args = Some(serde_json::to_string(&GetTradesBySymbolArgs { symbol, limit })?);
+
+        let resp = Runtime::call_contract::<Vec<Trade>>(
+            self.contract_id.clone(),
+            "get_trades_by_symbol".to_string(),
+            serialized_args,
+        )?;
+
+        Ok(resp)
+    }
+}